@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+pub const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+/// Administers a deployed GitHub oracle contract.
+#[derive(Debug, Parser)]
+#[command(name = "github-oracle", version)]
+pub struct Cli {
+    /// Oracle contract address (C...)
+    #[arg(long, env = "ORACLE_CONTRACT_ID")]
+    pub contract_id: String,
+    /// Soroban RPC endpoint, required unless --dry-run is given
+    #[arg(long, env = "ORACLE_RPC_URL")]
+    pub rpc_url: Option<String>,
+    #[arg(long, env = "ORACLE_NETWORK_PASSPHRASE", default_value = TESTNET_PASSPHRASE)]
+    pub network_passphrase: String,
+    /// Secret key (S...) of the account sending transactions
+    #[arg(long, env = "ORACLE_SECRET_KEY", hide_env_values = true)]
+    pub secret_key: Option<String>,
+    #[arg(long, default_value_t = 100)]
+    pub base_fee: u32,
+    /// Simulate against a local ledger snapshot instead of the network
+    #[arg(long, value_name = "SNAPSHOT")]
+    pub dry_run: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    pub output: Format,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Initializes the contract with an admin, limits and starting repos
+    Config(ConfigArgs),
+    #[command(subcommand)]
+    Repos(ReposCommand),
+    #[command(subcommand)]
+    Issues(IssuesCommand),
+    /// Swaps the contract code for an uploaded wasm
+    Upgrade {
+        /// Hex encoded hash of the uploaded wasm
+        wasm_hash: String,
+    },
+    #[command(subcommand)]
+    Roles(RolesCommand),
+    /// Diffs a GitHub export against the registry and plans the calls to converge
+    Reconcile(ReconcileArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigArgs {
+    #[arg(long)]
+    pub admin: String,
+    #[arg(long, default_value_t = 256)]
+    pub max_repos: u32,
+    #[arg(long, default_value_t = 512)]
+    pub max_issues_per_repo: u32,
+    /// Who adds and removes repos: the admin, or registered voters
+    #[arg(long, value_enum, default_value_t = Governance::Admin)]
+    pub repo_governance: Governance,
+    #[arg(long, default_value_t = 17_280)]
+    pub voting_period_ledgers: u32,
+    /// Votes, or token-weighted votes, a repo change needs to pass
+    #[arg(long, required_if_eq("repo_governance", "voting"))]
+    pub quorum: Option<i128>,
+    /// Weigh votes by the voter's balance of this token (C...)
+    #[arg(long)]
+    pub weight_token: Option<String>,
+    #[command(flatten)]
+    pub repos: RepoInput,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Governance {
+    Admin,
+    Voting,
+}
+
+/// Repo names given inline, from a CSV or JSON file, or both.
+#[derive(Debug, Args)]
+pub struct RepoInput {
+    pub names: Vec<String>,
+    /// CSV with a repo column, or a JSON array of names
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+/// Issues for one repo given inline, rows from a CSV or JSON file, or both.
+#[derive(Debug, Args)]
+pub struct IssueInput {
+    /// Repo the inline issues belong to
+    #[arg(long, requires = "issues")]
+    pub repo: Option<String>,
+    pub issues: Vec<String>,
+    /// CSV with repo and issue columns, or a JSON array of {"repo", "issue"} objects
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ReconcileArgs {
+    /// JSON array from the REST issues endpoint, repeatable
+    #[arg(long, required = true)]
+    pub issues: Vec<PathBuf>,
+    /// JSON array from the REST pulls endpoint, repeatable
+    #[arg(long)]
+    pub pulls: Vec<PathBuf>,
+    /// Saved `issues list --output json` to compare against instead of the contract
+    #[arg(long)]
+    pub state: Option<PathBuf>,
+    /// JSON file mapping GitHub logins to payout accounts
+    #[arg(long)]
+    pub contributors: Option<PathBuf>,
+    /// Claw stale claims back to this account (G... or C...) instead of only reporting them
+    #[arg(long)]
+    pub clawback_to: Option<String>,
+    /// Send the planned calls instead of only listing them
+    #[arg(long)]
+    pub apply: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ReposCommand {
+    Add(RepoInput),
+    Remove(RepoInput),
+    List,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum IssuesCommand {
+    Add(IssueInput),
+    Remove(IssueInput),
+    List {
+        /// Only list the issues of this repo
+        #[arg(long)]
+        repo: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RolesCommand {
+    /// Shows the admin, signers, attestor, dust recipient and voters
+    Show,
+    SetAttestor {
+        address: String,
+    },
+    SetDustRecipient {
+        address: String,
+    },
+    /// Replaces the multisig signers, given as ADDRESS=WEIGHT
+    SetSigners {
+        #[arg(long)]
+        threshold: u32,
+        #[arg(long, default_value_t = 17_280)]
+        proposal_expiry_ledgers: u32,
+        #[arg(required = true)]
+        signers: Vec<String>,
+    },
+    RegisterVoters {
+        #[arg(required = true)]
+        addresses: Vec<String>,
+    },
+    RemoveVoters {
+        #[arg(required = true)]
+        addresses: Vec<String>,
+    },
+}
@@ -0,0 +1,189 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use github_oracle::GithubOracleContract;
+use github_oracle_relayer::config::contract_id;
+use github_oracle_relayer::rpc::HttpRpc;
+use github_oracle_relayer::scval::address_strkey;
+use github_oracle_relayer::tx::Signer;
+use github_oracle_relayer::Invoker;
+use soroban_ledger_snapshot::LedgerSnapshot;
+use soroban_sdk::testutils::EnvTestConfig;
+use soroban_sdk::xdr::ScVal;
+use soroban_sdk::{Address, Env, Symbol, TryFromVal, Val, Vec};
+
+use crate::error::Error;
+use crate::invocation::Invocation;
+
+/// What happened to a state-changing call.
+#[derive(Clone, Debug)]
+pub enum Outcome {
+    /// Applied to the in-memory copy of a snapshot, nothing was sent.
+    Simulated,
+    /// Sent to the network under this transaction hash.
+    Sent(String),
+}
+
+/// Somewhere oracle invocations can be run: a local snapshot or the network.
+pub trait Backend {
+    /// Env used to encode arguments; for snapshots it also holds the ledger.
+    fn env(&self) -> &Env;
+    /// Runs a view and returns what it produced.
+    fn read(&self, invocation: Invocation) -> Result<ScVal, Error>;
+    fn write(&self, invocation: Invocation) -> Result<Outcome, Error>;
+    /// Address writes are made as, passed where an entrypoint takes a caller.
+    fn caller(&self) -> Result<String, Error>;
+}
+
+/// Runs invocations against a ledger snapshot with every auth mocked.
+///
+/// The current contract code is registered over the snapshotted instance, so
+/// a dry run exercises this build against the deployed state.
+pub struct SnapshotBackend {
+    env: Env,
+    contract: Address,
+}
+
+impl SnapshotBackend {
+    pub fn load(path: &Path, contract: &str) -> Result<SnapshotBackend, Error> {
+        contract_id(contract)?;
+        let snapshot = LedgerSnapshot::read_file(path)
+            .map_err(|err| Error::Input(format!("{}: {err}", path.display())))?;
+        let mut env = Env::from_ledger_snapshot(snapshot);
+        env.set_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+
+        //mocked auths draw nonces from the base prng, which every snapshot env
+        //seeds identically, so reseed to avoid colliding with recorded nonces
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+            .to_le_bytes();
+        let mut seed = [0; 32];
+        seed[..16].copy_from_slice(&nanos);
+        seed[16..].copy_from_slice(&nanos);
+        env.host()
+            .set_base_prng_seed(seed)
+            .map_err(|err| Error::Input(format!("{err:?}")))?;
+
+        let contract = Address::from_string(&soroban_sdk::String::from_str(&env, contract));
+        env.register_contract(Some(&contract), GithubOracleContract);
+        env.mock_all_auths();
+        Ok(SnapshotBackend { env, contract })
+    }
+
+    fn invoke(&self, invocation: Invocation) -> Result<Val, Error> {
+        let failed = |reason: String| Error::Invocation {
+            function: invocation.function.clone(),
+            reason,
+        };
+
+        match self.env.try_invoke_contract::<Val, soroban_sdk::Error>(
+            &self.contract,
+            &Symbol::new(&self.env, &invocation.function),
+            invocation.args.clone(),
+        ) {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(err)) => Err(failed(format!("{err:?}"))),
+            Err(Ok(err)) => Err(failed(describe(err))),
+            Err(Err(err)) => Err(failed(format!("{err:?}"))),
+        }
+    }
+}
+
+impl Backend for SnapshotBackend {
+    fn env(&self) -> &Env {
+        &self.env
+    }
+
+    fn read(&self, invocation: Invocation) -> Result<ScVal, Error> {
+        let function = invocation.function.clone();
+        let value = self.invoke(invocation)?;
+        ScVal::try_from_val(&self.env, &value)
+            .map_err(|_| Error::Input(format!("`{function}` returned an unencodable value")))
+    }
+
+    fn write(&self, invocation: Invocation) -> Result<Outcome, Error> {
+        self.invoke(invocation)?;
+        Ok(Outcome::Simulated)
+    }
+
+    //every auth is mocked, so act as the admin, who may make any call
+    fn caller(&self) -> Result<String, Error> {
+        let admin = self.read(Invocation::build(&self.env, |oracle| oracle.try_admin()))?;
+        match admin {
+            ScVal::Address(address) => Ok(address_strkey(&address)),
+            _ => Err(Error::Input("the oracle has no admin".to_string())),
+        }
+    }
+}
+
+/// Simulates views and signs and sends writes through a Soroban RPC server.
+pub struct RpcBackend {
+    env: Env,
+    invoker: Invoker<HttpRpc>,
+}
+
+impl RpcBackend {
+    pub fn new(
+        rpc_url: &str,
+        network_passphrase: &str,
+        secret_key: &str,
+        contract: &str,
+        base_fee: u32,
+    ) -> Result<RpcBackend, Error> {
+        let signer = Signer::from_secret(secret_key, network_passphrase)?;
+        let invoker = Invoker::new(
+            signer,
+            HttpRpc::new(rpc_url),
+            contract_id(contract)?,
+            base_fee,
+        );
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        Ok(RpcBackend { env, invoker })
+    }
+
+    fn encode(&self, args: Vec<Val>) -> Result<std::vec::Vec<ScVal>, Error> {
+        args.iter()
+            .map(|arg| {
+                ScVal::try_from_val(&self.env, &arg)
+                    .map_err(|_| Error::Input("argument cannot be encoded".to_string()))
+            })
+            .collect()
+    }
+}
+
+impl Backend for RpcBackend {
+    fn env(&self) -> &Env {
+        &self.env
+    }
+
+    fn read(&self, invocation: Invocation) -> Result<ScVal, Error> {
+        let args = self.encode(invocation.args)?;
+        let simulation = self.invoker.simulate(&invocation.function, args)?;
+        Ok(simulation.result.unwrap_or(ScVal::Void))
+    }
+
+    fn write(&self, invocation: Invocation) -> Result<Outcome, Error> {
+        let args = self.encode(invocation.args)?;
+        Ok(Outcome::Sent(
+            self.invoker.send(&invocation.function, args)?,
+        ))
+    }
+
+    fn caller(&self) -> Result<String, Error> {
+        Ok(self.invoker.signer().account_id())
+    }
+}
+
+fn describe(err: soroban_sdk::Error) -> String {
+    if err.is_type(soroban_sdk::xdr::ScErrorType::Contract) {
+        format!("contract error #{}", err.get_code())
+    } else {
+        format!("{err:?}")
+    }
+}
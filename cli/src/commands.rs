@@ -0,0 +1,483 @@
+use github_oracle::types::config_data::ConfigData;
+use github_oracle::types::repo_governance::{RepoGovernance, VotingConfig};
+use github_oracle::types::signer_set::SignerSet;
+use github_oracle_relayer::reconcile::{claim_candidates, reconcile as reconcile_export, Drift};
+use github_oracle_relayer::scval::to_json;
+use github_oracle_relayer::{ContractCall, Contributors, GithubExport, OracleState};
+use serde_json::{json, Map, Value};
+use soroban_sdk::xdr::ScVal;
+use soroban_sdk::{Address, BytesN, Env};
+use stellar_strkey::Strkey;
+
+use crate::args::{
+    Command, ConfigArgs, Governance, IssueInput, IssuesCommand, ReconcileArgs, RepoInput,
+    ReposCommand, RolesCommand,
+};
+use crate::backend::{Backend, Outcome};
+use crate::error::Error;
+use crate::input::{group_by_repo, read_issues, read_repos};
+use crate::invocation::Invocation;
+
+/// Result of a command, ready to be rendered.
+pub struct Report {
+    pub headers: &'static [&'static str],
+    pub value: Value,
+}
+
+const CALL_HEADERS: &[&str] = &["call", "target", "outcome"];
+
+pub fn execute(backend: &dyn Backend, command: &Command) -> Result<Report, Error> {
+    let env = backend.env();
+    match command {
+        Command::Config(args) => config(backend, args),
+        Command::Repos(ReposCommand::Add(input)) => {
+            let repos = collect_repos(input)?;
+            let names = strings(env, &repos);
+            let invocation = Invocation::build(env, |oracle| oracle.try_add_repos(&names));
+            calls(vec![call(backend, repos.join(","), invocation)?])
+        }
+        Command::Repos(ReposCommand::Remove(input)) => {
+            let repos = collect_repos(input)?;
+            let names = strings(env, &repos);
+            let invocation = Invocation::build(env, |oracle| oracle.try_remove_repos(&names));
+            calls(vec![call(backend, repos.join(","), invocation)?])
+        }
+        Command::Repos(ReposCommand::List) => Ok(Report {
+            headers: &["repo"],
+            value: to_json(&backend.read(Invocation::build(env, |oracle| oracle.try_get_repos()))?),
+        }),
+        Command::Issues(IssuesCommand::Add(input)) => issues(backend, IssueChange::Add, input),
+        Command::Issues(IssuesCommand::Remove(input)) => {
+            issues(backend, IssueChange::Remove, input)
+        }
+        Command::Issues(IssuesCommand::List { repo: Some(repo) }) => {
+            let repo = soroban_sdk::String::from_str(env, repo);
+            let invocation = Invocation::build(env, |oracle| oracle.try_get_issues_for_repo(&repo));
+            Ok(Report {
+                headers: &["issue", "status"],
+                value: to_json(&backend.read(invocation)?),
+            })
+        }
+        Command::Issues(IssuesCommand::List { repo: None }) => Ok(Report {
+            headers: &["repo", "issue", "status"],
+            value: to_json(&backend.read(Invocation::build(env, |oracle| {
+                oracle.try_get_repos_and_issues()
+            }))?),
+        }),
+        Command::Upgrade { wasm_hash } => {
+            let hash: [u8; 32] = hex::decode(wasm_hash)
+                .ok()
+                .and_then(|hash| hash.try_into().ok())
+                .ok_or_else(|| Error::Input(format!("{wasm_hash} is not a 32 byte hex hash")))?;
+            let hash = BytesN::from_array(env, &hash);
+            let invocation = Invocation::build(env, |oracle| oracle.try_update_contract(&hash));
+            calls(vec![call(backend, wasm_hash.clone(), invocation)?])
+        }
+        Command::Roles(command) => roles(backend, command),
+        Command::Reconcile(args) => reconcile(backend, args),
+    }
+}
+
+fn config(backend: &dyn Backend, args: &ConfigArgs) -> Result<Report, Error> {
+    let env = backend.env();
+    let config = ConfigData {
+        admin: address(env, &args.admin)?,
+        repos: strings(env, &collect_repos_or_none(&args.repos)?),
+        max_repos: args.max_repos,
+        max_issues_per_repo: args.max_issues_per_repo,
+        repo_governance: repo_governance(env, args)?,
+    };
+    let invocation = Invocation::build(env, |oracle| oracle.try_config(&config));
+    calls(vec![call(backend, args.admin.clone(), invocation)?])
+}
+
+fn repo_governance(env: &Env, args: &ConfigArgs) -> Result<RepoGovernance, Error> {
+    Ok(match args.repo_governance {
+        Governance::Admin => RepoGovernance::Admin,
+        Governance::Voting => RepoGovernance::Voting(VotingConfig {
+            voting_period_ledgers: args.voting_period_ledgers,
+            //clap requires a quorum whenever voting is chosen
+            quorum: args.quorum.unwrap_or_default(),
+            weight_token: match &args.weight_token {
+                Some(token) => Some(address(env, token)?),
+                None => None,
+            },
+        }),
+    })
+}
+
+#[derive(Clone, Copy)]
+enum IssueChange {
+    Add,
+    Remove,
+}
+
+//one call per repo, in the order repos first appear in the input
+fn issues(backend: &dyn Backend, change: IssueChange, input: &IssueInput) -> Result<Report, Error> {
+    let env = backend.env();
+    let mut rows: Vec<(String, String)> = match &input.repo {
+        Some(repo) => input
+            .issues
+            .iter()
+            .map(|issue| (repo.clone(), issue.clone()))
+            .collect(),
+        None => Vec::new(),
+    };
+    if let Some(file) = &input.file {
+        rows.extend(read_issues(file)?);
+    }
+    if rows.is_empty() {
+        return Err(Error::Input("no issues given".to_string()));
+    }
+
+    let mut results = Vec::new();
+    for (repo, issues) in group_by_repo(rows) {
+        let name = soroban_sdk::String::from_str(env, &repo);
+        let issues = strings(env, &issues);
+        let invocation = match change {
+            IssueChange::Add => {
+                Invocation::build(env, |oracle| oracle.try_add_issues(&name, &issues))
+            }
+            IssueChange::Remove => {
+                Invocation::build(env, |oracle| oracle.try_remove_issues(&name, &issues))
+            }
+        };
+        results.push(call(backend, repo, invocation)?);
+    }
+    calls(results)
+}
+
+fn roles(backend: &dyn Backend, command: &RolesCommand) -> Result<Report, Error> {
+    let env = backend.env();
+    match command {
+        RolesCommand::Show => {
+            let mut roles = Map::new();
+            for (role, invocation) in [
+                ("admin", Invocation::build(env, |oracle| oracle.try_admin())),
+                (
+                    "signers",
+                    Invocation::build(env, |oracle| oracle.try_get_signers()),
+                ),
+                (
+                    "attestor",
+                    Invocation::build(env, |oracle| oracle.try_attestor()),
+                ),
+                (
+                    "dust_recipient",
+                    Invocation::build(env, |oracle| oracle.try_dust_recipient()),
+                ),
+                (
+                    "voters",
+                    Invocation::build(env, |oracle| oracle.try_get_voters()),
+                ),
+            ] {
+                roles.insert(role.to_string(), to_json(&backend.read(invocation)?));
+            }
+            Ok(Report {
+                headers: &["role", "value"],
+                value: Value::Object(roles),
+            })
+        }
+        RolesCommand::SetAttestor { address: attestor } => {
+            let account = address(env, attestor)?;
+            let invocation = Invocation::build(env, |oracle| oracle.try_set_attestor(&account));
+            calls(vec![call(backend, attestor.clone(), invocation)?])
+        }
+        RolesCommand::SetDustRecipient { address: recipient } => {
+            let account = address(env, recipient)?;
+            let invocation =
+                Invocation::build(env, |oracle| oracle.try_set_dust_recipient(&account));
+            calls(vec![call(backend, recipient.clone(), invocation)?])
+        }
+        RolesCommand::SetSigners {
+            threshold,
+            proposal_expiry_ledgers,
+            signers,
+        } => {
+            let mut weights = soroban_sdk::Map::new(env);
+            for signer in signers {
+                let (signer, weight) = signer
+                    .split_once('=')
+                    .and_then(|(signer, weight)| Some((signer, weight.parse::<u32>().ok()?)))
+                    .ok_or_else(|| Error::Input(format!("{signer} is not ADDRESS=WEIGHT")))?;
+                weights.set(address(env, signer)?, weight);
+            }
+            let signer_set = SignerSet {
+                signers: weights,
+                threshold: *threshold,
+                proposal_expiry_ledgers: *proposal_expiry_ledgers,
+            };
+            let invocation = Invocation::build(env, |oracle| oracle.try_set_signers(&signer_set));
+            calls(vec![call(
+                backend,
+                format!("{} signers", signers.len()),
+                invocation,
+            )?])
+        }
+        RolesCommand::RegisterVoters { addresses: voters } => {
+            let accounts = addresses(env, voters)?;
+            let invocation = Invocation::build(env, |oracle| oracle.try_register_voters(&accounts));
+            calls(vec![call(backend, voters.join(","), invocation)?])
+        }
+        RolesCommand::RemoveVoters { addresses: voters } => {
+            let accounts = addresses(env, voters)?;
+            let invocation = Invocation::build(env, |oracle| oracle.try_remove_voters(&accounts));
+            calls(vec![call(backend, voters.join(","), invocation)?])
+        }
+    }
+}
+
+fn reconcile(backend: &dyn Backend, args: &ReconcileArgs) -> Result<Report, Error> {
+    let env = backend.env();
+    let mut export = GithubExport::default();
+    for path in &args.issues {
+        export.add_issues(&std::fs::read(path)?)?;
+    }
+    for path in &args.pulls {
+        export.add_pulls(&std::fs::read(path)?)?;
+    }
+
+    let mut state: OracleState = match &args.state {
+        Some(path) => OracleState::from_json(&std::fs::read(path)?)?,
+        None => serde_json::from_value(to_json(
+            &backend.read(Invocation::build(env, |oracle| {
+                oracle.try_get_repos_and_issues()
+            }))?,
+        ))?,
+    };
+    let contributors = match &args.contributors {
+        Some(path) => Contributors::from_json(&std::fs::read(path)?)?,
+        None => Contributors::default(),
+    };
+    let caller = backend.caller()?;
+    let clawback_to = match &args.clawback_to {
+        Some(recipient) => {
+            address(env, recipient)?;
+            Some(recipient.as_str())
+        }
+        None => None,
+    };
+    //a completed issue that is open again only holds a claim while its payout vests
+    for (repo, issue) in claim_candidates(&export, &state) {
+        let name = soroban_sdk::String::from_str(env, &repo);
+        let issue_id = soroban_sdk::String::from_str(env, &issue);
+        let invocation = Invocation::build(env, |oracle| oracle.try_get_vesting(&name, &issue_id));
+        let vesting = to_json(&backend.read(invocation)?);
+        if let Some(claimant) = vesting["shares"]
+            .as_object()
+            .and_then(|shares| shares.keys().next())
+        {
+            state.mark_claimed(&repo, &issue, claimant);
+        }
+    }
+    //escrowed issues are never removed, so look up the escrow of every removal candidate
+    for planned in reconcile_export(&export, &state, &caller, &contributors, clawback_to).calls {
+        if let ContractCall::RemoveIssues { repo, issues } = planned {
+            for issue in &issues {
+                let name = soroban_sdk::String::from_str(env, &repo);
+                let issue_id = soroban_sdk::String::from_str(env, issue);
+                let invocation =
+                    Invocation::build(env, |oracle| oracle.try_get_escrow(&name, &issue_id));
+                if backend.read(invocation)? != ScVal::Void {
+                    state.mark_escrowed(&repo, issue);
+                }
+            }
+        }
+    }
+    let reconciliation = reconcile_export(&export, &state, &caller, &contributors, clawback_to);
+
+    let drift: Vec<Value> = reconciliation
+        .drift
+        .iter()
+        .map(|drift| match drift {
+            Drift::MissingRepo { repo } => record(drift.kind(), repo, "", ""),
+            Drift::MissingIssue { repo, issue } | Drift::UnknownIssue { repo, issue } => {
+                record(drift.kind(), repo, issue, "")
+            }
+            Drift::StaleClaim {
+                repo,
+                issue,
+                claimant,
+            } => record(drift.kind(), repo, issue, &format!("claimed by {claimant}")),
+            Drift::StatusMismatch {
+                repo,
+                issue,
+                github,
+                chain,
+            } => record(
+                drift.kind(),
+                repo,
+                issue,
+                &format!("github {}, chain {chain}", github.as_str()),
+            ),
+        })
+        .collect();
+
+    let mut calls = Vec::new();
+    for planned in &reconciliation.calls {
+        let (repo, issues) = match planned {
+            ContractCall::AddRepos { repos } => (repos.join(","), String::new()),
+            ContractCall::AddIssues { repo, issues }
+            | ContractCall::RemoveIssues { repo, issues } => (repo.clone(), issues.join(",")),
+            ContractCall::CompleteIssue { repo, issue, .. }
+            | ContractCall::SetIssueLabels { repo, issue, .. }
+            | ContractCall::Clawback { repo, issue, .. } => (repo.clone(), issue.clone()),
+            ContractCall::ConfirmRepoVerification { repo, .. } => (repo.clone(), String::new()),
+        };
+        let outcome = if args.apply {
+            outcome(backend.write(invocation(env, planned)?)?)
+        } else {
+            "planned".to_string()
+        };
+        calls.push(record(planned.function_name(), &repo, &issues, &outcome));
+    }
+
+    Ok(Report {
+        headers: &["section", "kind", "repo", "issue", "detail"],
+        value: json!({ "drift": drift, "calls": calls }),
+    })
+}
+
+fn record(kind: &str, repo: &str, issue: &str, detail: &str) -> Value {
+    json!({ "kind": kind, "repo": repo, "issue": issue, "detail": detail })
+}
+
+fn outcome(outcome: Outcome) -> String {
+    match outcome {
+        Outcome::Simulated => "simulated".to_string(),
+        Outcome::Sent(hash) => hash,
+    }
+}
+
+//the relayer encodes calls for its own use, here they go through the typed client
+fn invocation(env: &Env, planned: &ContractCall) -> Result<Invocation, Error> {
+    Ok(match planned {
+        ContractCall::AddRepos { repos } => {
+            let repos = strings(env, repos);
+            Invocation::build(env, |oracle| oracle.try_add_repos(&repos))
+        }
+        ContractCall::AddIssues { repo, issues } => {
+            let (repo, issues) = (
+                soroban_sdk::String::from_str(env, repo),
+                strings(env, issues),
+            );
+            Invocation::build(env, |oracle| oracle.try_add_issues(&repo, &issues))
+        }
+        ContractCall::RemoveIssues { repo, issues } => {
+            let (repo, issues) = (
+                soroban_sdk::String::from_str(env, repo),
+                strings(env, issues),
+            );
+            Invocation::build(env, |oracle| oracle.try_remove_issues(&repo, &issues))
+        }
+        ContractCall::SetIssueLabels {
+            caller,
+            repo,
+            issue,
+            labels,
+        } => {
+            let caller = address(env, caller)?;
+            let repo = soroban_sdk::String::from_str(env, repo);
+            let issue = soroban_sdk::String::from_str(env, issue);
+            let labels = strings(env, labels);
+            Invocation::build(env, |oracle| {
+                oracle.try_set_issue_labels(&caller, &repo, &issue, &labels)
+            })
+        }
+        ContractCall::CompleteIssue {
+            caller,
+            repo,
+            issue,
+            shares,
+        } => {
+            let caller = address(env, caller)?;
+            let repo = soroban_sdk::String::from_str(env, repo);
+            let issue = soroban_sdk::String::from_str(env, issue);
+            let mut payouts = soroban_sdk::Vec::new(env);
+            for (recipient, basis_points) in shares {
+                payouts.push_back((address(env, recipient)?, *basis_points));
+            }
+            Invocation::build(env, |oracle| {
+                oracle.try_complete_issue(&caller, &repo, &issue, &payouts)
+            })
+        }
+        ContractCall::ConfirmRepoVerification {
+            repo,
+            requester,
+            nonce,
+        } => {
+            let repo = soroban_sdk::String::from_str(env, repo);
+            let requester = address(env, requester)?;
+            Invocation::build(env, |oracle| {
+                oracle.try_confirm_repo_verification(&repo, &requester, nonce)
+            })
+        }
+        ContractCall::Clawback {
+            repo,
+            issue,
+            recipient,
+        } => {
+            let repo = soroban_sdk::String::from_str(env, repo);
+            let issue = soroban_sdk::String::from_str(env, issue);
+            let recipient = address(env, recipient)?;
+            Invocation::build(env, |oracle| oracle.try_clawback(&repo, &issue, &recipient))
+        }
+    })
+}
+
+fn call(backend: &dyn Backend, target: String, invocation: Invocation) -> Result<Value, Error> {
+    let function = invocation.function.clone();
+    let outcome = outcome(backend.write(invocation)?);
+    Ok(json!({ "call": function, "target": target, "outcome": outcome }))
+}
+
+fn calls(results: Vec<Value>) -> Result<Report, Error> {
+    Ok(Report {
+        headers: CALL_HEADERS,
+        value: Value::Array(results),
+    })
+}
+
+fn collect_repos(input: &RepoInput) -> Result<Vec<String>, Error> {
+    let repos = collect_repos_or_none(input)?;
+    if repos.is_empty() {
+        return Err(Error::Input("no repos given".to_string()));
+    }
+    Ok(repos)
+}
+
+fn collect_repos_or_none(input: &RepoInput) -> Result<Vec<String>, Error> {
+    let mut repos = input.names.clone();
+    if let Some(file) = &input.file {
+        repos.extend(read_repos(file)?);
+    }
+    Ok(repos)
+}
+
+fn strings(env: &Env, values: &[String]) -> soroban_sdk::Vec<soroban_sdk::String> {
+    let mut strings = soroban_sdk::Vec::new(env);
+    for value in values {
+        strings.push_back(soroban_sdk::String::from_str(env, value));
+    }
+    strings
+}
+
+//the host panics on malformed strkeys, so they are checked up front
+fn address(env: &Env, strkey: &str) -> Result<Address, Error> {
+    match Strkey::from_string(strkey) {
+        Ok(Strkey::PublicKeyEd25519(_) | Strkey::Contract(_)) => Ok(Address::from_string(
+            &soroban_sdk::String::from_str(env, strkey),
+        )),
+        _ => Err(Error::Input(format!(
+            "{strkey} is not an account or contract address"
+        ))),
+    }
+}
+
+fn addresses(env: &Env, strkeys: &[String]) -> Result<soroban_sdk::Vec<Address>, Error> {
+    let mut addresses = soroban_sdk::Vec::new(env);
+    for strkey in strkeys {
+        addresses.push_back(address(env, strkey)?);
+    }
+    Ok(addresses)
+}
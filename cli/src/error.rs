@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid input: {0}")]
+    Input(String),
+    #[error("missing setting: {0}")]
+    Config(String),
+    #[error("`{function}` failed: {reason}")]
+    Invocation { function: String, reason: String },
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Relayer(#[from] github_oracle_relayer::Error),
+}
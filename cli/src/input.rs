@@ -0,0 +1,109 @@
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Reads repo names from a CSV file with one repo per row, or a JSON array.
+pub fn read_repos(path: &Path) -> Result<Vec<String>, Error> {
+    match extension(path)? {
+        Kind::Csv => Ok(read_csv(path, "repo")?
+            .into_iter()
+            .map(|mut row| row.swap_remove(0))
+            .collect()),
+        Kind::Json => match read_json(path)? {
+            Value::Array(items) => items.iter().map(text).collect(),
+            _ => Err(Error::Input(format!(
+                "{}: expected an array of repo names",
+                path.display()
+            ))),
+        },
+    }
+}
+
+/// Reads `(repo, issue)` rows from a CSV file, or a JSON array of
+/// `{"repo", "issue"}` objects.
+pub fn read_issues(path: &Path) -> Result<Vec<(String, String)>, Error> {
+    match extension(path)? {
+        Kind::Csv => read_csv(path, "repo")?
+            .into_iter()
+            .map(|row| match row.as_slice() {
+                [repo, issue, ..] => Ok((repo.clone(), issue.clone())),
+                _ => Err(Error::Input(format!(
+                    "{}: expected repo and issue columns",
+                    path.display()
+                ))),
+            })
+            .collect(),
+        Kind::Json => match read_json(path)? {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| Ok((text(&item["repo"])?, text(&item["issue"])?)))
+                .collect(),
+            _ => Err(Error::Input(format!(
+                "{}: expected an array of issues",
+                path.display()
+            ))),
+        },
+    }
+}
+
+/// Groups rows by repo, keeping the order repos first appear in.
+pub fn group_by_repo(rows: Vec<(String, String)>) -> Vec<(String, Vec<String>)> {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for (repo, issue) in rows {
+        match groups.iter_mut().find(|(name, _)| *name == repo) {
+            Some((_, issues)) => issues.push(issue),
+            None => groups.push((repo, vec![issue])),
+        }
+    }
+    groups
+}
+
+enum Kind {
+    Csv,
+    Json,
+}
+
+fn extension(path: &Path) -> Result<Kind, Error> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => Ok(Kind::Csv),
+        Some("json") => Ok(Kind::Json),
+        _ => Err(Error::Input(format!(
+            "{}: expected a .csv or .json file",
+            path.display()
+        ))),
+    }
+}
+
+//rows of a headerless or headed CSV, the header is recognised by its first column
+fn read_csv(path: &Path, header: &str) -> Result<Vec<Vec<String>>, Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record?;
+        if record.iter().all(str::is_empty) || (index == 0 && record.get(0) == Some(header)) {
+            continue;
+        }
+        rows.push(record.iter().map(str::to_string).collect());
+    }
+    Ok(rows)
+}
+
+fn read_json(path: &Path) -> Result<Value, Error> {
+    Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+}
+
+//issue numbers are accepted as JSON numbers as well as strings
+fn text(value: &Value) -> Result<String, Error> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        other => Err(Error::Input(format!("expected a string, found {other}"))),
+    }
+}
@@ -0,0 +1,40 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use github_oracle::GithubOracleContractClient;
+use soroban_sdk::testutils::ContractFunctionSet;
+use soroban_sdk::{Env, IntoVal, Val, Vec};
+
+/// One oracle entrypoint with its encoded arguments.
+#[derive(Clone, Debug)]
+pub struct Invocation {
+    pub function: String,
+    pub args: Vec<Val>,
+}
+
+impl Invocation {
+    /// Builds the invocation from the one call `call` makes on the generated client, so
+    /// function names, arity and argument types are checked against the contract at compile
+    /// time. The call reaches a stand-in contract that records it and returns nothing, which
+    /// is why `call` goes through a `try_` method, whose result is dropped.
+    pub fn build<R>(env: &Env, call: impl FnOnce(&GithubOracleContractClient) -> R) -> Invocation {
+        let recorded = Rc::new(RefCell::new(None));
+        let recorder = env.register_contract(None, Recorder(recorded.clone()));
+        call(&GithubOracleContractClient::new(env, &recorder));
+
+        let invocation = recorded.borrow_mut().take();
+        invocation.expect("the client call did not reach the contract")
+    }
+}
+
+struct Recorder(Rc<RefCell<Option<Invocation>>>);
+
+impl ContractFunctionSet for Recorder {
+    fn call(&self, func: &str, env: Env, args: &[Val]) -> Option<Val> {
+        *self.0.borrow_mut() = Some(Invocation {
+            function: func.to_string(),
+            args: Vec::from_slice(&env, args),
+        });
+        Some(().into_val(&env))
+    }
+}
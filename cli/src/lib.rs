@@ -0,0 +1,43 @@
+pub mod args;
+pub mod backend;
+pub mod commands;
+pub mod error;
+pub mod input;
+pub mod invocation;
+pub mod output;
+
+use std::io::Write;
+
+pub use args::Cli;
+pub use error::Error;
+
+use backend::{Backend, RpcBackend, SnapshotBackend};
+
+/// Runs one command and renders its report to `out`.
+pub fn run(cli: &Cli, out: &mut dyn Write) -> Result<(), Error> {
+    let backend = backend(cli)?;
+    let report = commands::execute(backend.as_ref(), &cli.command)?;
+    output::render(out, cli.output, report.headers, &report.value)
+}
+
+fn backend(cli: &Cli) -> Result<Box<dyn Backend>, Error> {
+    if let Some(snapshot) = &cli.dry_run {
+        return Ok(Box::new(SnapshotBackend::load(snapshot, &cli.contract_id)?));
+    }
+
+    let rpc_url = cli
+        .rpc_url
+        .as_deref()
+        .ok_or_else(|| Error::Config("--rpc-url or --dry-run".to_string()))?;
+    let secret_key = cli
+        .secret_key
+        .as_deref()
+        .ok_or_else(|| Error::Config("--secret-key".to_string()))?;
+    Ok(Box::new(RpcBackend::new(
+        rpc_url,
+        &cli.network_passphrase,
+        secret_key,
+        &cli.contract_id,
+        cli.base_fee,
+    )?))
+}
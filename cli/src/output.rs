@@ -0,0 +1,66 @@
+use std::io::Write;
+
+use comfy_table::Table;
+use serde_json::Value;
+
+use crate::args::Format;
+use crate::error::Error;
+
+/// Prints `value` as pretty JSON, or as a table whose rows are the paths to
+/// each leaf of the value.
+pub fn render(
+    out: &mut dyn Write,
+    format: Format,
+    headers: &[&str],
+    value: &Value,
+) -> Result<(), Error> {
+    match format {
+        Format::Json => writeln!(out, "{}", serde_json::to_string_pretty(value)?)?,
+        Format::Table => {
+            let mut table = Table::new();
+            table.set_header(headers.to_vec());
+            for mut row in rows(value) {
+                row.resize(headers.len().max(row.len()), String::new());
+                table.add_row(row);
+            }
+            writeln!(out, "{table}")?;
+        }
+    }
+    Ok(())
+}
+
+fn rows(value: &Value) -> Vec<Vec<String>> {
+    match value {
+        Value::Null => Vec::new(),
+        //arrays of objects are records, one row each
+        Value::Array(items) => items
+            .iter()
+            .flat_map(|item| match item {
+                Value::Object(record) => vec![record.values().map(cell).collect()],
+                _ => rows(item),
+            })
+            .collect(),
+        Value::Object(object) => object
+            .iter()
+            .flat_map(|(key, value)| {
+                let nested = rows(value);
+                if nested.is_empty() {
+                    return vec![vec![key.clone()]];
+                }
+                nested
+                    .into_iter()
+                    .map(|row| std::iter::once(key.clone()).chain(row).collect())
+                    .collect()
+            })
+            .collect(),
+        other => vec![vec![cell(other)]],
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
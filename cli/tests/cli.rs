@@ -0,0 +1,266 @@
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use github_oracle::types::config_data::ConfigData;
+use github_oracle::types::repo_governance::RepoGovernance;
+use github_oracle::{GithubOracleContract, GithubOracleContractClient};
+use github_oracle_cli::{run, Cli, Error};
+use serde_json::{json, Value};
+use soroban_sdk::testutils::{Address as _, EnvTestConfig};
+use soroban_sdk::{vec, Address, Env, String};
+use stellar_strkey::{Contract, Strkey};
+use tempfile::TempDir;
+
+struct Fixture {
+    dir: TempDir,
+    snapshot: PathBuf,
+    contract_id: std::string::String,
+    admin: std::string::String,
+}
+
+//a snapshot holding a configured oracle tracking two repos and one issue
+fn setup_snapshot() -> Fixture {
+    let dir = TempDir::new().unwrap();
+    let snapshot = dir.path().join("snapshot.json");
+    let contract_id = Strkey::Contract(Contract([1; 32])).to_string();
+
+    let mut env = Env::default();
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    let contract = Address::from_string(&String::from_str(&env, &contract_id));
+    env.register_contract(Some(&contract), GithubOracleContract);
+    let client = GithubOracleContractClient::new(&env, &contract);
+
+    let admin = Address::generate(&env);
+    client.config(&ConfigData {
+        admin: admin.clone(),
+        repos: vec![
+            &env,
+            String::from_str(&env, "sdf/soroban-sdk"),
+            String::from_str(&env, "sdf/soroban-cli"),
+        ],
+        max_repos: 10,
+        max_issues_per_repo: 10,
+        repo_governance: RepoGovernance::Admin,
+    });
+    client.add_issues(
+        &String::from_str(&env, "sdf/soroban-sdk"),
+        &vec![&env, String::from_str(&env, "1")],
+    );
+    env.to_ledger_snapshot_file(&snapshot);
+
+    Fixture {
+        dir,
+        snapshot,
+        contract_id,
+        admin: strkey(&admin),
+    }
+}
+
+fn strkey(address: &Address) -> std::string::String {
+    let text = address.to_string();
+    let mut buffer = std::vec![0; text.len() as usize];
+    text.copy_into_slice(&mut buffer);
+    std::string::String::from_utf8(buffer).unwrap()
+}
+
+fn dry_run(fixture: &Fixture, args: &[&str]) -> Result<std::string::String, Error> {
+    let mut argv = std::vec![
+        "github-oracle",
+        "--contract-id",
+        &fixture.contract_id,
+        "--dry-run",
+        fixture.snapshot.to_str().unwrap(),
+    ];
+    argv.extend_from_slice(args);
+    let cli = Cli::try_parse_from(argv).unwrap();
+
+    let mut out = Vec::new();
+    run(&cli, &mut out)?;
+    Ok(std::string::String::from_utf8(out).unwrap())
+}
+
+fn write_file(dir: &Path, name: &str, contents: &str) -> std::string::String {
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn issues_are_listed_from_the_snapshot() {
+    let fixture = setup_snapshot();
+
+    let out = dry_run(&fixture, &["--output", "json", "issues", "list"]).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(&out).unwrap(),
+        json!({ "sdf/soroban-sdk": { "1": "unclaimed" }, "sdf/soroban-cli": {} })
+    );
+
+    let out = dry_run(&fixture, &["repos", "list"]).unwrap();
+    assert!(out.contains("sdf/soroban-sdk") && out.contains("sdf/soroban-cli"));
+}
+
+#[test]
+fn config_takes_the_repo_governance() {
+    let mut fixture = setup_snapshot();
+    //an oracle that has been deployed but not configured yet
+    let mut env = Env::default();
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    let contract = Address::from_string(&String::from_str(&env, &fixture.contract_id));
+    env.register_contract(Some(&contract), GithubOracleContract);
+    fixture.snapshot = fixture.dir.path().join("unconfigured.json");
+    env.to_ledger_snapshot_file(&fixture.snapshot);
+
+    let config = |extra: &[&str]| {
+        let mut args = std::vec!["config", "--admin", &fixture.admin, "sdf/soroban-sdk"];
+        args.extend_from_slice(extra);
+        dry_run(&fixture, &args)
+    };
+    let out = config(&["--repo-governance", "voting", "--quorum", "2"]).unwrap();
+    assert!(out.contains("simulated"));
+
+    let argv = [
+        "github-oracle",
+        "--contract-id",
+        &fixture.contract_id,
+        "config",
+        "--admin",
+        &fixture.admin,
+        "--repo-governance",
+        "voting",
+    ];
+    assert!(Cli::try_parse_from(argv).is_err());
+}
+
+#[test]
+fn bulk_issues_are_grouped_into_one_call_per_repo() {
+    let fixture = setup_snapshot();
+    let csv = write_file(
+        fixture.dir.path(),
+        "issues.csv",
+        "repo,issue\nsdf/soroban-sdk,2\nsdf/soroban-cli,7\nsdf/soroban-sdk,3\n",
+    );
+    let json = write_file(
+        fixture.dir.path(),
+        "issues.json",
+        r#"[{"repo": "sdf/soroban-cli", "issue": 8}]"#,
+    );
+
+    for file in [&csv, &json] {
+        let out = dry_run(
+            &fixture,
+            &["--output", "json", "issues", "add", "--file", file],
+        )
+        .unwrap();
+        let calls: Value = serde_json::from_str(&out).unwrap();
+        for call in calls.as_array().unwrap() {
+            assert_eq!(call["call"], "add_issues");
+            assert_eq!(call["outcome"], "simulated");
+        }
+        let targets: std::vec::Vec<&Value> = calls
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|call| &call["target"])
+            .collect();
+        if file == &csv {
+            assert_eq!(
+                targets,
+                [&json!("sdf/soroban-sdk"), &json!("sdf/soroban-cli")]
+            );
+        } else {
+            assert_eq!(targets, [&json!("sdf/soroban-cli")]);
+        }
+    }
+}
+
+#[test]
+fn contract_errors_are_reported() {
+    let fixture = setup_snapshot();
+
+    let result = dry_run(&fixture, &["issues", "add", "--repo", "sdf/unknown", "1"]);
+    assert!(matches!(
+        result,
+        Err(Error::Invocation { ref function, .. }) if function == "add_issues"
+    ));
+
+    let result = dry_run(&fixture, &["roles", "set-attestor", "not-an-address"]);
+    assert!(matches!(result, Err(Error::Input(_))));
+}
+
+#[test]
+fn roles_are_shown_as_a_table() {
+    let fixture = setup_snapshot();
+
+    let out = dry_run(&fixture, &["roles", "show"]).unwrap();
+    assert!(out.contains("role"));
+    assert!(out.contains(&fixture.admin));
+}
+
+#[test]
+fn reconcile_applies_the_planned_calls_in_order() {
+    let fixture = setup_snapshot();
+    let issue = |repo: &str, number: u32| {
+        json!({
+            "number": number,
+            "state": "open",
+            "repository_url": format!("https://api.github.com/repos/{repo}"),
+        })
+    };
+    let issues = write_file(
+        fixture.dir.path(),
+        "issues.json",
+        &Value::Array(std::vec![
+            issue("sdf/soroban-sdk", 1),
+            issue("sdf/soroban-sdk", 2),
+            issue("sdf/rs-stellar-xdr", 3),
+        ])
+        .to_string(),
+    );
+
+    let out = dry_run(
+        &fixture,
+        &[
+            "--output",
+            "json",
+            "reconcile",
+            "--issues",
+            &issues,
+            "--apply",
+        ],
+    )
+    .unwrap();
+    let report: Value = serde_json::from_str(&out).unwrap();
+    let kinds = |section: &str| -> std::vec::Vec<std::string::String> {
+        report[section]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| format!("{} {} {}", entry["kind"], entry["repo"], entry["issue"]))
+            .collect()
+    };
+
+    assert_eq!(
+        kinds("drift"),
+        [
+            r#""missing_repo" "sdf/rs-stellar-xdr" """#,
+            r#""missing_issue" "sdf/rs-stellar-xdr" "3""#,
+            r#""missing_issue" "sdf/soroban-sdk" "2""#,
+        ]
+    );
+    assert_eq!(
+        kinds("calls"),
+        [
+            r#""add_repos" "sdf/rs-stellar-xdr" """#,
+            r#""add_issues" "sdf/rs-stellar-xdr" "3""#,
+            r#""add_issues" "sdf/soroban-sdk" "2""#,
+        ]
+    );
+    for call in report["calls"].as_array().unwrap() {
+        assert_eq!(call["detail"], "simulated");
+    }
+}
@@ -0,0 +1,142 @@
+use core::ops::Deref;
+
+use github_oracle::types::escrow::Escrow;
+use github_oracle::types::forge::Forge;
+use github_oracle::GithubOracleContractClient;
+use soroban_sdk::{Address, Env, InvokeError, Map, String, Vec};
+
+use crate::pages::{IssueProposals, IssuesByLabel};
+use crate::repo_id::RepoId;
+use crate::retry::{CallError, Retry, TryResult};
+
+/// `GithubOracleContractClient` with typed repo ids, results instead of
+/// panics, and retries. Derefs to the generated client for everything else.
+pub struct OracleClient<'a> {
+    inner: GithubOracleContractClient<'a>,
+    retry: Retry,
+}
+
+impl<'a> OracleClient<'a> {
+    pub fn new(env: &Env, address: &Address) -> OracleClient<'a> {
+        OracleClient::from(GithubOracleContractClient::new(env, address))
+    }
+
+    /// Replaces the retry policy used by every helper and by `call`.
+    pub fn with_retry(mut self, retry: Retry) -> OracleClient<'a> {
+        self.retry = retry;
+        self
+    }
+
+    pub fn inner(&self) -> &GithubOracleContractClient<'a> {
+        &self.inner
+    }
+
+    /// Runs a generated `try_` method under the retry policy.
+    pub fn call<T, E, F: Into<InvokeError>>(
+        &self,
+        mut call: impl FnMut(&GithubOracleContractClient<'a>) -> TryResult<T, E, F>,
+    ) -> Result<T, CallError> {
+        self.retry.run(|_| call(&self.inner))
+    }
+
+    /// Adds the repos one forge at a time. When a forge's batch would exceed the repo
+    /// limit the call fails; batches of earlier forges stay added.
+    pub fn add_repos(&self, repos: &[RepoId]) -> Result<(), CallError> {
+        for forge in [Forge::GitHub, Forge::GitLab, Forge::Gitea] {
+            let mut paths = Vec::new(&self.env);
+            for repo in repos.iter().filter(|repo| repo.forge == forge) {
+                paths.push_back(String::from_str(&self.env, repo.path));
+            }
+            if paths.is_empty() {
+                continue;
+            }
+            self.call(|client| client.try_add_forge_repos(&forge, &paths))?;
+        }
+        Ok(())
+    }
+
+    pub fn remove_repos(&self, repos: &[RepoId]) -> Result<(), CallError> {
+        let keys = self.keys(repos);
+        self.call(|client| client.try_remove_repos(&keys))
+    }
+
+    pub fn add_issues(&self, repo: RepoId, issues: &[&str]) -> Result<(), CallError> {
+        let (key, issues) = (repo.key(&self.env), self.strings(issues));
+        self.call(|client| client.try_add_issues(&key, &issues))
+    }
+
+    pub fn remove_issues(&self, repo: RepoId, issues: &[&str]) -> Result<(), CallError> {
+        let (key, issues) = (repo.key(&self.env), self.strings(issues));
+        self.call(|client| client.try_remove_issues(&key, &issues))
+    }
+
+    /// Issues of `repo` mapped to their status.
+    pub fn issues(&self, repo: RepoId) -> Result<Map<String, String>, CallError> {
+        let key = repo.key(&self.env);
+        self.call(|client| client.try_get_issues_for_repo(&key))
+    }
+
+    pub fn escrow(&self, repo: RepoId, issue: &str) -> Result<Option<Escrow>, CallError> {
+        let (key, issue) = (repo.key(&self.env), String::from_str(&self.env, issue));
+        self.call(|client| client.try_get_escrow(&key, &issue))
+    }
+
+    /// Completes `issue` as `caller`, splitting any escrow by `shares` in basis points.
+    pub fn complete_issue(
+        &self,
+        caller: &Address,
+        repo: RepoId,
+        issue: &str,
+        shares: &[(Address, u32)],
+    ) -> Result<(), CallError> {
+        let (key, issue) = (repo.key(&self.env), String::from_str(&self.env, issue));
+        let mut share_vec = Vec::new(&self.env);
+        for share in shares {
+            share_vec.push_back(share.clone());
+        }
+        self.call(|client| client.try_complete_issue(caller, &key, &issue, &share_vec))
+    }
+
+    /// Every issue labeled `label`, across repos.
+    pub fn issues_by_label(&self, label: &str, page_size: u32) -> IssuesByLabel<'_, 'a> {
+        IssuesByLabel::new(&self.inner, String::from_str(&self.env, label), page_size)
+    }
+
+    /// Every pending issue proposal, oldest first.
+    pub fn issue_proposals(&self, page_size: u32) -> IssueProposals<'_, 'a> {
+        IssueProposals::new(&self.inner, page_size)
+    }
+
+    fn keys(&self, repos: &[RepoId]) -> Vec<String> {
+        let mut keys = Vec::new(&self.env);
+        for repo in repos {
+            keys.push_back(repo.key(&self.env));
+        }
+        keys
+    }
+
+    fn strings(&self, values: &[&str]) -> Vec<String> {
+        let mut strings = Vec::new(&self.env);
+        for value in values {
+            strings.push_back(String::from_str(&self.env, value));
+        }
+        strings
+    }
+}
+
+impl<'a> From<GithubOracleContractClient<'a>> for OracleClient<'a> {
+    fn from(inner: GithubOracleContractClient<'a>) -> OracleClient<'a> {
+        OracleClient {
+            inner,
+            retry: Retry::default(),
+        }
+    }
+}
+
+impl<'a> Deref for OracleClient<'a> {
+    type Target = GithubOracleContractClient<'a>;
+
+    fn deref(&self) -> &GithubOracleContractClient<'a> {
+        &self.inner
+    }
+}
@@ -0,0 +1,26 @@
+//! Typed client for the `github_oracle` contract.
+//!
+//! Re-exports the contract's types and errors, and wraps the generated
+//! `GithubOracleContractClient` with typed repo ids, pagination and retries.
+#![no_std]
+
+mod client;
+mod pages;
+mod repo_id;
+mod retry;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+mod test;
+
+pub use github_oracle::types;
+pub use github_oracle::types::config_data::ConfigData;
+pub use github_oracle::types::error::{Error, ExtendedError};
+pub use github_oracle::types::forge::Forge;
+pub use github_oracle::types::issue_ref::IssueRef;
+pub use github_oracle::{GithubOracleContract, GithubOracleContractClient};
+
+pub use client::OracleClient;
+pub use pages::{IssueProposals, IssuesByLabel};
+pub use repo_id::RepoId;
+pub use retry::{CallError, Retry};
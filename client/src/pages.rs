@@ -0,0 +1,114 @@
+use github_oracle::types::issue_proposal::IssueProposal;
+use github_oracle::types::issue_ref::IssueRef;
+use github_oracle::GithubOracleContractClient;
+use soroban_sdk::{String, Vec};
+
+/// Every unclaimed issue carrying a label, fetched `page_size` at a time.
+pub struct IssuesByLabel<'c, 'a> {
+    client: &'c GithubOracleContractClient<'a>,
+    label: String,
+    page_size: u32,
+    cursor: Option<IssueRef>,
+    page: Option<Vec<IssueRef>>,
+    index: u32,
+}
+
+impl<'c, 'a> IssuesByLabel<'c, 'a> {
+    pub(crate) fn new(
+        client: &'c GithubOracleContractClient<'a>,
+        label: String,
+        page_size: u32,
+    ) -> IssuesByLabel<'c, 'a> {
+        IssuesByLabel {
+            client,
+            label,
+            page_size: page_size.max(1),
+            cursor: None,
+            page: None,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for IssuesByLabel<'_, '_> {
+    type Item = IssueRef;
+
+    fn next(&mut self) -> Option<IssueRef> {
+        if self
+            .page
+            .as_ref()
+            .is_none_or(|page| self.index >= page.len())
+        {
+            //a short page means the index is exhausted
+            if matches!(&self.page, Some(page) if page.len() < self.page_size) {
+                return None;
+            }
+            let page = self
+                .client
+                .issues_by_label(&self.label, &self.cursor, &self.page_size);
+            //pages are keyed by issue ref, so resume after the last one seen
+            if let Some(last) = page.last() {
+                self.cursor = Some(last);
+            }
+            self.page = Some(page);
+            self.index = 0;
+        }
+
+        let issue = self.page.as_ref()?.get(self.index)?;
+        self.index += 1;
+        Some(issue)
+    }
+}
+
+/// Every pending issue proposal, fetched `page_size` at a time.
+pub struct IssueProposals<'c, 'a> {
+    client: &'c GithubOracleContractClient<'a>,
+    page_size: u32,
+    cursor: u32,
+    page: Option<Vec<IssueProposal>>,
+    index: u32,
+}
+
+impl<'c, 'a> IssueProposals<'c, 'a> {
+    pub(crate) fn new(
+        client: &'c GithubOracleContractClient<'a>,
+        page_size: u32,
+    ) -> IssueProposals<'c, 'a> {
+        IssueProposals {
+            client,
+            page_size: page_size.max(1),
+            cursor: 0,
+            page: None,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for IssueProposals<'_, '_> {
+    type Item = IssueProposal;
+
+    fn next(&mut self) -> Option<IssueProposal> {
+        if self
+            .page
+            .as_ref()
+            .is_none_or(|page| self.index >= page.len())
+        {
+            if matches!(&self.page, Some(page) if page.len() < self.page_size) {
+                return None;
+            }
+            let page = self
+                .client
+                .get_issue_proposals(&self.cursor, &self.page_size);
+            //proposals are paged by id, so resume after the last one seen
+            if let Some(last) = page.last() {
+                self.cursor = last.id + 1;
+            }
+            self.page = Some(page);
+            self.index = 0;
+        }
+
+        let proposal = self.page.as_ref()?.get(self.index)?;
+        self.index += 1;
+        Some(proposal)
+    }
+}
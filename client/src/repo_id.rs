@@ -0,0 +1,63 @@
+use core::fmt;
+
+use github_oracle::types::forge::Forge;
+use soroban_sdk::{Env, String};
+
+// longest repo key the contract accepts, including the forge prefix
+const MAX_KEY_LEN: usize = 256;
+
+/// A repo on a forge, as `owner/name`. Converts to and from the registry key,
+/// so callers never build `gitlab:` prefixes by hand.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RepoId<'a> {
+    pub forge: Forge,
+    pub path: &'a str,
+}
+
+impl<'a> RepoId<'a> {
+    pub fn new(forge: Forge, path: &'a str) -> RepoId<'a> {
+        RepoId { forge, path }
+    }
+
+    pub fn github(path: &'a str) -> RepoId<'a> {
+        RepoId::new(Forge::GitHub, path)
+    }
+
+    pub fn gitlab(path: &'a str) -> RepoId<'a> {
+        RepoId::new(Forge::GitLab, path)
+    }
+
+    pub fn gitea(path: &'a str) -> RepoId<'a> {
+        RepoId::new(Forge::Gitea, path)
+    }
+
+    /// Splits a registry key back into its forge and path.
+    pub fn from_key(key: &'a str) -> RepoId<'a> {
+        for forge in [Forge::GitLab, Forge::Gitea] {
+            if let Some(path) = key.strip_prefix(forge.key_prefix()) {
+                return RepoId::new(forge, path);
+            }
+        }
+        RepoId::github(key)
+    }
+
+    /// The key the contract stores the repo under. Panics if the path is
+    /// longer than any key the contract accepts.
+    pub fn key(&self, env: &Env) -> String {
+        let prefix = self.forge.key_prefix().as_bytes();
+        let path = self.path.as_bytes();
+        let len = prefix.len() + path.len();
+        assert!(len <= MAX_KEY_LEN, "repo key is too long: {}", self);
+
+        let mut buffer = [0; MAX_KEY_LEN];
+        buffer[..prefix.len()].copy_from_slice(prefix);
+        buffer[prefix.len()..len].copy_from_slice(path);
+        String::from_bytes(env, &buffer[..len])
+    }
+}
+
+impl fmt::Display for RepoId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.forge.key_prefix(), self.path)
+    }
+}
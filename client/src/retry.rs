@@ -0,0 +1,109 @@
+use core::fmt;
+
+use github_oracle::types::error::{Error, ExtendedError};
+use soroban_sdk::InvokeError;
+
+/// What a generated `try_` method returns. The oracle panics with its errors
+/// rather than returning them, so they arrive as `soroban_sdk::Error`s.
+pub type TryResult<T, E, F = soroban_sdk::Error> = Result<Result<T, E>, Result<F, InvokeError>>;
+
+/// Why a `try_` call failed, with the nested results flattened.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CallError {
+    /// The oracle rejected the call.
+    Contract(Error),
+    /// The oracle rejected the call with one of its codes from 50 on.
+    Extended(ExtendedError),
+    /// The host aborted the call, or it failed with a code the oracle does not define.
+    Host(InvokeError),
+    /// The call succeeded but its result did not decode.
+    Conversion,
+}
+
+impl CallError {
+    pub fn from_try<T, E, F: Into<InvokeError>>(
+        result: TryResult<T, E, F>,
+    ) -> Result<T, CallError> {
+        let err = match result {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(_)) => return Err(CallError::Conversion),
+            Err(Ok(err)) => err.into(),
+            Err(Err(err)) => err,
+        };
+        if let Ok(err) = Error::try_from(err) {
+            return Err(CallError::Contract(err));
+        }
+        Err(match ExtendedError::try_from(err) {
+            Ok(err) => CallError::Extended(err),
+            Err(err) => CallError::Host(err),
+        })
+    }
+
+    /// Host failures may pass on a later attempt; contract errors are
+    /// deterministic for a given ledger state and conversions never change.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, CallError::Host(_))
+    }
+}
+
+impl From<Error> for CallError {
+    fn from(err: Error) -> CallError {
+        CallError::Contract(err)
+    }
+}
+
+impl From<ExtendedError> for CallError {
+    fn from(err: ExtendedError) -> CallError {
+        CallError::Extended(err)
+    }
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CallError::Contract(err) => write!(f, "oracle error #{}: {}", *err as u32, err),
+            CallError::Extended(err) => write!(f, "oracle error #{}: {}", *err as u32, err),
+            CallError::Host(InvokeError::Contract(code)) => write!(f, "unknown error #{}", code),
+            CallError::Host(InvokeError::Abort) => f.write_str("the host aborted the call"),
+            CallError::Conversion => f.write_str("the result could not be decoded"),
+        }
+    }
+}
+
+/// How often to repeat a failing `try_` call, and which failures to repeat.
+#[derive(Copy, Clone, Debug)]
+pub struct Retry {
+    pub attempts: u32,
+    pub retry_on: fn(&CallError) -> bool,
+}
+
+impl Retry {
+    pub fn new(attempts: u32) -> Retry {
+        Retry {
+            attempts,
+            retry_on: CallError::is_transient,
+        }
+    }
+
+    /// Runs `call` until it succeeds, fails with an error `retry_on` rejects,
+    /// or runs out of attempts. `call` gets the attempt number, starting at 0,
+    /// so it can change something between attempts.
+    pub fn run<T, E, F: Into<InvokeError>>(
+        &self,
+        mut call: impl FnMut(u32) -> TryResult<T, E, F>,
+    ) -> Result<T, CallError> {
+        let mut attempt = 0;
+        loop {
+            match CallError::from_try(call(attempt)) {
+                Err(err) if attempt + 1 < self.attempts && (self.retry_on)(&err) => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for Retry {
+    fn default() -> Retry {
+        Retry::new(3)
+    }
+}
@@ -0,0 +1,161 @@
+#![cfg(test)]
+extern crate std;
+
+use std::string::ToString;
+
+use super::*;
+use crate::retry::TryResult;
+use crate::testutils::setup_oracle;
+use github_oracle::types::issue_proposal::ProposalDepositConfig;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::StellarAssetClient;
+use soroban_sdk::{vec, Address, Env, InvokeError, String};
+
+#[test]
+fn repo_ids_round_trip_through_registry_keys() {
+    let env = Env::default();
+
+    let gitlab = RepoId::gitlab("inkscape/inkscape");
+    assert_eq!(
+        gitlab.key(&env),
+        String::from_str(&env, "gitlab:inkscape/inkscape")
+    );
+    assert_eq!(RepoId::from_key("gitlab:inkscape/inkscape"), gitlab);
+    assert_eq!(
+        RepoId::from_key("gitea:forgejo/forgejo").forge,
+        Forge::Gitea
+    );
+    assert_eq!(
+        RepoId::from_key("sdf/soroban-sdk"),
+        RepoId::github("sdf/soroban-sdk")
+    );
+    assert_eq!(gitlab.to_string(), "gitlab:inkscape/inkscape");
+}
+
+#[test]
+fn typed_helpers_use_forge_keys() {
+    let env = Env::default();
+    let (client, config) = setup_oracle(&env);
+
+    let sdk = RepoId::github("sdf/soroban-sdk");
+    let inkscape = RepoId::gitlab("inkscape/inkscape");
+    client.add_repos(&[sdk, inkscape]).unwrap();
+    client.add_issues(inkscape, &["12", "13"]).unwrap();
+
+    assert_eq!(
+        client.get_repos_by_forge(&Forge::GitLab),
+        vec![&env, String::from_str(&env, "gitlab:inkscape/inkscape")]
+    );
+    assert_eq!(client.issues(inkscape).unwrap().len(), 2);
+    assert_eq!(client.escrow(inkscape, "12").unwrap(), None);
+
+    let contributor = Address::generate(&env);
+    client
+        .complete_issue(&config.admin, inkscape, "12", &[(contributor, 10_000)])
+        .unwrap();
+    assert_eq!(
+        client
+            .issues(inkscape)
+            .unwrap()
+            .get(String::from_str(&env, "12")),
+        Some(String::from_str(&env, "completed"))
+    );
+}
+
+#[test]
+fn contract_errors_are_returned_with_messages() {
+    let env = Env::default();
+    let (client, _config) = setup_oracle(&env);
+
+    let err = client.issues(RepoId::github("sdf/missing")).unwrap_err();
+    assert_eq!(err, CallError::Contract(Error::RepoMissing));
+    assert_eq!(err.to_string(), "oracle error #2: the repo is not tracked");
+
+    client
+        .add_repos(&[RepoId::github("sdf/soroban-sdk")])
+        .unwrap();
+    assert_eq!(
+        client.add_repos(&[RepoId::github("sdf/soroban-sdk")]),
+        Err(CallError::Contract(Error::RepoAlreadyExists))
+    );
+}
+
+#[test]
+fn retries_only_transient_failures() {
+    let mut attempts = 0;
+    let result = Retry::new(3).run(|attempt| -> TryResult<u32, ()> {
+        attempts += 1;
+        match attempt {
+            0 | 1 => Err(Err(InvokeError::Abort)),
+            _ => Ok(Ok(attempt)),
+        }
+    });
+    assert_eq!(result, Ok(2));
+    assert_eq!(attempts, 3);
+
+    let mut attempts = 0;
+    let result = Retry::new(3).run(|_| -> TryResult<(), ()> {
+        attempts += 1;
+        Err(Ok(soroban_sdk::Error::from(Error::ClearDelayPending)))
+    });
+    assert_eq!(result, Err(CallError::Contract(Error::ClearDelayPending)));
+    assert_eq!(attempts, 1);
+
+    let result = Retry::new(3).run(|_| -> TryResult<(), ()> {
+        Err(Ok(soroban_sdk::Error::from(ExtendedError::RepoInactive)))
+    });
+    assert_eq!(
+        result,
+        Err(CallError::Extended(ExtendedError::RepoInactive))
+    );
+
+    let result = Retry::new(2).run(|_| -> TryResult<(), ()> { Err(Err(InvokeError::Abort)) });
+    assert_eq!(result, Err(CallError::Host(InvokeError::Abort)));
+}
+
+#[test]
+fn label_and_proposal_iterators_walk_every_page() {
+    let env = Env::default();
+    let (client, config) = setup_oracle(&env);
+    let caller = config.admin.clone();
+
+    let sdk = RepoId::github("sdf/soroban-sdk");
+    let cli = RepoId::github("sdf/soroban-cli");
+    client.add_repos(&[sdk, cli]).unwrap();
+    client.add_issues(sdk, &["1", "2", "3"]).unwrap();
+    client.add_issues(cli, &["4", "5"]).unwrap();
+    let bug = String::from_str(&env, "bug");
+    for (repo, issue) in [(sdk, "1"), (sdk, "3"), (cli, "4"), (cli, "5")] {
+        client.set_issue_labels(
+            &caller,
+            &repo.key(&env),
+            &String::from_str(&env, issue),
+            &vec![&env, bug.clone()],
+        );
+    }
+
+    let issues: std::vec::Vec<IssueRef> = client.issues_by_label("bug", 3).collect();
+    assert_eq!(issues.len(), 4);
+    assert_eq!(issues[3].issue, String::from_str(&env, "5"));
+    assert_eq!(client.issues_by_label("feature", 3).count(), 0);
+
+    let token = env.register_stellar_asset_contract_v2(caller).address();
+    client.set_proposal_deposit(&ProposalDepositConfig {
+        token: token.clone(),
+        amount: 10,
+        expiry_ledgers: 100,
+    });
+    let proposer = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&proposer, &40);
+    for issue in ["6", "7", "8", "9"] {
+        client.propose_issue(&proposer, &sdk.key(&env), &String::from_str(&env, issue));
+    }
+    client.approve_issue_proposal(&config.admin, &1);
+
+    let pending: std::vec::Vec<u32> = client
+        .issue_proposals(2)
+        .map(|proposal| proposal.id)
+        .collect();
+    assert_eq!(pending.len(), 3);
+    assert!(!pending.contains(&1));
+}
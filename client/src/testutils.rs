@@ -0,0 +1,29 @@
+use github_oracle::types::config_data::ConfigData;
+use github_oracle::types::repo_governance::RepoGovernance;
+use github_oracle::GithubOracleContract;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::client::OracleClient;
+
+/// Registers an oracle in `env` and configures it with a generated admin,
+/// no repos and the default limits, with every auth mocked and an unlimited
+/// budget.
+pub fn setup_oracle<'a>(env: &Env) -> (OracleClient<'a>, ConfigData) {
+    let contract_id = env.register_contract(None, GithubOracleContract);
+    let client = OracleClient::new(env, &contract_id);
+
+    env.budget().reset_unlimited();
+    env.mock_all_auths();
+
+    let config = ConfigData {
+        admin: Address::generate(env),
+        repos: Vec::new(env),
+        max_repos: 256,
+        max_issues_per_repo: 512,
+        repo_governance: RepoGovernance::Admin,
+    };
+    client.config(&config);
+
+    (client, config)
+}
@@ -0,0 +1,116 @@
+#![no_std]
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{
+    contract, contractimpl, log, panic_with_error, symbol_short, Address, BytesN, Env, Map, String,
+};
+
+use error::Error;
+use oracle::{ConfigData, OracleClient};
+
+pub mod error;
+pub mod oracle;
+mod test;
+
+const ADMIN_KEY: &str = "admin";
+const WASM_HASH_KEY: &str = "wasm_hash";
+const INSTANCES_KEY: &str = "instances";
+
+#[contract]
+pub struct GithubOracleFactory;
+
+#[contractimpl]
+impl GithubOracleFactory {
+    pub fn config(e: Env, admin: Address, wasm_hash: BytesN<32>) {
+        admin.require_auth();
+        if e.storage().instance().has(&ADMIN_KEY) {
+            panic_with_error!(&e, Error::AlreadyInitialized);
+        }
+        e.storage().instance().set(&ADMIN_KEY, &admin);
+        e.storage().instance().set(&WASM_HASH_KEY, &wasm_hash);
+    }
+
+    pub fn deploy(e: Env, org: String, config: ConfigData) -> Address {
+        log!(&e, "deploy: {} {:?}", org, config);
+        Self::__panic_if_not_admin(&e);
+        //the org's admin signs for the nested `config` and `set_upgrader` calls too
+        config.admin.require_auth();
+        let mut instances = Self::__get_instances(&e);
+        if instances.contains_key(org.clone()) {
+            panic_with_error!(&e, Error::OrgAlreadyExists);
+        }
+
+        //the org name picks the address, so an org maps to one instance for good
+        let salt = e.crypto().sha256(&org.clone().to_xdr(&e)).to_bytes();
+        let instance = e
+            .deployer()
+            .with_current_contract(salt)
+            .deploy(Self::wasm_hash(e.clone()));
+        let oracle = OracleClient::new(&e, &instance);
+        oracle.config(&config);
+        oracle.set_upgrader(&Some(e.current_contract_address()));
+
+        instances.set(org.clone(), instance.clone());
+        e.storage().instance().set(&INSTANCES_KEY, &instances);
+        e.events()
+            .publish((symbol_short!("deployed"), org), instance.clone());
+        instance
+    }
+
+    //new deployments use the new wasm too. Instances are upgraded a page at a
+    //time, and one that refuses the upgrade (e.g. its org took upgrades back)
+    //is reported in its own event rather than reverting the rest. Returns the
+    //start of the next page, if any.
+    pub fn upgrade(e: Env, wasm_hash: BytesN<32>, start: u32, limit: u32) -> Option<u32> {
+        Self::__panic_if_not_admin(&e);
+        e.storage().instance().set(&WASM_HASH_KEY, &wasm_hash);
+        let instances = Self::__get_instances(&e);
+        let end = instances.len().min(start.saturating_add(limit));
+        for (org, instance) in instances.iter().skip(start as usize).take(limit as usize) {
+            let upgraded = OracleClient::new(&e, &instance)
+                .try_update_contract(&wasm_hash)
+                .is_ok();
+            e.events().publish(
+                (symbol_short!("upgraded"), org),
+                (wasm_hash.clone(), upgraded),
+            );
+        }
+        if end < instances.len() {
+            Some(end)
+        } else {
+            None
+        }
+    }
+
+    pub fn instance(e: Env, org: String) -> Address {
+        match Self::__get_instances(&e).get(org) {
+            Some(instance) => instance,
+            None => panic_with_error!(&e, Error::OrgMissing),
+        }
+    }
+
+    pub fn instances(e: Env) -> Map<String, Address> {
+        Self::__get_instances(&e)
+    }
+
+    pub fn wasm_hash(e: Env) -> BytesN<32> {
+        match e.storage().instance().get(&WASM_HASH_KEY) {
+            Some(wasm_hash) => wasm_hash,
+            None => panic_with_error!(&e, Error::NotInitialized),
+        }
+    }
+
+    fn __panic_if_not_admin(e: &Env) {
+        let admin: Option<Address> = e.storage().instance().get(&ADMIN_KEY);
+        match admin {
+            Some(admin) => admin.require_auth(),
+            None => panic_with_error!(e, Error::NotInitialized),
+        }
+    }
+
+    fn __get_instances(e: &Env) -> Map<String, Address> {
+        e.storage()
+            .instance()
+            .get(&INSTANCES_KEY)
+            .unwrap_or_else(|| Map::new(e))
+    }
+}
@@ -0,0 +1,36 @@
+// The parts of the oracle interface the factory calls. The oracle's types are
+// mirrored here rather than imported, so linking the oracle crate does not pull
+// its entrypoints into the factory's wasm. They serialize identically.
+use soroban_sdk::{contractclient, contracttype, Address, BytesN, Env, String, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotingConfig {
+    pub voting_period_ledgers: u32,
+    pub quorum: i128,
+    pub weight_token: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RepoGovernance {
+    Admin,
+    Voting(VotingConfig),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigData {
+    pub admin: Address,
+    pub repos: Vec<String>,
+    pub max_repos: u32,
+    pub max_issues_per_repo: u32,
+    pub repo_governance: RepoGovernance,
+}
+
+#[contractclient(name = "OracleClient")]
+pub trait Oracle {
+    fn config(e: Env, config: ConfigData);
+    fn set_upgrader(e: Env, upgrader: Option<Address>);
+    fn update_contract(e: Env, wasm_hash: BytesN<32>);
+}
@@ -0,0 +1,189 @@
+#![cfg(test)]
+extern crate std;
+
+use std::rc::Rc;
+
+use super::*;
+use crate::oracle::RepoGovernance;
+use github_oracle::{GithubOracleContract, GithubOracleContractClient};
+use soroban_sdk::testutils::{Address as _, Events, MockAuth, MockAuthInvoke};
+use soroban_sdk::xdr::{
+    ContractDataDurability, LedgerKey, LedgerKeyContractData, ScAddress, ScVal,
+};
+use soroban_sdk::{symbol_short, vec, Bytes, IntoVal, Symbol, TryIntoVal};
+
+fn setup_factory<'a>(env: &Env) -> (GithubOracleFactoryClient<'a>, Address) {
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let factory = env.register_contract(None, GithubOracleFactory);
+    let client = GithubOracleFactoryClient::new(env, &factory);
+    let admin = Address::generate(env);
+    client.config(&admin, &test_wasm_hash(env));
+    (client, admin)
+}
+
+//native test contracts run under the hash of the empty wasm
+fn test_wasm_hash(env: &Env) -> BytesN<32> {
+    env.deployer().upload_contract_wasm(Bytes::new(env))
+}
+
+//there is no oracle wasm in native tests, so the oracle is registered at the
+//address the factory will deploy the org to, minus its instance entry, and the
+//factory's deployment then dispatches to the native contract
+fn expect_deploy(env: &Env, factory: &Address, org: &str) -> Address {
+    let salt = env
+        .crypto()
+        .sha256(&String::from_str(env, org).to_xdr(env))
+        .to_bytes();
+    let address = env.as_contract(factory, || {
+        env.deployer()
+            .with_current_contract(salt)
+            .deployed_address()
+    });
+    env.register_contract(&address, GithubOracleContract);
+
+    let instance = LedgerKey::ContractData(LedgerKeyContractData {
+        contract: ScAddress::from(&address),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+    });
+    env.host()
+        .with_mut_storage(|storage| storage.del(&Rc::new(instance), &env.host().budget_cloned()))
+        .unwrap();
+    address
+}
+
+fn org_config(env: &Env, repo: &str) -> (ConfigData, Address) {
+    let admin = Address::generate(env);
+    let config = ConfigData {
+        admin: admin.clone(),
+        repos: vec![env, String::from_str(env, repo)],
+        max_repos: 16,
+        max_issues_per_repo: 64,
+        repo_governance: RepoGovernance::Admin,
+    };
+    (config, admin)
+}
+
+#[test]
+fn deploys_a_configured_oracle_per_org() {
+    let env = Env::default();
+    let (factory, _admin) = setup_factory(&env);
+    let sdf = String::from_str(&env, "sdf");
+    let inkscape = String::from_str(&env, "inkscape");
+
+    let expected = expect_deploy(&env, &factory.address, "sdf");
+    let (config, sdf_admin) = org_config(&env, "sdf/soroban-sdk");
+    assert_eq!(factory.deploy(&sdf, &config), expected);
+    expect_deploy(&env, &factory.address, "inkscape");
+    let (config, _) = org_config(&env, "gitlab:inkscape/inkscape");
+    let inkscape_oracle = factory.deploy(&inkscape, &config);
+
+    let oracle = GithubOracleContractClient::new(&env, &expected);
+    assert_eq!(oracle.admin(), Some(sdf_admin));
+    assert_eq!(
+        oracle.get_repos(),
+        vec![&env, String::from_str(&env, "sdf/soroban-sdk")]
+    );
+    assert_eq!(oracle.get_upgrader(), Some(factory.address.clone()));
+
+    assert_eq!(factory.instance(&sdf), expected);
+    assert_eq!(factory.instances().len(), 2);
+    assert_eq!(factory.instances().get(inkscape), Some(inkscape_oracle));
+    assert_eq!(
+        factory.try_instance(&String::from_str(&env, "gnome")),
+        Err(Ok(Error::OrgMissing.into()))
+    );
+}
+
+#[test]
+fn an_org_is_deployed_only_once() {
+    let env = Env::default();
+    let (factory, _admin) = setup_factory(&env);
+    let sdf = String::from_str(&env, "sdf");
+    expect_deploy(&env, &factory.address, "sdf");
+    let (config, _) = org_config(&env, "sdf/soroban-sdk");
+    factory.deploy(&sdf, &config);
+
+    assert_eq!(
+        factory.try_deploy(&sdf, &config),
+        Err(Ok(Error::OrgAlreadyExists.into()))
+    );
+    assert_eq!(
+        factory.try_config(&Address::generate(&env), &test_wasm_hash(&env)),
+        Err(Ok(Error::AlreadyInitialized.into()))
+    );
+}
+
+#[test]
+fn upgrades_every_instance_a_page_at_a_time() {
+    let env = Env::default();
+    let (factory, _admin) = setup_factory(&env);
+    for org in ["gnome", "inkscape", "sdf"] {
+        expect_deploy(&env, &factory.address, org);
+        let (config, _) = org_config(&env, "sdf/soroban-sdk");
+        factory.deploy(&String::from_str(&env, org), &config);
+    }
+
+    let wasm_hash = test_wasm_hash(&env);
+    assert_eq!(factory.upgrade(&wasm_hash, &0, &2), Some(2));
+    assert_eq!(
+        upgrade_results(&env),
+        std::vec![("gnome", true), ("inkscape", true)]
+    );
+    assert_eq!(factory.upgrade(&wasm_hash, &2, &2), None);
+    assert_eq!(
+        upgrade_results(&env),
+        std::vec![("gnome", true), ("inkscape", true), ("sdf", true)]
+    );
+    assert_eq!(factory.wasm_hash(), wasm_hash);
+}
+
+#[test]
+fn an_instance_refusing_the_upgrade_does_not_block_the_rest() {
+    let env = Env::default();
+    let (factory, admin) = setup_factory(&env);
+    for org in ["inkscape", "sdf"] {
+        expect_deploy(&env, &factory.address, org);
+        let (config, _) = org_config(&env, "sdf/soroban-sdk");
+        factory.deploy(&String::from_str(&env, org), &config);
+    }
+    //inkscape hands upgrades to someone other than the factory
+    let inkscape = factory.instance(&String::from_str(&env, "inkscape"));
+    GithubOracleContractClient::new(&env, &inkscape).set_upgrader(&Some(Address::generate(&env)));
+
+    let wasm_hash = test_wasm_hash(&env);
+    env.mock_auths(&[MockAuth {
+        address: &admin,
+        invoke: &MockAuthInvoke {
+            contract: &factory.address,
+            fn_name: "upgrade",
+            args: (wasm_hash.clone(), 0u32, 10u32).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert_eq!(factory.upgrade(&wasm_hash, &0, &10), None);
+    assert_eq!(
+        upgrade_results(&env),
+        std::vec![("inkscape", false), ("sdf", true)]
+    );
+}
+
+fn upgrade_results(env: &Env) -> std::vec::Vec<(&'static str, bool)> {
+    env.events()
+        .all()
+        .iter()
+        .filter_map(|(_, topics, data)| {
+            let name: Symbol = topics.get(0)?.try_into_val(env).ok()?;
+            if name != symbol_short!("upgraded") {
+                return None;
+            }
+            let org: String = topics.get(1)?.try_into_val(env).ok()?;
+            let (_, upgraded): (BytesN<32>, bool) = data.try_into_val(env).ok()?;
+            let org = ["gnome", "inkscape", "sdf"]
+                .into_iter()
+                .find(|name| org == String::from_str(env, name))?;
+            Some((org, upgraded))
+        })
+        .collect()
+}
@@ -0,0 +1,57 @@
+use serde_json::{json, Value};
+
+use crate::error::Error;
+use crate::store::Store;
+
+/// Answers one read-only query, returning the HTTP status and JSON body.
+///
+/// * `GET /repos`
+/// * `GET /repos/{owner}/{repo}/issues`
+/// * `GET /repos/{owner}/{repo}/timeline`
+/// * `GET /bounties`
+/// * `GET /contributors/{address}/earnings`
+pub fn route(store: &Store, method: &str, url: &str) -> (u16, Value) {
+    if method != "GET" {
+        return (405, json!({ "error": "only GET is supported" }));
+    }
+
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let result = match segments.as_slice() {
+        ["repos"] => store.repos(),
+        ["repos", owner, repo, "issues"] => store.issues(&format!("{owner}/{repo}")),
+        ["repos", owner, repo, "timeline"] => store.timeline(&format!("{owner}/{repo}")),
+        ["bounties"] => store.open_bounties(),
+        ["contributors", contributor, "earnings"] => store.earnings(contributor),
+        _ => return (404, json!({ "error": "not found" })),
+    };
+
+    match result {
+        Ok(body) => (200, body),
+        Err(err) => (500, json!({ "error": err.to_string() })),
+    }
+}
+
+/// Serves queries until the listener fails.
+pub fn serve(store: &std::sync::Mutex<Store>, listen_addr: &str) -> Result<(), Error> {
+    let server =
+        tiny_http::Server::http(listen_addr).map_err(|err| Error::Config(err.to_string()))?;
+    let content_type = tiny_http::Header::from_bytes("Content-Type", "application/json")
+        .expect("static header is valid");
+
+    for request in server.incoming_requests() {
+        let (status, body) = {
+            let store = store
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            route(&store, request.method().as_str(), request.url())
+        };
+        let response = tiny_http::Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(err) = request.respond(response) {
+            eprintln!("failed to respond: {err}");
+        }
+    }
+    Ok(())
+}
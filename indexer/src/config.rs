@@ -0,0 +1,67 @@
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::error::Error;
+
+const DEFAULT_DATABASE: &str = "oracle-index.sqlite";
+const DEFAULT_LISTEN_ADDR: &str = "0.0.0.0:8081";
+const DEFAULT_POLL_SECONDS: u64 = 5;
+
+/// Where events are read from.
+#[derive(Clone, Debug)]
+pub enum Feed {
+    /// A saved `getEvents` response, read once.
+    File(PathBuf),
+    /// A Soroban RPC server, polled for new events.
+    Rpc {
+        url: String,
+        contract_id: String,
+        start_ledger: u32,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub database: PathBuf,
+    pub feed: Feed,
+    pub listen_addr: String,
+    pub poll_interval: Duration,
+}
+
+impl Config {
+    /// Reads the `INDEXER_*` environment variables. `INDEXER_EVENTS_FILE`
+    /// takes precedence over `INDEXER_RPC_URL`.
+    pub fn from_env() -> Result<Config, Error> {
+        let feed = match env::var("INDEXER_EVENTS_FILE") {
+            Ok(path) => Feed::File(path.into()),
+            Err(_) => Feed::Rpc {
+                url: required("INDEXER_RPC_URL")?,
+                contract_id: required("INDEXER_CONTRACT_ID")?,
+                start_ledger: required("INDEXER_START_LEDGER")?
+                    .parse()
+                    .map_err(|_| Error::Config("INDEXER_START_LEDGER".to_string()))?,
+            },
+        };
+        let poll_seconds = match env::var("INDEXER_POLL_SECONDS") {
+            Ok(seconds) => seconds
+                .parse()
+                .map_err(|_| Error::Config("INDEXER_POLL_SECONDS".to_string()))?,
+            Err(_) => DEFAULT_POLL_SECONDS,
+        };
+
+        Ok(Config {
+            database: env::var("INDEXER_DATABASE")
+                .unwrap_or_else(|_| DEFAULT_DATABASE.to_string())
+                .into(),
+            feed,
+            listen_addr: env::var("INDEXER_LISTEN_ADDR")
+                .unwrap_or_else(|_| DEFAULT_LISTEN_ADDR.to_string()),
+            poll_interval: Duration::from_secs(poll_seconds),
+        })
+    }
+}
+
+fn required(name: &str) -> Result<String, Error> {
+    env::var(name).map_err(|_| Error::Config(name.to_string()))
+}
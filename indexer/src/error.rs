@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("malformed event {id}: {reason}")]
+    Event { id: String, reason: String },
+    #[error("invalid event feed: {0}")]
+    Feed(String),
+    #[error("rpc error: {0}")]
+    Rpc(String),
+    #[error("missing or invalid configuration: {0}")]
+    Config(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("xdr error: {0}")]
+    Xdr(#[from] stellar_xdr::curr::Error),
+}
@@ -0,0 +1,79 @@
+use github_oracle_relayer::scval::to_json;
+use serde_json::Value;
+use stellar_xdr::curr::{Limits, ReadXdr, ScVal};
+
+use crate::error::Error;
+
+/// One contract event in the shape of a `getEvents` result, with its topics
+/// and value decoded to JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractEvent {
+    /// Paging id, ordered by ledger and position within it.
+    pub id: String,
+    pub ledger: u32,
+    pub contract_id: String,
+    pub tx_hash: Option<String>,
+    pub topic: Vec<Value>,
+    pub value: Value,
+}
+
+impl ContractEvent {
+    /// Decodes an entry of the `events` array returned by `getEvents`.
+    pub fn from_rpc(entry: &Value) -> Result<ContractEvent, Error> {
+        let id = entry["id"].as_str().unwrap_or_default().to_string();
+        let malformed = |reason: &str| Error::Event {
+            id: id.clone(),
+            reason: reason.to_string(),
+        };
+
+        let topic = entry["topic"]
+            .as_array()
+            .ok_or_else(|| malformed("missing topic"))?
+            .iter()
+            .map(|topic| {
+                decode(
+                    topic
+                        .as_str()
+                        .ok_or_else(|| malformed("topic is not xdr"))?,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        //older RPC versions nest the value under `xdr`
+        let value = entry["value"]
+            .as_str()
+            .or_else(|| entry["value"]["xdr"].as_str())
+            .ok_or_else(|| malformed("missing value"))?;
+
+        Ok(ContractEvent {
+            id: id.clone(),
+            ledger: entry["ledger"]
+                .as_u64()
+                .and_then(|ledger| u32::try_from(ledger).ok())
+                .ok_or_else(|| malformed("missing ledger"))?,
+            contract_id: entry["contractId"]
+                .as_str()
+                .ok_or_else(|| malformed("missing contractId"))?
+                .to_string(),
+            tx_hash: entry["txHash"].as_str().map(str::to_string),
+            topic,
+            value: decode(value)?,
+        })
+    }
+
+    /// The event name, the first topic.
+    pub fn kind(&self) -> &str {
+        self.topic
+            .first()
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    /// The string topic at `index`, where the oracle puts repo and issue names.
+    pub fn topic_str(&self, index: usize) -> Option<&str> {
+        self.topic.get(index).and_then(Value::as_str)
+    }
+}
+
+fn decode(xdr: &str) -> Result<Value, Error> {
+    Ok(to_json(&ScVal::from_xdr_base64(xdr, Limits::none())?))
+}
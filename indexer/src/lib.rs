@@ -0,0 +1,28 @@
+//! Materializes `github_oracle` contract events into SQLite and serves
+//! read-only queries over them.
+
+pub mod api;
+pub mod config;
+pub mod error;
+pub mod event;
+pub mod source;
+pub mod store;
+
+pub use config::Config;
+pub use error::Error;
+pub use event::ContractEvent;
+pub use source::{EventPage, EventSource, FileSource, RpcSource};
+pub use store::Store;
+
+/// Applies pages from `source` until it is caught up, resuming after the
+/// store's cursor. Returns how many new events were applied.
+pub fn sync(store: &mut Store, source: &impl EventSource) -> Result<usize, Error> {
+    let mut applied = 0;
+    loop {
+        let page = source.fetch(store.cursor()?.as_deref())?;
+        if page.events.is_empty() {
+            return Ok(applied);
+        }
+        applied += store.apply(&page.events)?;
+    }
+}
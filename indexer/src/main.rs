@@ -0,0 +1,50 @@
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use github_oracle_indexer::config::Feed;
+use github_oracle_indexer::{api, sync, Config, EventSource, FileSource, RpcSource, Store};
+
+fn main() {
+    let config = Config::from_env().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    let store = Store::open(&config.database).unwrap_or_else(|err| {
+        eprintln!("cannot open {}: {}", config.database.display(), err);
+        process::exit(1);
+    });
+    let store = Arc::new(Mutex::new(store));
+
+    match config.feed.clone() {
+        Feed::File(path) => index(&store, &FileSource::new(path)),
+        Feed::Rpc {
+            url,
+            contract_id,
+            start_ledger,
+        } => {
+            let source = RpcSource::new(&url, &contract_id, start_ledger);
+            let store = Arc::clone(&store);
+            thread::spawn(move || loop {
+                index(&store, &source);
+                thread::sleep(config.poll_interval);
+            });
+        }
+    }
+
+    if let Err(err) = api::serve(&store, &config.listen_addr) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn index(store: &Mutex<Store>, source: &impl EventSource) {
+    let mut store = store
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match sync(&mut store, source) {
+        Ok(0) => {}
+        Ok(applied) => eprintln!("indexed {} events", applied),
+        Err(err) => eprintln!("indexing failed: {}", err),
+    }
+}
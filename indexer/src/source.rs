@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+
+use crate::error::Error;
+use crate::event::ContractEvent;
+
+/// A batch of events and the cursor to resume after it.
+#[derive(Clone, Debug, Default)]
+pub struct EventPage {
+    pub events: Vec<ContractEvent>,
+    pub cursor: Option<String>,
+}
+
+/// Somewhere contract events can be read from, in order.
+pub trait EventSource {
+    /// Events after `cursor`, or from the beginning when there is none.
+    /// An empty page means the source is caught up.
+    fn fetch(&self, cursor: Option<&str>) -> Result<EventPage, Error>;
+}
+
+/// A saved `getEvents` response, or a bare array of its events.
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(path: impl Into<PathBuf>) -> FileSource {
+        FileSource { path: path.into() }
+    }
+}
+
+impl EventSource for FileSource {
+    fn fetch(&self, cursor: Option<&str>) -> Result<EventPage, Error> {
+        let export: Value = serde_json::from_slice(&std::fs::read(&self.path)?)?;
+        let entries = match &export {
+            Value::Array(entries) => entries,
+            _ => export["events"].as_array().ok_or_else(|| {
+                Error::Feed(format!("{}: expected an events array", self.path.display()))
+            })?,
+        };
+
+        let events = entries
+            .iter()
+            .map(ContractEvent::from_rpc)
+            .filter(|event| match (event, cursor) {
+                (Ok(event), Some(cursor)) => event.id.as_str() > cursor,
+                _ => true,
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(EventPage {
+            cursor: events.last().map(|event| event.id.clone()),
+            events,
+        })
+    }
+}
+
+/// Pages through `getEvents` on a Soroban RPC server for one contract.
+pub struct RpcSource {
+    url: String,
+    contract_id: String,
+    start_ledger: u32,
+    page_size: u32,
+    agent: ureq::Agent,
+}
+
+impl RpcSource {
+    pub fn new(url: &str, contract_id: &str, start_ledger: u32) -> RpcSource {
+        RpcSource {
+            url: url.to_string(),
+            contract_id: contract_id.to_string(),
+            start_ledger,
+            page_size: 200,
+            agent: ureq::Agent::new(),
+        }
+    }
+}
+
+impl EventSource for RpcSource {
+    fn fetch(&self, cursor: Option<&str>) -> Result<EventPage, Error> {
+        let mut params = json!({
+            "filters": [{ "type": "contract", "contractIds": [self.contract_id] }],
+            "pagination": { "limit": self.page_size },
+        });
+        //the start ledger and a cursor are mutually exclusive
+        match cursor {
+            Some(cursor) => params["pagination"]["cursor"] = cursor.into(),
+            None => params["startLedger"] = self.start_ledger.into(),
+        }
+
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getEvents",
+                "params": params,
+            }))
+            .map_err(|err| Error::Rpc(err.to_string()))?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            return Err(Error::Rpc(error.to_string()));
+        }
+
+        let events = response["result"]["events"]
+            .as_array()
+            .ok_or_else(|| Error::Rpc("getEvents returned no events array".to_string()))?
+            .iter()
+            .map(ContractEvent::from_rpc)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(EventPage {
+            cursor: events.last().map(|event| event.id.clone()),
+            events,
+        })
+    }
+}
@@ -0,0 +1,435 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde_json::{json, Value};
+
+use crate::error::Error;
+use crate::event::ContractEvent;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+CREATE TABLE IF NOT EXISTS repos (name TEXT PRIMARY KEY, added_ledger INTEGER NOT NULL);
+CREATE TABLE IF NOT EXISTS issues (
+    repo TEXT NOT NULL,
+    issue TEXT NOT NULL,
+    status TEXT NOT NULL,
+    added_ledger INTEGER NOT NULL,
+    completed_ledger INTEGER,
+    PRIMARY KEY (repo, issue)
+);
+CREATE TABLE IF NOT EXISTS bounties (
+    repo TEXT NOT NULL,
+    issue TEXT NOT NULL,
+    token TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    PRIMARY KEY (repo, issue)
+);
+CREATE TABLE IF NOT EXISTS claims (
+    repo TEXT NOT NULL,
+    issue TEXT NOT NULL,
+    contributor TEXT NOT NULL,
+    basis_points INTEGER NOT NULL,
+    ledger INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS payouts (
+    repo TEXT,
+    issue TEXT,
+    grant_id INTEGER,
+    token TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    ledger INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS payouts_by_recipient ON payouts (recipient);
+CREATE TABLE IF NOT EXISTS events (
+    id TEXT PRIMARY KEY,
+    ledger INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    repo TEXT,
+    issue TEXT,
+    topic TEXT NOT NULL,
+    value TEXT NOT NULL,
+    tx_hash TEXT
+);
+CREATE INDEX IF NOT EXISTS events_by_repo ON events (repo, ledger);
+";
+
+// issue statuses, as stored by the contract
+const UNCLAIMED: &str = "unclaimed";
+const COMPLETED: &str = "completed";
+
+/// The SQLite database events are materialized into.
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Result<Store, Error> {
+        Store::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Store, Error> {
+        Store::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Store, Error> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Store { conn })
+    }
+
+    /// Id of the last applied event.
+    pub fn cursor(&self) -> Result<Option<String>, Error> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM meta WHERE key = 'cursor'", [], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    /// Applies a page of events and advances the cursor in one transaction.
+    /// Events already recorded are skipped, so replaying a page is harmless.
+    pub fn apply(&mut self, events: &[ContractEvent]) -> Result<usize, Error> {
+        let tx = self.conn.transaction()?;
+        let mut applied = 0;
+        for event in events {
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO events (id, ledger, kind, repo, issue, topic, value, tx_hash)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    event.id,
+                    event.ledger,
+                    event.kind(),
+                    event.topic_str(1),
+                    event.topic_str(2),
+                    Value::Array(event.topic.clone()).to_string(),
+                    event.value.to_string(),
+                    event.tx_hash,
+                ],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+            materialize(&tx, event)?;
+            applied += 1;
+        }
+
+        if let Some(last) = events.last() {
+            tx.execute(
+                "INSERT INTO meta (key, value) VALUES ('cursor', ?1)
+                 ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+                params![last.id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(applied)
+    }
+
+    /// Tracked repos with their issue counts.
+    pub fn repos(&self) -> Result<Value, Error> {
+        self.rows(
+            "SELECT r.name,
+                    COUNT(i.issue) FILTER (WHERE i.status = 'unclaimed'),
+                    COUNT(i.issue) FILTER (WHERE i.status = 'completed')
+             FROM repos r LEFT JOIN issues i ON i.repo = r.name
+             GROUP BY r.name ORDER BY r.name",
+            [],
+            |row| {
+                Ok(json!({
+                    "repo": row.get::<_, String>(0)?,
+                    "unclaimed": row.get::<_, i64>(1)?,
+                    "completed": row.get::<_, i64>(2)?,
+                }))
+            },
+        )
+    }
+
+    pub fn issues(&self, repo: &str) -> Result<Value, Error> {
+        self.rows(
+            "SELECT issue, status, added_ledger, completed_ledger FROM issues
+             WHERE repo = ?1 ORDER BY added_ledger, issue",
+            [repo],
+            |row| {
+                Ok(json!({
+                    "issue": row.get::<_, String>(0)?,
+                    "status": row.get::<_, String>(1)?,
+                    "added_ledger": row.get::<_, u32>(2)?,
+                    "completed_ledger": row.get::<_, Option<u32>>(3)?,
+                }))
+            },
+        )
+    }
+
+    /// Funded issues nobody has completed yet, largest first.
+    pub fn open_bounties(&self) -> Result<Value, Error> {
+        let bounties = self.rows(
+            "SELECT b.repo, b.issue, b.token, b.amount FROM bounties b
+             JOIN issues i ON i.repo = b.repo AND i.issue = b.issue
+             WHERE i.status = 'unclaimed'
+             ORDER BY b.repo, b.issue",
+            [],
+            |row| {
+                Ok(json!({
+                    "repo": row.get::<_, String>(0)?,
+                    "issue": row.get::<_, String>(1)?,
+                    "token": row.get::<_, String>(2)?,
+                    "amount": row.get::<_, String>(3)?,
+                }))
+            },
+        )?;
+
+        //amounts are decimal text, so they are compared as i128 here rather than in SQL
+        let mut bounties = bounties.as_array().cloned().unwrap_or_default();
+        bounties.sort_by_key(|bounty| std::cmp::Reverse(amount(&bounty["amount"])));
+        Ok(Value::Array(bounties))
+    }
+
+    /// Totals per token and every payout received by `contributor`, from
+    /// completed issues and released grant tranches alike.
+    pub fn earnings(&self, contributor: &str) -> Result<Value, Error> {
+        let payouts = self.rows(
+            "SELECT repo, issue, grant_id, token, amount, ledger FROM payouts
+             WHERE recipient = ?1 ORDER BY ledger, rowid",
+            [contributor],
+            |row| {
+                Ok(json!({
+                    "repo": row.get::<_, Option<String>>(0)?,
+                    "issue": row.get::<_, Option<String>>(1)?,
+                    "grant": row.get::<_, Option<u32>>(2)?,
+                    "token": row.get::<_, String>(3)?,
+                    "amount": row.get::<_, String>(4)?,
+                    "ledger": row.get::<_, u32>(5)?,
+                }))
+            },
+        )?;
+
+        let mut sums: BTreeMap<&str, i128> = BTreeMap::new();
+        for payout in payouts.as_array().into_iter().flatten() {
+            let token = payout["token"].as_str().unwrap_or_default();
+            *sums.entry(token).or_default() += amount(&payout["amount"]).unwrap_or_default();
+        }
+        let totals: Vec<Value> = sums
+            .into_iter()
+            .map(|(token, sum)| json!({ "token": token, "amount": sum.to_string() }))
+            .collect();
+        Ok(json!({ "contributor": contributor, "totals": totals, "payouts": payouts }))
+    }
+
+    /// Every event about `repo`, oldest first, including those from before a rename.
+    pub fn timeline(&self, repo: &str) -> Result<Value, Error> {
+        self.rows(
+            "SELECT id, ledger, kind, issue, value, tx_hash FROM events
+             WHERE repo = ?1 ORDER BY id",
+            [repo],
+            |row| {
+                Ok(json!({
+                    "id": row.get::<_, String>(0)?,
+                    "ledger": row.get::<_, u32>(1)?,
+                    "kind": row.get::<_, String>(2)?,
+                    "issue": row.get::<_, Option<String>>(3)?,
+                    "value": serde_json::from_str::<Value>(&row.get::<_, String>(4)?)
+                        .unwrap_or(Value::Null),
+                    "tx_hash": row.get::<_, Option<String>>(5)?,
+                }))
+            },
+        )
+    }
+
+    fn rows<P: rusqlite::Params>(
+        &self,
+        sql: &str,
+        params: P,
+        map: impl FnMut(&rusqlite::Row) -> rusqlite::Result<Value>,
+    ) -> Result<Value, Error> {
+        let mut statement = self.conn.prepare(sql)?;
+        let rows = statement
+            .query_map(params, map)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Value::Array(rows))
+    }
+}
+
+//updates the materialized tables for the events that change them
+fn materialize(tx: &Transaction, event: &ContractEvent) -> Result<(), Error> {
+    let malformed = |reason: &str| Error::Event {
+        id: event.id.clone(),
+        reason: reason.to_string(),
+    };
+    let repo = || {
+        event
+            .topic_str(1)
+            .ok_or_else(|| malformed("missing repo topic"))
+    };
+    let issue = || {
+        event
+            .topic_str(2)
+            .ok_or_else(|| malformed("missing issue topic"))
+    };
+
+    match event.kind() {
+        "repo_add" => {
+            tx.execute(
+                "INSERT OR REPLACE INTO repos (name, added_ledger) VALUES (?1, ?2)",
+                params![repo()?, event.ledger],
+            )?;
+        }
+        "repo_rm" => {
+            for table in ["issues", "bounties"] {
+                tx.execute(&format!("DELETE FROM {table} WHERE repo = ?1"), [repo()?])?;
+            }
+            tx.execute("DELETE FROM repos WHERE name = ?1", [repo()?])?;
+        }
+        "issue_add" => {
+            for issue in strings(&event.value).ok_or_else(|| malformed("expected issues"))? {
+                tx.execute(
+                    "INSERT OR REPLACE INTO issues (repo, issue, status, added_ledger)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![repo()?, issue, UNCLAIMED, event.ledger],
+                )?;
+            }
+        }
+        "issue_rm" => {
+            for issue in strings(&event.value).ok_or_else(|| malformed("expected issues"))? {
+                for table in ["issues", "bounties"] {
+                    tx.execute(
+                        &format!("DELETE FROM {table} WHERE repo = ?1 AND issue = ?2"),
+                        params![repo()?, issue],
+                    )?;
+                }
+            }
+        }
+        "funded" => {
+            let token = event.value[0]
+                .as_str()
+                .ok_or_else(|| malformed("expected token"))?;
+            let funded = amount(&event.value[1]).ok_or_else(|| malformed("expected amount"))?;
+            let current: Option<String> = tx
+                .query_row(
+                    "SELECT amount FROM bounties WHERE repo = ?1 AND issue = ?2",
+                    params![repo()?, issue()?],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let total = match current {
+                Some(current) => current
+                    .parse::<i128>()
+                    .ok()
+                    .and_then(|current| current.checked_add(funded))
+                    .ok_or_else(|| malformed("bounty amount overflows"))?,
+                None => funded,
+            };
+            tx.execute(
+                "INSERT INTO bounties (repo, issue, token, amount) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (repo, issue) DO UPDATE SET amount = excluded.amount",
+                params![repo()?, issue()?, token, total.to_string()],
+            )?;
+        }
+        "complete" => {
+            tx.execute(
+                "UPDATE issues SET status = ?3, completed_ledger = ?4
+                 WHERE repo = ?1 AND issue = ?2",
+                params![repo()?, issue()?, COMPLETED, event.ledger],
+            )?;
+            for share in event.value.as_array().into_iter().flatten() {
+                tx.execute(
+                    "INSERT INTO claims (repo, issue, contributor, basis_points, ledger)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        repo()?,
+                        issue()?,
+                        share[0]
+                            .as_str()
+                            .ok_or_else(|| malformed("expected share"))?,
+                        share[1]
+                            .as_u64()
+                            .ok_or_else(|| malformed("expected share"))?,
+                        event.ledger,
+                    ],
+                )?;
+            }
+        }
+        "payout" => {
+            let token = event.value[0]
+                .as_str()
+                .ok_or_else(|| malformed("expected token"))?;
+            for payout in event.value[1].as_array().into_iter().flatten() {
+                tx.execute(
+                    "INSERT INTO payouts (repo, issue, token, recipient, amount, ledger)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        repo()?,
+                        issue()?,
+                        token,
+                        payout[0]
+                            .as_str()
+                            .ok_or_else(|| malformed("expected recipient"))?,
+                        amount(&payout[1])
+                            .ok_or_else(|| malformed("expected amount"))?
+                            .to_string(),
+                        event.ledger,
+                    ],
+                )?;
+            }
+            tx.execute(
+                "DELETE FROM bounties WHERE repo = ?1 AND issue = ?2",
+                params![repo()?, issue()?],
+            )?;
+        }
+        "tranche" => {
+            //grant tranches are paid per grant, not per issue
+            let grant_id = event
+                .topic
+                .get(1)
+                .and_then(Value::as_u64)
+                .ok_or_else(|| malformed("expected grant id"))?;
+            tx.execute(
+                "INSERT INTO payouts (grant_id, token, recipient, amount, ledger)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    grant_id,
+                    event.value[1]
+                        .as_str()
+                        .ok_or_else(|| malformed("expected token"))?,
+                    event.value[0]
+                        .as_str()
+                        .ok_or_else(|| malformed("expected grantee"))?,
+                    amount(&event.value[2])
+                        .ok_or_else(|| malformed("expected amount"))?
+                        .to_string(),
+                    event.ledger,
+                ],
+            )?;
+        }
+        "renamed" => {
+            let new = event
+                .value
+                .as_str()
+                .ok_or_else(|| malformed("expected new name"))?;
+            tx.execute(
+                "UPDATE repos SET name = ?2 WHERE name = ?1",
+                params![repo()?, new],
+            )?;
+            for table in ["issues", "bounties", "claims", "payouts", "events"] {
+                tx.execute(
+                    &format!("UPDATE {table} SET repo = ?2 WHERE repo = ?1"),
+                    params![repo()?, new],
+                )?;
+            }
+        }
+        "cleared" => {
+            tx.execute_batch("DELETE FROM repos; DELETE FROM issues; DELETE FROM bounties;")?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn strings(value: &Value) -> Option<Vec<&str>> {
+    value.as_array()?.iter().map(Value::as_str).collect()
+}
+
+//token amounts are i128 on-chain and arrive as decimal strings, which is also how they are stored
+fn amount(value: &Value) -> Option<i128> {
+    value.as_str()?.parse().ok()
+}
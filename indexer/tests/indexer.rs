@@ -0,0 +1,343 @@
+use std::cell::{Cell, RefCell};
+
+use github_oracle::types::config_data::ConfigData;
+use github_oracle::types::grant::{GrantData, Milestone};
+use github_oracle::types::issue_ref::IssueRef;
+use github_oracle::types::repo_governance::RepoGovernance;
+use github_oracle::{GithubOracleContract, GithubOracleContractClient};
+use github_oracle_indexer::api::route;
+use github_oracle_indexer::{
+    sync, ContractEvent, Error, EventPage, EventSource, FileSource, Store,
+};
+use serde_json::{json, Value};
+use soroban_sdk::testutils::{Address as _, EnvTestConfig, Events, Ledger};
+use soroban_sdk::xdr::{Limits, ScVal, WriteXdr};
+use soroban_sdk::{token, Address, Env, String, TryFromVal};
+
+//records the oracle's events in the shape `getEvents` returns them
+struct Recorder {
+    env: Env,
+    contract: Address,
+    seen: Cell<u32>,
+    events: RefCell<Vec<Value>>,
+}
+
+impl Recorder {
+    //captures the events since the last call, then moves to the next ledger
+    fn record(&self) {
+        let ledger = self.env.ledger().sequence();
+        let all = self.env.events().all();
+        let mut events = self.events.borrow_mut();
+        for (contract, topics, value) in all.slice(self.seen.get()..).iter() {
+            if contract != self.contract {
+                continue;
+            }
+            let topic: Vec<Value> = topics.iter().map(|topic| xdr(&self.env, topic)).collect();
+            let index = events.len();
+            events.push(json!({
+                "id": format!("{:019}-{:010}", u64::from(ledger) << 32, index),
+                "ledger": ledger,
+                "contractId": self.contract.to_string().to_string(),
+                "txHash": format!("{:064x}", index),
+                "topic": topic,
+                "value": xdr(&self.env, value),
+            }));
+        }
+        self.seen.set(all.len());
+        self.env
+            .ledger()
+            .with_mut(|ledger| ledger.sequence_number += 1);
+    }
+}
+
+fn xdr(env: &Env, value: soroban_sdk::Val) -> Value {
+    Value::String(
+        ScVal::try_from_val(env, &value)
+            .unwrap()
+            .to_xdr_base64(Limits::none())
+            .unwrap(),
+    )
+}
+
+//serves recorded events a few at a time, like a paginated `getEvents`
+struct StubRpc {
+    events: Vec<Value>,
+    page_size: usize,
+}
+
+impl EventSource for StubRpc {
+    fn fetch(&self, cursor: Option<&str>) -> Result<EventPage, Error> {
+        let events = self
+            .events
+            .iter()
+            .filter(|event| cursor.is_none_or(|cursor| event["id"].as_str().unwrap() > cursor))
+            .take(self.page_size)
+            .map(ContractEvent::from_rpc)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(EventPage {
+            cursor: events.last().map(|event| event.id.clone()),
+            events,
+        })
+    }
+}
+
+//more than an i64 holds
+const LARGE_BOUNTY: i128 = 100_000_000_000_000_000_000;
+
+struct Fixture {
+    events: Vec<Value>,
+    alice: std::string::String,
+    bob: std::string::String,
+    token: std::string::String,
+}
+
+//an oracle that tracks two repos, pays out one issue and a grant tranche, leaves one
+//issue funded beyond i64 and renames a repo
+fn setup_events() -> Fixture {
+    let mut env = Env::default();
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let contract = env.register_contract(None, GithubOracleContract);
+    let client = GithubOracleContractClient::new(&env, &contract);
+    let recorder = Recorder {
+        env: env.clone(),
+        contract: contract.clone(),
+        seen: Cell::new(0),
+        events: RefCell::new(Vec::new()),
+    };
+
+    let admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let funder = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&funder, &(LARGE_BOUNTY + 750));
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let sdk = String::from_str(&env, "sdf/soroban-sdk");
+    let cli = String::from_str(&env, "sdf/soroban-cli");
+    let issue = |name: &str| String::from_str(&env, name);
+
+    client.config(&ConfigData {
+        admin: admin.clone(),
+        repos: soroban_sdk::vec![&env],
+        max_repos: 10,
+        max_issues_per_repo: 10,
+        repo_governance: RepoGovernance::Admin,
+    });
+    recorder.record();
+    client.add_repos(&soroban_sdk::vec![&env, sdk.clone(), cli.clone()]);
+    recorder.record();
+    client.add_issues(
+        &sdk,
+        &soroban_sdk::vec![&env, issue("1"), issue("2"), issue("3"), issue("4")],
+    );
+    recorder.record();
+    client.add_issues(&cli, &soroban_sdk::vec![&env, issue("7")]);
+    recorder.record();
+    client.fund_issue(&funder, &sdk, &issue("1"), &token, &600);
+    recorder.record();
+    client.fund_issue(&funder, &sdk, &issue("2"), &token, &LARGE_BOUNTY);
+    recorder.record();
+    client.fund_issue(&funder, &cli, &issue("7"), &token, &100);
+    recorder.record();
+    client.complete_issue(
+        &admin,
+        &sdk,
+        &issue("1"),
+        &soroban_sdk::vec![&env, (alice.clone(), 5_000), (bob.clone(), 5_000)],
+    );
+    recorder.record();
+    client.create_grant(&GrantData {
+        funder: funder.clone(),
+        grantee: alice.clone(),
+        token: token.clone(),
+        milestones: soroban_sdk::vec![
+            &env,
+            Milestone {
+                issues: soroban_sdk::vec![
+                    &env,
+                    IssueRef {
+                        repo: sdk.clone(),
+                        issue: issue("4"),
+                    }
+                ],
+                amount: 50,
+            }
+        ],
+    });
+    recorder.record();
+    client.complete_issue(
+        &admin,
+        &sdk,
+        &issue("4"),
+        &soroban_sdk::vec![&env, (bob.clone(), 10_000)],
+    );
+    recorder.record();
+    client.remove_issues(&sdk, &soroban_sdk::vec![&env, issue("3")]);
+    recorder.record();
+    client.rename_repo(&sdk, &String::from_str(&env, "stellar/rs-soroban-sdk"));
+    recorder.record();
+
+    Fixture {
+        events: recorder.events.into_inner(),
+        alice: alice.to_string().to_string(),
+        bob: bob.to_string().to_string(),
+        token: token.to_string().to_string(),
+    }
+}
+
+fn get(store: &Store, url: &str) -> Value {
+    let (status, body) = route(store, "GET", url);
+    assert_eq!(status, 200, "{url}: {body}");
+    body
+}
+
+#[test]
+fn indexes_events_from_a_paginated_feed() {
+    let fixture = setup_events();
+    let mut store = Store::open_in_memory().unwrap();
+    let source = StubRpc {
+        events: fixture.events.clone(),
+        page_size: 2,
+    };
+
+    assert_eq!(sync(&mut store, &source).unwrap(), fixture.events.len());
+    assert_eq!(
+        store.cursor().unwrap().as_deref(),
+        fixture.events.last().unwrap()["id"].as_str()
+    );
+    //caught up, nothing new to apply
+    assert_eq!(sync(&mut store, &source).unwrap(), 0);
+
+    assert_eq!(
+        get(&store, "/repos"),
+        json!([
+            { "repo": "sdf/soroban-cli", "unclaimed": 1, "completed": 0 },
+            { "repo": "stellar/rs-soroban-sdk", "unclaimed": 1, "completed": 2 },
+        ])
+    );
+    assert_eq!(
+        get(&store, "/bounties"),
+        json!([
+            { "repo": "stellar/rs-soroban-sdk", "issue": "2", "token": fixture.token, "amount": LARGE_BOUNTY.to_string() },
+            { "repo": "sdf/soroban-cli", "issue": "7", "token": fixture.token, "amount": "100" },
+        ])
+    );
+
+    let issues = get(&store, "/repos/stellar/rs-soroban-sdk/issues");
+    let statuses: Vec<(&str, &str)> = issues
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| {
+            (
+                issue["issue"].as_str().unwrap(),
+                issue["status"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        statuses,
+        [("1", "completed"), ("2", "unclaimed"), ("4", "completed")]
+    );
+}
+
+#[test]
+fn serves_earnings_and_timelines_across_renames() {
+    let fixture = setup_events();
+    let mut store = Store::open_in_memory().unwrap();
+    sync(
+        &mut store,
+        &StubRpc {
+            events: fixture.events,
+            page_size: 100,
+        },
+    )
+    .unwrap();
+
+    let earnings = get(&store, &format!("/contributors/{}/earnings", fixture.alice));
+    assert_eq!(
+        earnings["totals"],
+        json!([{ "token": fixture.token, "amount": "350" }])
+    );
+    assert_eq!(earnings["payouts"][0]["repo"], "stellar/rs-soroban-sdk");
+    assert_eq!(earnings["payouts"][0]["issue"], "1");
+    //the grant tranche released by completing issue 4
+    assert_eq!(earnings["payouts"][1]["grant"], 0);
+    assert_eq!(earnings["payouts"][1]["repo"], Value::Null);
+    assert_eq!(earnings["payouts"][1]["amount"], "50");
+    assert_eq!(
+        get(&store, &format!("/contributors/{}/earnings", fixture.bob))["totals"][0]["amount"],
+        "300"
+    );
+
+    //events from before the rename are listed under the new name
+    let kinds: Vec<std::string::String> = get(&store, "/repos/stellar/rs-soroban-sdk/timeline")
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| event["kind"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "repo_add",
+            "issue_add",
+            "funded",
+            "funded",
+            "payout",
+            "complete",
+            "complete",
+            "issue_rm",
+            "renamed",
+        ]
+    );
+    assert_eq!(
+        get(&store, "/repos/sdf/soroban-sdk/timeline"),
+        Value::Array(Vec::new())
+    );
+}
+
+#[test]
+fn replaying_a_file_export_is_idempotent() {
+    let fixture = setup_events();
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("events.json");
+    std::fs::write(
+        &path,
+        json!({ "events": fixture.events, "latestLedger": 100 }).to_string(),
+    )
+    .unwrap();
+
+    let mut store = Store::open(&dir.path().join("index.sqlite")).unwrap();
+    let source = FileSource::new(&path);
+    sync(&mut store, &source).unwrap();
+    let bounties = get(&store, "/bounties");
+
+    //a fresh cursor replays the file, but recorded events are skipped
+    let events: Vec<ContractEvent> = fixture
+        .events
+        .iter()
+        .map(ContractEvent::from_rpc)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(store.apply(&events).unwrap(), 0);
+    assert_eq!(get(&store, "/bounties"), bounties);
+}
+
+#[test]
+fn rejects_unknown_routes_and_writes() {
+    let store = Store::open_in_memory().unwrap();
+
+    assert_eq!(
+        route(&store, "GET", "/repos?page=2"),
+        (200, Value::Array(Vec::new()))
+    );
+    assert_eq!(route(&store, "GET", "/nope").0, 404);
+    assert_eq!(route(&store, "POST", "/repos").0, 405);
+}
@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use stellar_strkey::Strkey;
+use stellar_xdr::curr::{
+    AccountId, Hash, PublicKey, ScAddress, ScString, ScVal, ScVec, StringM, Uint256, VecM,
+};
+
+use crate::error::Error;
+use crate::webhook::{IssuesPayload, PullRequestPayload, PushPayload, Repository, WebhookEvent};
+
+// payout shares are expressed in basis points, a merged PR pays its author in full
+pub(crate) const TOTAL_BASIS_POINTS: u32 = 10_000;
+
+// commit message marker the attestor looks for when verifying repo ownership
+const VERIFICATION_MARKER: &str = "oracle-verify:";
+
+// keywords GitHub itself uses to link a PR to the issues it closes
+const CLOSING_KEYWORDS: [&str; 9] = [
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+];
+
+/// An invocation of one of the oracle's entrypoints.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContractCall {
+    AddRepos {
+        repos: Vec<String>,
+    },
+    AddIssues {
+        repo: String,
+        issues: Vec<String>,
+    },
+    RemoveIssues {
+        repo: String,
+        issues: Vec<String>,
+    },
+    SetIssueLabels {
+        caller: String,
+        repo: String,
+        issue: String,
+        labels: Vec<String>,
+    },
+    CompleteIssue {
+        caller: String,
+        repo: String,
+        issue: String,
+        shares: Vec<(String, u32)>,
+    },
+    ConfirmRepoVerification {
+        repo: String,
+        requester: String,
+        nonce: u64,
+    },
+    Clawback {
+        repo: String,
+        issue: String,
+        recipient: String,
+    },
+}
+
+impl ContractCall {
+    pub fn function_name(&self) -> &'static str {
+        match self {
+            ContractCall::AddRepos { .. } => "add_repos",
+            ContractCall::AddIssues { .. } => "add_issues",
+            ContractCall::RemoveIssues { .. } => "remove_issues",
+            ContractCall::SetIssueLabels { .. } => "set_issue_labels",
+            ContractCall::CompleteIssue { .. } => "complete_issue",
+            ContractCall::ConfirmRepoVerification { .. } => "confirm_repo_verification",
+            ContractCall::Clawback { .. } => "clawback",
+        }
+    }
+
+    pub fn args(&self) -> Result<Vec<ScVal>, Error> {
+        Ok(match self {
+            ContractCall::AddRepos { repos } => vec![strings_val(repos)?],
+            ContractCall::AddIssues { repo, issues }
+            | ContractCall::RemoveIssues { repo, issues } => {
+                vec![string_val(repo)?, strings_val(issues)?]
+            }
+            ContractCall::SetIssueLabels {
+                caller,
+                repo,
+                issue,
+                labels,
+            } => vec![
+                address_val(caller)?,
+                string_val(repo)?,
+                string_val(issue)?,
+                strings_val(labels)?,
+            ],
+            ContractCall::CompleteIssue {
+                caller,
+                repo,
+                issue,
+                shares,
+            } => {
+                let mut share_vals = Vec::new();
+                for (recipient, basis_points) in shares {
+                    share_vals.push(vec_val(vec![
+                        address_val(recipient)?,
+                        ScVal::U32(*basis_points),
+                    ])?);
+                }
+                vec![
+                    address_val(caller)?,
+                    string_val(repo)?,
+                    string_val(issue)?,
+                    vec_val(share_vals)?,
+                ]
+            }
+            ContractCall::ConfirmRepoVerification {
+                repo,
+                requester,
+                nonce,
+            } => vec![
+                string_val(repo)?,
+                address_val(requester)?,
+                ScVal::U64(*nonce),
+            ],
+            ContractCall::Clawback {
+                repo,
+                issue,
+                recipient,
+            } => vec![
+                string_val(repo)?,
+                string_val(issue)?,
+                address_val(recipient)?,
+            ],
+        })
+    }
+}
+
+/// Maps GitHub logins to the Stellar accounts that receive their payouts.
+#[derive(Clone, Debug, Default)]
+pub struct Contributors(HashMap<String, String>);
+
+impl Contributors {
+    pub fn new(accounts: HashMap<String, String>) -> Contributors {
+        Contributors(accounts)
+    }
+
+    pub fn from_json(json: &[u8]) -> Result<Contributors, Error> {
+        Ok(Contributors(serde_json::from_slice(json)?))
+    }
+
+    pub fn account(&self, login: &str) -> Option<&String> {
+        self.0.get(login)
+    }
+}
+
+/// Turns a webhook event into the contract calls that mirror it on-chain.
+/// `relayer_account` is the relayer's own address, passed where an entrypoint takes a caller.
+pub fn map_event(
+    event: &WebhookEvent,
+    relayer_account: &str,
+    contributors: &Contributors,
+) -> Vec<ContractCall> {
+    match event {
+        WebhookEvent::Issues(payload) => map_issues(payload, relayer_account),
+        WebhookEvent::PullRequest(payload) => {
+            map_pull_request(payload, relayer_account, contributors)
+        }
+        WebhookEvent::Push(payload) => map_push(payload),
+        WebhookEvent::Ping | WebhookEvent::Unsupported(_) => Vec::new(),
+    }
+}
+
+fn map_issues(payload: &IssuesPayload, relayer_account: &str) -> Vec<ContractCall> {
+    let repo = payload.repository.full_name.clone();
+    let issue = payload.issue.number.to_string();
+    match payload.action.as_str() {
+        "opened" => vec![ContractCall::AddIssues {
+            repo,
+            issues: vec![issue],
+        }],
+        "deleted" => vec![ContractCall::RemoveIssues {
+            repo,
+            issues: vec![issue],
+        }],
+        "labeled" | "unlabeled" => vec![ContractCall::SetIssueLabels {
+            caller: relayer_account.to_string(),
+            repo,
+            issue,
+            labels: payload
+                .issue
+                .labels
+                .iter()
+                .map(|label| label.name.clone())
+                .collect(),
+        }],
+        _ => Vec::new(),
+    }
+}
+
+fn map_pull_request(
+    payload: &PullRequestPayload,
+    relayer_account: &str,
+    contributors: &Contributors,
+) -> Vec<ContractCall> {
+    let pull_request = &payload.pull_request;
+    if payload.action != "closed" || !pull_request.merged {
+        return Vec::new();
+    }
+
+    //GitHub only closes referenced issues on merges into the default branch
+    if pull_request.base.git_ref != default_branch(&payload.repository) {
+        return Vec::new();
+    }
+
+    //authors without a registered account cannot be paid, so their issues stay open
+    let Some(account) = contributors.account(&pull_request.user.login) else {
+        return Vec::new();
+    };
+
+    let text = format!(
+        "{}\n{}",
+        pull_request.title,
+        pull_request.body.as_deref().unwrap_or_default()
+    );
+    closed_issues(&text)
+        .into_iter()
+        .map(|issue| ContractCall::CompleteIssue {
+            caller: relayer_account.to_string(),
+            repo: payload.repository.full_name.clone(),
+            issue: issue.to_string(),
+            shares: vec![(account.clone(), TOTAL_BASIS_POINTS)],
+        })
+        .collect()
+}
+
+fn map_push(payload: &PushPayload) -> Vec<ContractCall> {
+    //only commits landing on the default branch prove control over the repo
+    if payload.git_ref != format!("refs/heads/{}", default_branch(&payload.repository)) {
+        return Vec::new();
+    }
+
+    payload
+        .commits
+        .iter()
+        .filter_map(|commit| verification_challenge(&commit.message))
+        .map(|(requester, nonce)| ContractCall::ConfirmRepoVerification {
+            repo: payload.repository.full_name.clone(),
+            requester,
+            nonce,
+        })
+        .collect()
+}
+
+fn default_branch(repository: &Repository) -> &str {
+    repository.default_branch.as_deref().unwrap_or("main")
+}
+
+/// Issue numbers referenced with a closing keyword, such as `fixes #12`.
+pub fn closed_issues(text: &str) -> Vec<u64> {
+    let mut issues = Vec::new();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    for pair in words.windows(2) {
+        let keyword = pair[0].trim_end_matches(':').to_lowercase();
+        if !CLOSING_KEYWORDS.contains(&keyword.as_str()) {
+            continue;
+        }
+
+        let reference = pair[1].trim_end_matches(|c: char| !c.is_ascii_digit());
+        if let Some(Ok(issue)) = reference.strip_prefix('#').map(str::parse::<u64>) {
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+    }
+
+    issues
+}
+
+// `oracle-verify: <requester> <nonce>`, as challenges are kept per requester
+fn verification_challenge(message: &str) -> Option<(String, u64)> {
+    message.lines().find_map(|line| {
+        let mut words = line
+            .trim()
+            .strip_prefix(VERIFICATION_MARKER)?
+            .split_whitespace();
+        let requester = words.next()?.to_string();
+        let nonce = words.next()?.parse().ok()?;
+        Some((requester, nonce))
+    })
+}
+
+fn string_val(value: &str) -> Result<ScVal, Error> {
+    Ok(ScVal::String(ScString(StringM::try_from(value)?)))
+}
+
+fn strings_val(values: &[String]) -> Result<ScVal, Error> {
+    vec_val(
+        values
+            .iter()
+            .map(|value| string_val(value))
+            .collect::<Result<_, _>>()?,
+    )
+}
+
+fn vec_val(values: Vec<ScVal>) -> Result<ScVal, Error> {
+    Ok(ScVal::Vec(Some(ScVec(VecM::try_from(values)?))))
+}
+
+pub fn address_val(address: &str) -> Result<ScVal, Error> {
+    Ok(ScVal::Address(sc_address(address)?))
+}
+
+pub fn sc_address(address: &str) -> Result<ScAddress, Error> {
+    match Strkey::from_string(address) {
+        Ok(Strkey::PublicKeyEd25519(key)) => Ok(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256(key.0)),
+        ))),
+        Ok(Strkey::Contract(contract)) => Ok(ScAddress::Contract(Hash(contract.0))),
+        _ => Err(Error::InvalidKey(address.to_string())),
+    }
+}
@@ -0,0 +1,67 @@
+use std::env;
+use std::fs;
+
+use stellar_strkey::Strkey;
+
+use crate::calls::Contributors;
+use crate::error::Error;
+
+const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+const DEFAULT_BASE_FEE: u32 = 100;
+const DEFAULT_LISTEN_ADDR: &str = "0.0.0.0:8080";
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub webhook_secret: Vec<u8>,
+    /// `S...` seed of the account that signs and pays for the invocations.
+    pub secret_key: String,
+    pub contract_id: [u8; 32],
+    pub network_passphrase: String,
+    pub rpc_url: String,
+    pub base_fee: u32,
+    pub listen_addr: String,
+    pub contributors: Contributors,
+}
+
+impl Config {
+    /// Reads the `RELAYER_*` environment variables.
+    pub fn from_env() -> Result<Config, Error> {
+        let contributors = match env::var("RELAYER_CONTRIBUTORS") {
+            Ok(path) => Contributors::from_json(
+                &fs::read(&path).map_err(|err| Error::Config(format!("{}: {}", path, err)))?,
+            )?,
+            Err(_) => Contributors::default(),
+        };
+        let base_fee = match env::var("RELAYER_BASE_FEE") {
+            Ok(fee) => fee
+                .parse()
+                .map_err(|_| Error::Config("RELAYER_BASE_FEE".to_string()))?,
+            Err(_) => DEFAULT_BASE_FEE,
+        };
+
+        Ok(Config {
+            webhook_secret: required("RELAYER_WEBHOOK_SECRET")?.into_bytes(),
+            secret_key: required("RELAYER_SECRET_KEY")?,
+            contract_id: contract_id(&required("RELAYER_CONTRACT_ID")?)?,
+            network_passphrase: env::var("RELAYER_NETWORK_PASSPHRASE")
+                .unwrap_or_else(|_| TESTNET_PASSPHRASE.to_string()),
+            rpc_url: required("RELAYER_RPC_URL")?,
+            base_fee,
+            listen_addr: env::var("RELAYER_LISTEN_ADDR")
+                .unwrap_or_else(|_| DEFAULT_LISTEN_ADDR.to_string()),
+            contributors,
+        })
+    }
+}
+
+/// Decodes a `C...` contract address.
+pub fn contract_id(address: &str) -> Result<[u8; 32], Error> {
+    match Strkey::from_string(address) {
+        Ok(Strkey::Contract(contract)) => Ok(contract.0),
+        _ => Err(Error::InvalidKey(address.to_string())),
+    }
+}
+
+fn required(name: &str) -> Result<String, Error> {
+    env::var(name).map_err(|_| Error::Config(name.to_string()))
+}
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("missing X-Hub-Signature-256 header")]
+    MissingSignature,
+    #[error("webhook signature does not match the payload")]
+    InvalidSignature,
+    #[error("invalid webhook payload: {0}")]
+    Payload(#[from] serde_json::Error),
+    #[error("invalid GitHub export: {0}")]
+    Export(String),
+    #[error("invalid stellar key or address: {0}")]
+    InvalidKey(String),
+    #[error("xdr error: {0}")]
+    Xdr(#[from] stellar_xdr::curr::Error),
+    #[error("rpc error: {0}")]
+    Rpc(String),
+    #[error("rpc did not accept the transaction, try again later")]
+    TryAgainLater,
+    #[error("transaction {hash} failed: {result}")]
+    TransactionFailed { hash: String, result: String },
+    #[error("transaction {0} was sent but not applied in time, check it before resending")]
+    Unconfirmed(String),
+    #[error("missing or invalid configuration: {0}")]
+    Config(String),
+}
@@ -0,0 +1,118 @@
+use std::cell::Cell;
+use std::thread;
+use std::time::Duration;
+
+use stellar_xdr::curr::{ScVal, Transaction};
+
+use crate::error::Error;
+use crate::rpc::{Rpc, TransactionStatus};
+use crate::tx::{apply_simulation, invocation_transaction, Signer, Simulation};
+
+//ledgers close every few seconds, so a transaction is applied within a handful of polls
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const POLL_ATTEMPTS: u32 = 30;
+//how often a submission the server turned away with TRY_AGAIN_LATER is repeated
+const SEND_ATTEMPTS: u32 = 3;
+
+/// Simulates and submits invocations of one oracle contract from one account.
+pub struct Invoker<R: Rpc> {
+    signer: Signer,
+    rpc: R,
+    contract_id: [u8; 32],
+    base_fee: u32,
+    //sequence of the last transaction sent, RPC lags behind until it is applied
+    sequence: Cell<Option<i64>>,
+}
+
+impl<R: Rpc> Invoker<R> {
+    pub fn new(signer: Signer, rpc: R, contract_id: [u8; 32], base_fee: u32) -> Invoker<R> {
+        Invoker {
+            signer,
+            rpc,
+            contract_id,
+            base_fee,
+            sequence: Cell::new(None),
+        }
+    }
+
+    pub fn signer(&self) -> &Signer {
+        &self.signer
+    }
+
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    /// Runs the invocation in simulation only, which is how views are read.
+    pub fn simulate(&self, function: &str, args: Vec<ScVal>) -> Result<Simulation, Error> {
+        let (_, simulation) = self.prepare(function, args)?;
+        Ok(simulation)
+    }
+
+    /// Simulates, signs and sends the invocation, then waits until it is applied and returns
+    /// the transaction hash.
+    pub fn send(&self, function: &str, args: Vec<ScVal>) -> Result<String, Error> {
+        let (mut tx, simulation) = self.prepare(function, args)?;
+        apply_simulation(&mut tx, simulation)?;
+        let sequence = tx.seq_num.0;
+        let envelope = self.signer.sign(tx)?;
+        let mut attempt = 1;
+        let hash = loop {
+            match self.rpc.send(&envelope) {
+                Ok(hash) => break hash,
+                Err(Error::TryAgainLater) if attempt < SEND_ATTEMPTS => {
+                    attempt += 1;
+                    thread::sleep(POLL_INTERVAL);
+                }
+                Err(err) => {
+                    //the account may not have consumed the sequence, so read it again next time
+                    self.sequence.set(None);
+                    return Err(err);
+                }
+            }
+        };
+        //an accepted transaction consumes the sequence even when it fails
+        self.sequence.set(Some(sequence));
+        self.confirm(hash)
+    }
+
+    fn confirm(&self, hash: String) -> Result<String, Error> {
+        for attempt in 0..POLL_ATTEMPTS {
+            if attempt > 0 {
+                thread::sleep(POLL_INTERVAL);
+            }
+            match self.rpc.transaction_status(&hash)? {
+                TransactionStatus::Pending => {}
+                TransactionStatus::Success => return Ok(hash),
+                TransactionStatus::Failed(result) => {
+                    return Err(Error::TransactionFailed { hash, result })
+                }
+            }
+        }
+        Err(Error::Unconfirmed(hash))
+    }
+
+    fn prepare(
+        &self,
+        function: &str,
+        args: Vec<ScVal>,
+    ) -> Result<(Transaction, Simulation), Error> {
+        let public_key = self.signer.public_key();
+        let sequence = match self.sequence.get() {
+            Some(sequence) => sequence,
+            None => self.rpc.sequence_number(public_key)?,
+        } + 1;
+        let tx = invocation_transaction(
+            public_key,
+            sequence,
+            self.base_fee,
+            self.contract_id,
+            function,
+            args,
+        )?;
+
+        //simulation only needs the transaction body, the signature is checked on submission
+        let simulation = self.rpc.simulate(&self.signer.sign(tx.clone())?)?;
+        Ok((tx, simulation))
+    }
+}
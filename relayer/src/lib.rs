@@ -0,0 +1,22 @@
+//! Off-chain relayer that turns GitHub webhook deliveries into signed
+//! `github_oracle` contract invocations.
+
+pub mod calls;
+pub mod config;
+pub mod error;
+pub mod invoker;
+pub mod reconcile;
+pub mod relay;
+pub mod rpc;
+pub mod scval;
+pub mod signature;
+pub mod tx;
+pub mod webhook;
+
+pub use calls::{ContractCall, Contributors};
+pub use config::Config;
+pub use error::Error;
+pub use invoker::Invoker;
+pub use reconcile::{reconcile, GithubExport, OracleState, Reconciliation};
+pub use relay::{Relayer, Submission};
+pub use rpc::{HttpRpc, Rpc, TransactionStatus};
@@ -0,0 +1,94 @@
+use std::io::Read;
+use std::process;
+
+use github_oracle_relayer::{Config, Error, HttpRpc, Relayer};
+use serde_json::{json, Value};
+use tiny_http::{Header, Request, Response, Server};
+
+//payloads of the events the relayer maps are a few KiB, GitHub caps them at 25 MB
+const MAX_BODY: u64 = 1024 * 1024;
+
+fn main() {
+    let config = Config::from_env().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    let server = Server::http(&config.listen_addr).unwrap_or_else(|err| {
+        eprintln!("cannot listen on {}: {}", config.listen_addr, err);
+        process::exit(1);
+    });
+    let relayer =
+        Relayer::new(config.clone(), HttpRpc::new(&config.rpc_url)).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            process::exit(1);
+        });
+
+    for request in server.incoming_requests() {
+        handle_request(&relayer, request);
+    }
+}
+
+fn handle_request(relayer: &Relayer<HttpRpc>, mut request: Request) {
+    let event_name = header(&request, "X-GitHub-Event").unwrap_or_default();
+    let signature = header(&request, "X-Hub-Signature-256");
+
+    let mut body = Vec::new();
+    if let Err(err) = request
+        .as_reader()
+        .take(MAX_BODY + 1)
+        .read_to_end(&mut body)
+    {
+        eprintln!("failed to read webhook body: {}", err);
+        return;
+    }
+    if body.len() as u64 > MAX_BODY {
+        eprintln!("{} delivery is over {} bytes", event_name, MAX_BODY);
+        let error = json!({ "error": "payload too large" });
+        return respond(request, 413, error);
+    }
+
+    let (status, body) = match relayer.handle(&event_name, signature.as_deref(), &body) {
+        Ok(submissions) => {
+            let mut status = 200;
+            let transactions: Vec<Value> = submissions
+                .iter()
+                .map(|submission| match &submission.result {
+                    Ok(hash) => json!({ "function": submission.function, "hash": hash }),
+                    Err(err) => {
+                        eprintln!("{} call failed: {}", submission.function, err);
+                        status = 502;
+                        json!({ "function": submission.function, "error": err.to_string() })
+                    }
+                })
+                .collect();
+            (status, json!({ "transactions": transactions }))
+        }
+        Err(err) => {
+            eprintln!("{} delivery failed: {}", event_name, err);
+            let status = match err {
+                Error::MissingSignature | Error::InvalidSignature => 401,
+                Error::Payload(_) => 400,
+                _ => 502,
+            };
+            (status, json!({ "error": err.to_string() }))
+        }
+    };
+    respond(request, status, body);
+}
+
+fn respond(request: Request, status: u16, body: Value) {
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    if let Err(err) = request.respond(response) {
+        eprintln!("failed to respond: {}", err);
+    }
+}
+
+fn header(request: &Request, name: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.to_string())
+}
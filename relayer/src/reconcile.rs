@@ -0,0 +1,377 @@
+//! Compares a GitHub REST export with the oracle's registry and plans the
+//! calls that bring the registry back in line.
+//!
+//! Issues are either `unclaimed` or `completed` on-chain. Completing one records
+//! a claim: the contributors it paid, with whatever is still vesting. A claim on
+//! an issue GitHub has reopened or closed as not planned, e.g. after the merged PR
+//! was reverted, is stale, and its unvested remainder is clawed back.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::calls::{closed_issues, ContractCall, Contributors, TOTAL_BASIS_POINTS};
+use crate::error::Error;
+use crate::webhook::User;
+
+/// An issue status as stored by the oracle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Unclaimed,
+    Completed,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Unclaimed => "unclaimed",
+            Status::Completed => "completed",
+        }
+    }
+}
+
+/// One way the registry disagrees with GitHub.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Drift {
+    /// The repo has exported issues but is not tracked.
+    MissingRepo { repo: String },
+    /// The issue is open on GitHub but not tracked.
+    MissingIssue { repo: String, issue: String },
+    /// The issue is tracked but was closed on GitHub without being completed.
+    UnknownIssue { repo: String, issue: String },
+    StatusMismatch {
+        repo: String,
+        issue: String,
+        github: Status,
+        chain: String,
+    },
+    /// The issue was paid out with a vesting remainder, but GitHub no longer shows it
+    /// as completed.
+    StaleClaim {
+        repo: String,
+        issue: String,
+        claimant: String,
+    },
+}
+
+impl Drift {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Drift::MissingRepo { .. } => "missing_repo",
+            Drift::MissingIssue { .. } => "missing_issue",
+            Drift::UnknownIssue { .. } => "unknown_issue",
+            Drift::StatusMismatch { .. } => "status_mismatch",
+            Drift::StaleClaim { .. } => "stale_claim",
+        }
+    }
+}
+
+/// The drift found and the calls planned to converge.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Reconciliation {
+    pub drift: Vec<Drift>,
+    pub calls: Vec<ContractCall>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ExportedIssue {
+    number: u64,
+    state: String,
+    #[serde(default)]
+    state_reason: Option<String>,
+    repository_url: String,
+    //the issues endpoint lists pull requests too, marked by this field
+    #[serde(default)]
+    pull_request: Option<Value>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ExportedPullBase {
+    repo: ExportedPullRepo,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ExportedPullRepo {
+    full_name: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ExportedPull {
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    merged_at: Option<String>,
+    user: User,
+    base: ExportedPullBase,
+}
+
+/// What GitHub says about one issue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Expected {
+    Open,
+    //closed as completed, with the author of the merged PR that closed it if known
+    Completed(Option<String>),
+    Dropped,
+}
+
+/// Issues and pull requests saved from the GitHub REST API, for any number of repos.
+#[derive(Clone, Debug, Default)]
+pub struct GithubExport {
+    issues: BTreeMap<String, BTreeMap<String, Expected>>,
+    //(repo, issue) -> login of the author of the merged PR closing it
+    closers: BTreeMap<(String, String), String>,
+}
+
+impl GithubExport {
+    /// Adds a JSON array from `GET /repos/{owner}/{repo}/issues?state=all`.
+    pub fn add_issues(&mut self, json: &[u8]) -> Result<(), Error> {
+        let issues: Vec<ExportedIssue> = parse(json)?;
+        for issue in issues
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+        {
+            let repo = repo_from_url(&issue.repository_url)?;
+            let expected = match (issue.state.as_str(), issue.state_reason.as_deref()) {
+                ("open", _) => Expected::Open,
+                ("closed", Some("completed")) => Expected::Completed(None),
+                _ => Expected::Dropped,
+            };
+            self.issues
+                .entry(repo)
+                .or_default()
+                .insert(issue.number.to_string(), expected);
+        }
+        Ok(())
+    }
+
+    /// Adds a JSON array from `GET /repos/{owner}/{repo}/pulls?state=all`.
+    pub fn add_pulls(&mut self, json: &[u8]) -> Result<(), Error> {
+        let pulls: Vec<ExportedPull> = parse(json)?;
+        for pull in pulls.iter().filter(|pull| pull.merged_at.is_some()) {
+            let text = format!(
+                "{}\n{}",
+                pull.title,
+                pull.body.as_deref().unwrap_or_default()
+            );
+            for issue in closed_issues(&text) {
+                self.closers.insert(
+                    (pull.base.repo.full_name.clone(), issue.to_string()),
+                    pull.user.login.clone(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn repos(&self) -> impl Iterator<Item = &String> {
+        self.issues.keys()
+    }
+
+    fn expected(&self, repo: &str, issue: &str) -> Expected {
+        match self.issues.get(repo).and_then(|issues| issues.get(issue)) {
+            Some(Expected::Completed(_)) => Expected::Completed(
+                self.closers
+                    .get(&(repo.to_string(), issue.to_string()))
+                    .cloned(),
+            ),
+            Some(expected) => expected.clone(),
+            None => Expected::Dropped,
+        }
+    }
+}
+
+/// The registry as returned by `get_repos_and_issues`: repo -> issue -> status.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct OracleState {
+    #[serde(flatten)]
+    pub issues: BTreeMap<String, BTreeMap<String, String>>,
+    /// (repo, issue) pairs holding an escrow, which are never planned for removal.
+    #[serde(skip)]
+    pub escrowed: BTreeSet<(String, String)>,
+    /// (repo, issue) -> claimant, for completed issues whose payout is vesting.
+    #[serde(skip)]
+    pub claims: BTreeMap<(String, String), String>,
+}
+
+impl OracleState {
+    pub fn from_json(json: &[u8]) -> Result<OracleState, Error> {
+        parse(json)
+    }
+
+    pub fn mark_escrowed(&mut self, repo: &str, issue: &str) {
+        self.escrowed.insert((repo.to_string(), issue.to_string()));
+    }
+
+    /// Records a claim read from `get_vesting`, so it can be checked against GitHub.
+    pub fn mark_claimed(&mut self, repo: &str, issue: &str, claimant: &str) {
+        self.claims
+            .insert((repo.to_string(), issue.to_string()), claimant.to_string());
+    }
+}
+
+/// Completed issues that GitHub shows as open or dropped. Their claims, if any,
+/// are stale, so these are the issues to look up with `get_vesting` before reconciling.
+pub fn claim_candidates(export: &GithubExport, state: &OracleState) -> Vec<(String, String)> {
+    let mut candidates = Vec::new();
+    for (repo, issues) in &export.issues {
+        let Some(tracked) = state.issues.get(repo) else {
+            continue;
+        };
+        for issue in issues.keys() {
+            let completed =
+                tracked.get(issue).map(String::as_str) == Some(Status::Completed.as_str());
+            if completed && !matches!(export.expected(repo, issue), Expected::Completed(_)) {
+                candidates.push((repo.clone(), issue.clone()));
+            }
+        }
+    }
+    candidates
+}
+
+/// Diffs the exported repos against the registry. Repos and issues tracked
+/// on-chain but absent from the export are left alone, since exports are paged
+/// and nothing is known about them. Only issues the export shows as closed
+/// without being completed are removed, and only when they hold no escrow.
+/// Issues are completed as `caller`. Stale claims are clawed back to
+/// `clawback_recipient`, and only reported without one.
+pub fn reconcile(
+    export: &GithubExport,
+    state: &OracleState,
+    caller: &str,
+    contributors: &Contributors,
+    clawback_recipient: Option<&str>,
+) -> Reconciliation {
+    let mut reconciliation = Reconciliation::default();
+    let mut missing_repos = Vec::new();
+
+    for (repo, issues) in &export.issues {
+        let tracked = state.issues.get(repo);
+        if tracked.is_none() {
+            reconciliation
+                .drift
+                .push(Drift::MissingRepo { repo: repo.clone() });
+            missing_repos.push(repo.clone());
+        }
+
+        let mut to_add = Vec::new();
+        let mut to_remove = Vec::new();
+        let mut to_complete = Vec::new();
+        let mut to_claw_back = Vec::new();
+
+        for issue in issues.keys() {
+            let status = tracked.and_then(|tracked| tracked.get(issue));
+            let expected = export.expected(repo, issue);
+            let claim = state.claims.get(&(repo.clone(), issue.clone()));
+            if let (Some(claimant), false) = (claim, matches!(expected, Expected::Completed(_))) {
+                reconciliation.drift.push(Drift::StaleClaim {
+                    repo: repo.clone(),
+                    issue: issue.clone(),
+                    claimant: claimant.clone(),
+                });
+                to_claw_back.push(issue.clone());
+                continue;
+            }
+
+            match (expected, status) {
+                (Expected::Open, None) => {
+                    reconciliation.drift.push(Drift::MissingIssue {
+                        repo: repo.clone(),
+                        issue: issue.clone(),
+                    });
+                    to_add.push(issue.clone());
+                }
+                (Expected::Open, Some(status)) if status != Status::Unclaimed.as_str() => {
+                    //a completed issue cannot be reopened, so this is only reported
+                    reconciliation.drift.push(Drift::StatusMismatch {
+                        repo: repo.clone(),
+                        issue: issue.clone(),
+                        github: Status::Unclaimed,
+                        chain: status.clone(),
+                    });
+                }
+                (Expected::Completed(author), Some(status))
+                    if status != Status::Completed.as_str() =>
+                {
+                    reconciliation.drift.push(Drift::StatusMismatch {
+                        repo: repo.clone(),
+                        issue: issue.clone(),
+                        github: Status::Completed,
+                        chain: status.clone(),
+                    });
+                    if let Some(account) = author.and_then(|login| contributors.account(&login)) {
+                        to_complete.push((issue.clone(), account.clone()));
+                    }
+                }
+                (Expected::Dropped, Some(status)) if status == Status::Unclaimed.as_str() => {
+                    reconciliation.drift.push(Drift::UnknownIssue {
+                        repo: repo.clone(),
+                        issue: issue.clone(),
+                    });
+                    //the contract refuses to drop an escrowed issue, which would fail the batch
+                    if !state.escrowed.contains(&(repo.clone(), issue.clone())) {
+                        to_remove.push(issue.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if !to_add.is_empty() {
+            reconciliation.calls.push(ContractCall::AddIssues {
+                repo: repo.clone(),
+                issues: to_add,
+            });
+        }
+        if !to_remove.is_empty() {
+            reconciliation.calls.push(ContractCall::RemoveIssues {
+                repo: repo.clone(),
+                issues: to_remove,
+            });
+        }
+        for (issue, account) in to_complete {
+            reconciliation.calls.push(ContractCall::CompleteIssue {
+                caller: caller.to_string(),
+                repo: repo.clone(),
+                issue,
+                shares: vec![(account, TOTAL_BASIS_POINTS)],
+            });
+        }
+        if let Some(recipient) = clawback_recipient {
+            for issue in to_claw_back {
+                reconciliation.calls.push(ContractCall::Clawback {
+                    repo: repo.clone(),
+                    issue,
+                    recipient: recipient.to_string(),
+                });
+            }
+        }
+    }
+
+    //repos have to exist before any of their issues can be added
+    if !missing_repos.is_empty() {
+        reconciliation.calls.insert(
+            0,
+            ContractCall::AddRepos {
+                repos: missing_repos,
+            },
+        );
+    }
+    reconciliation
+}
+
+fn parse<T: serde::de::DeserializeOwned>(json: &[u8]) -> Result<T, Error> {
+    serde_json::from_slice(json).map_err(|err| Error::Export(err.to_string()))
+}
+
+//https://api.github.com/repos/{owner}/{repo}
+fn repo_from_url(url: &str) -> Result<String, Error> {
+    let mut segments = url.trim_end_matches('/').rsplit('/');
+    match (segments.next(), segments.next()) {
+        (Some(repo), Some(owner)) if !repo.is_empty() && !owner.is_empty() => {
+            Ok(format!("{owner}/{repo}"))
+        }
+        _ => Err(Error::Export(format!("unexpected repository_url {url}"))),
+    }
+}
@@ -0,0 +1,68 @@
+use crate::calls::{map_event, ContractCall};
+use crate::config::Config;
+use crate::error::Error;
+use crate::invoker::Invoker;
+use crate::rpc::Rpc;
+use crate::signature::verify_signature;
+use crate::tx::Signer;
+use crate::webhook::parse_event;
+
+/// The outcome of one contract call made for a delivery.
+#[derive(Debug)]
+pub struct Submission {
+    pub function: &'static str,
+    /// The transaction hash, or why the call was not applied.
+    pub result: Result<String, Error>,
+}
+
+/// Verifies webhook deliveries and submits the matching contract calls.
+pub struct Relayer<R: Rpc> {
+    config: Config,
+    invoker: Invoker<R>,
+}
+
+impl<R: Rpc> Relayer<R> {
+    pub fn new(config: Config, rpc: R) -> Result<Relayer<R>, Error> {
+        let signer = Signer::from_secret(&config.secret_key, &config.network_passphrase)?;
+        let invoker = Invoker::new(signer, rpc, config.contract_id, config.base_fee);
+        Ok(Relayer { config, invoker })
+    }
+
+    pub fn rpc(&self) -> &R {
+        self.invoker.rpc()
+    }
+
+    /// Handles one delivery and returns the outcome of every call it maps to. A failed call
+    /// does not stop the ones after it, so the caller sees which were applied.
+    pub fn handle(
+        &self,
+        event_name: &str,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> Result<Vec<Submission>, Error> {
+        verify_signature(
+            &self.config.webhook_secret,
+            body,
+            signature.ok_or(Error::MissingSignature)?,
+        )?;
+
+        let event = parse_event(event_name, body)?;
+        let calls = map_event(
+            &event,
+            &self.invoker.signer().account_id(),
+            &self.config.contributors,
+        );
+        Ok(calls
+            .iter()
+            .map(|call| Submission {
+                function: call.function_name(),
+                result: self.submit(call),
+            })
+            .collect())
+    }
+
+    /// Simulates, signs and sends a single call.
+    pub fn submit(&self, call: &ContractCall) -> Result<String, Error> {
+        self.invoker.send(call.function_name(), call.args()?)
+    }
+}
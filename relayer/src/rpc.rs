@@ -0,0 +1,166 @@
+use serde_json::{json, Value};
+use stellar_xdr::curr::{
+    AccountId, LedgerEntryData, LedgerKey, LedgerKeyAccount, Limits, PublicKey, ReadXdr, ScVal,
+    SorobanAuthorizationEntry, SorobanTransactionData, TransactionEnvelope, Uint256, WriteXdr,
+};
+
+use crate::error::Error;
+use crate::tx::Simulation;
+
+/// Where a submitted transaction stands, as reported by `getTransaction`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// Not applied yet, or not known to the server.
+    Pending,
+    Success,
+    /// Applied but failed, with the result XDR when the server reports it.
+    Failed(String),
+}
+
+/// The Soroban RPC methods the relayer depends on.
+pub trait Rpc {
+    /// Current sequence number of the account with the given ed25519 public key.
+    fn sequence_number(&self, public_key: [u8; 32]) -> Result<i64, Error>;
+    fn simulate(&self, envelope: &TransactionEnvelope) -> Result<Simulation, Error>;
+    /// Submits a signed transaction and returns its hash, or `Error::TryAgainLater`
+    /// when the server did not accept it.
+    fn send(&self, envelope: &TransactionEnvelope) -> Result<String, Error>;
+    fn transaction_status(&self, hash: &str) -> Result<TransactionStatus, Error>;
+}
+
+/// JSON-RPC client for a Soroban RPC server.
+pub struct HttpRpc {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl HttpRpc {
+    pub fn new(url: &str) -> HttpRpc {
+        HttpRpc {
+            url: url.to_string(),
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .map_err(|err| Error::Rpc(err.to_string()))?
+            .into_json()
+            .map_err(|err| Error::Rpc(err.to_string()))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(Error::Rpc(error.to_string()));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| Error::Rpc(format!("{} returned no result", method)))
+    }
+}
+
+impl Rpc for HttpRpc {
+    fn sequence_number(&self, public_key: [u8; 32]) -> Result<i64, Error> {
+        let key = LedgerKey::Account(LedgerKeyAccount {
+            account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(public_key))),
+        });
+        let result = self.call(
+            "getLedgerEntries",
+            json!({ "keys": [key.to_xdr_base64(Limits::none())?] }),
+        )?;
+
+        let entry = result["entries"][0]["xdr"]
+            .as_str()
+            .ok_or_else(|| Error::Rpc("relayer account not found".to_string()))?;
+        match LedgerEntryData::from_xdr_base64(entry, Limits::none())? {
+            LedgerEntryData::Account(account) => Ok(account.seq_num.0),
+            _ => Err(Error::Rpc("unexpected ledger entry".to_string())),
+        }
+    }
+
+    fn simulate(&self, envelope: &TransactionEnvelope) -> Result<Simulation, Error> {
+        let result = self.call(
+            "simulateTransaction",
+            json!({ "transaction": envelope.to_xdr_base64(Limits::none())? }),
+        )?;
+        if let Some(error) = result.get("error") {
+            return Err(Error::Rpc(error.to_string()));
+        }
+
+        let transaction_data = result["transactionData"]
+            .as_str()
+            .ok_or_else(|| Error::Rpc("simulation returned no transaction data".to_string()))?;
+        let min_resource_fee = result["minResourceFee"]
+            .as_str()
+            .and_then(|fee| fee.parse().ok())
+            .ok_or_else(|| Error::Rpc("simulation returned no resource fee".to_string()))?;
+        let mut auth = Vec::new();
+        for entry in result["results"][0]["auth"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            let entry = entry
+                .as_str()
+                .ok_or_else(|| Error::Rpc("malformed auth entry".to_string()))?;
+            auth.push(SorobanAuthorizationEntry::from_xdr_base64(
+                entry,
+                Limits::none(),
+            )?);
+        }
+
+        let result = match result["results"][0]["xdr"].as_str() {
+            Some(value) => Some(ScVal::from_xdr_base64(value, Limits::none())?),
+            None => None,
+        };
+
+        Ok(Simulation {
+            transaction_data: SorobanTransactionData::from_xdr_base64(
+                transaction_data,
+                Limits::none(),
+            )?,
+            min_resource_fee,
+            auth,
+            result,
+        })
+    }
+
+    fn send(&self, envelope: &TransactionEnvelope) -> Result<String, Error> {
+        let result = self.call(
+            "sendTransaction",
+            json!({ "transaction": envelope.to_xdr_base64(Limits::none())? }),
+        )?;
+        match result["status"].as_str() {
+            Some("ERROR") => return Err(Error::Rpc(result.to_string())),
+            Some("TRY_AGAIN_LATER") => return Err(Error::TryAgainLater),
+            _ => {}
+        }
+
+        result["hash"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| Error::Rpc("sendTransaction returned no hash".to_string()))
+    }
+
+    fn transaction_status(&self, hash: &str) -> Result<TransactionStatus, Error> {
+        let result = self.call("getTransaction", json!({ "hash": hash }))?;
+        match result["status"].as_str() {
+            Some("SUCCESS") => Ok(TransactionStatus::Success),
+            Some("FAILED") => Ok(TransactionStatus::Failed(
+                result["resultXdr"].as_str().unwrap_or_default().to_string(),
+            )),
+            Some("NOT_FOUND") => Ok(TransactionStatus::Pending),
+            _ => Err(Error::Rpc(format!(
+                "getTransaction returned an unknown status: {}",
+                result["status"]
+            ))),
+        }
+    }
+}
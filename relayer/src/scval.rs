@@ -0,0 +1,57 @@
+use serde_json::{Map, Number, Value};
+use stellar_strkey::{ed25519, Contract, Strkey};
+use stellar_xdr::curr::{
+    AccountId, Int128Parts, PublicKey, ScAddress, ScVal, UInt128Parts, Uint256,
+};
+
+/// Converts a contract value into JSON: addresses become strkeys, 128-bit
+/// numbers become decimal strings and bytes become hex.
+pub fn to_json(value: &ScVal) -> Value {
+    match value {
+        ScVal::Void => Value::Null,
+        ScVal::Bool(value) => Value::Bool(*value),
+        ScVal::U32(value) => Value::Number((*value).into()),
+        ScVal::I32(value) => Value::Number((*value).into()),
+        ScVal::U64(value) => Value::Number((*value).into()),
+        ScVal::I64(value) => Value::Number((*value).into()),
+        ScVal::Timepoint(value) => Value::Number(Number::from(value.0)),
+        ScVal::Duration(value) => Value::Number(Number::from(value.0)),
+        ScVal::U128(UInt128Parts { hi, lo }) => {
+            Value::String(((u128::from(*hi) << 64) | u128::from(*lo)).to_string())
+        }
+        ScVal::I128(Int128Parts { hi, lo }) => {
+            Value::String((((i128::from(*hi)) << 64) | i128::from(*lo)).to_string())
+        }
+        ScVal::Bytes(bytes) => Value::String(hex::encode(bytes.as_slice())),
+        ScVal::String(text) => Value::String(text.to_utf8_string_lossy()),
+        ScVal::Symbol(symbol) => Value::String(symbol.to_utf8_string_lossy()),
+        ScVal::Address(address) => Value::String(address_strkey(address)),
+        ScVal::Vec(items) => Value::Array(
+            items
+                .as_ref()
+                .map(|items| items.iter().map(to_json).collect())
+                .unwrap_or_default(),
+        ),
+        ScVal::Map(entries) => {
+            let mut object = Map::new();
+            for entry in entries.iter().flat_map(|entries| entries.iter()) {
+                let key = match to_json(&entry.key) {
+                    Value::String(key) => key,
+                    other => other.to_string(),
+                };
+                object.insert(key, to_json(&entry.val));
+            }
+            Value::Object(object)
+        }
+        other => Value::String(format!("{other:?}")),
+    }
+}
+
+pub fn address_strkey(address: &ScAddress) -> String {
+    match address {
+        ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key)))) => {
+            Strkey::PublicKeyEd25519(ed25519::PublicKey(*key)).to_string()
+        }
+        ScAddress::Contract(hash) => Strkey::Contract(Contract(hash.0)).to_string(),
+    }
+}
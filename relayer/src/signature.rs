@@ -0,0 +1,31 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::error::Error;
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// Checks an `X-Hub-Signature-256` header against the raw request body.
+pub fn verify_signature(secret: &[u8], body: &[u8], header: &str) -> Result<(), Error> {
+    let signature = header
+        .strip_prefix(SIGNATURE_PREFIX)
+        .and_then(|signature| hex::decode(signature).ok())
+        .ok_or(Error::InvalidSignature)?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("hmac accepts any key length");
+    mac.update(body);
+    //verify_slice compares in constant time
+    mac.verify_slice(&signature)
+        .map_err(|_| Error::InvalidSignature)
+}
+
+/// Produces the header GitHub would send for `body`, used by tests and tooling.
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("hmac accepts any key length");
+    mac.update(body);
+    format!(
+        "{}{}",
+        SIGNATURE_PREFIX,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
@@ -0,0 +1,120 @@
+use ed25519_dalek::{Signer as _, SigningKey};
+use sha2::{Digest, Sha256};
+use stellar_strkey::{ed25519, Strkey};
+use stellar_xdr::curr::{
+    DecoratedSignature, Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
+    MuxedAccount, Operation, OperationBody, Preconditions, ScAddress, ScSymbol, ScVal,
+    SequenceNumber, Signature, SignatureHint, SorobanAuthorizationEntry, SorobanTransactionData,
+    Transaction, TransactionEnvelope, TransactionExt, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, TransactionV1Envelope, Uint256, VecM, WriteXdr,
+};
+
+use crate::error::Error;
+
+/// What the RPC reports back when simulating an invocation.
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    pub transaction_data: SorobanTransactionData,
+    pub min_resource_fee: i64,
+    pub auth: Vec<SorobanAuthorizationEntry>,
+    /// The value the invocation returned, if any.
+    pub result: Option<ScVal>,
+}
+
+/// Signs transactions for one account on one network.
+pub struct Signer {
+    key: SigningKey,
+    network_id: Hash,
+}
+
+impl Signer {
+    pub fn new(secret_seed: [u8; 32], network_passphrase: &str) -> Signer {
+        Signer {
+            key: SigningKey::from_bytes(&secret_seed),
+            network_id: Hash(Sha256::digest(network_passphrase.as_bytes()).into()),
+        }
+    }
+
+    /// Parses an `S...` secret seed.
+    pub fn from_secret(secret: &str, network_passphrase: &str) -> Result<Signer, Error> {
+        match Strkey::from_string(secret) {
+            Ok(Strkey::PrivateKeyEd25519(key)) => Ok(Signer::new(key.0, network_passphrase)),
+            _ => Err(Error::InvalidKey("secret key".to_string())),
+        }
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.key.verifying_key().to_bytes()
+    }
+
+    /// The signer's `G...` account id.
+    pub fn account_id(&self) -> String {
+        Strkey::PublicKeyEd25519(ed25519::PublicKey(self.public_key())).to_string()
+    }
+
+    pub fn sign(&self, tx: Transaction) -> Result<TransactionEnvelope, Error> {
+        let payload = TransactionSignaturePayload {
+            network_id: self.network_id.clone(),
+            tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+        };
+        let hash = Sha256::digest(payload.to_xdr(Limits::none())?);
+        let public_key = self.public_key();
+
+        let signature = DecoratedSignature {
+            hint: SignatureHint(public_key[28..].try_into().unwrap()),
+            signature: Signature(self.key.sign(&hash).to_bytes().to_vec().try_into()?),
+        };
+        Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: vec![signature].try_into()?,
+        }))
+    }
+}
+
+/// Builds an unsimulated transaction invoking `function` on the oracle contract.
+pub fn invocation_transaction(
+    source: [u8; 32],
+    sequence: i64,
+    base_fee: u32,
+    contract_id: [u8; 32],
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<Transaction, Error> {
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                contract_address: ScAddress::Contract(Hash(contract_id)),
+                function_name: ScSymbol(function.try_into()?),
+                args: args.try_into()?,
+            }),
+            auth: VecM::default(),
+        }),
+    };
+
+    Ok(Transaction {
+        source_account: MuxedAccount::Ed25519(Uint256(source)),
+        fee: base_fee,
+        seq_num: SequenceNumber(sequence),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![operation].try_into()?,
+        ext: TransactionExt::V0,
+    })
+}
+
+/// Attaches the footprint, resource fee and auth entries from a simulation.
+pub fn apply_simulation(tx: &mut Transaction, simulation: Simulation) -> Result<(), Error> {
+    let resource_fee = u32::try_from(simulation.min_resource_fee)
+        .map_err(|_| Error::Rpc("resource fee out of range".to_string()))?;
+    tx.fee = tx.fee.saturating_add(resource_fee);
+    tx.ext = TransactionExt::V1(simulation.transaction_data);
+
+    let mut operations = tx.operations.to_vec();
+    if let OperationBody::InvokeHostFunction(op) = &mut operations[0].body {
+        op.auth = simulation.auth.try_into()?;
+    }
+    tx.operations = operations.try_into()?;
+
+    Ok(())
+}
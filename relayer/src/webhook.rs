@@ -0,0 +1,94 @@
+use serde::Deserialize;
+
+use crate::error::Error;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Repository {
+    pub full_name: String,
+    #[serde(default)]
+    pub default_branch: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct User {
+    pub login: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct IssuesPayload {
+    pub action: String,
+    pub issue: Issue,
+    pub repository: Repository,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Branch {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    #[serde(default)]
+    pub merged: bool,
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub user: User,
+    pub base: Branch,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PullRequestPayload {
+    pub action: String,
+    pub pull_request: PullRequest,
+    pub repository: Repository,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Commit {
+    pub id: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PushPayload {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    #[serde(default)]
+    pub commits: Vec<Commit>,
+    pub repository: Repository,
+}
+
+/// A webhook delivery, keyed by its `X-GitHub-Event` header.
+#[derive(Clone, Debug)]
+pub enum WebhookEvent {
+    Issues(IssuesPayload),
+    PullRequest(PullRequestPayload),
+    Push(PushPayload),
+    Ping,
+    Unsupported(String),
+}
+
+pub fn parse_event(event_name: &str, body: &[u8]) -> Result<WebhookEvent, Error> {
+    Ok(match event_name {
+        "issues" => WebhookEvent::Issues(serde_json::from_slice(body)?),
+        "pull_request" => WebhookEvent::PullRequest(serde_json::from_slice(body)?),
+        "push" => WebhookEvent::Push(serde_json::from_slice(body)?),
+        "ping" => WebhookEvent::Ping,
+        other => WebhookEvent::Unsupported(other.to_string()),
+    })
+}
@@ -0,0 +1,443 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use github_oracle_relayer::calls::map_event;
+use github_oracle_relayer::reconcile::{Drift, Status};
+use github_oracle_relayer::signature::sign;
+use github_oracle_relayer::tx::Simulation;
+use github_oracle_relayer::webhook::parse_event;
+use github_oracle_relayer::{
+    reconcile, Config, ContractCall, Contributors, Error, GithubExport, OracleState, Relayer, Rpc,
+    TransactionStatus,
+};
+use stellar_strkey::{ed25519, Contract, Strkey};
+use stellar_xdr::curr::{
+    ExtensionPoint, HostFunction, LedgerFootprint, OperationBody, ScString, ScVal,
+    SorobanResources, SorobanTransactionData, TransactionEnvelope, TransactionExt, VecM,
+};
+
+const SECRET: &[u8] = b"webhook-secret";
+const RESOURCE_FEE: i64 = 1_234;
+
+#[derive(Default)]
+struct StubRpc {
+    sent: RefCell<Vec<TransactionEnvelope>>,
+    //submissions turned away with TRY_AGAIN_LATER before one is accepted
+    congested: Cell<u32>,
+    //hashes whose transactions fail once applied
+    failing: RefCell<Vec<String>>,
+}
+
+impl Rpc for StubRpc {
+    fn sequence_number(&self, _public_key: [u8; 32]) -> Result<i64, Error> {
+        Ok(41)
+    }
+
+    fn simulate(&self, _envelope: &TransactionEnvelope) -> Result<Simulation, Error> {
+        Ok(Simulation {
+            transaction_data: SorobanTransactionData {
+                ext: ExtensionPoint::V0,
+                resources: SorobanResources {
+                    footprint: LedgerFootprint {
+                        read_only: VecM::default(),
+                        read_write: VecM::default(),
+                    },
+                    instructions: 1_000_000,
+                    read_bytes: 1_024,
+                    write_bytes: 1_024,
+                },
+                resource_fee: RESOURCE_FEE,
+            },
+            min_resource_fee: RESOURCE_FEE,
+            auth: Vec::new(),
+            result: None,
+        })
+    }
+
+    fn send(&self, envelope: &TransactionEnvelope) -> Result<String, Error> {
+        if self.congested.get() > 0 {
+            self.congested.set(self.congested.get() - 1);
+            return Err(Error::TryAgainLater);
+        }
+        let mut sent = self.sent.borrow_mut();
+        sent.push(envelope.clone());
+        Ok(format!("tx-{}", sent.len()))
+    }
+
+    fn transaction_status(&self, hash: &str) -> Result<TransactionStatus, Error> {
+        if self.failing.borrow().iter().any(|failing| failing == hash) {
+            return Ok(TransactionStatus::Failed("AAAAAA==".to_string()));
+        }
+        Ok(TransactionStatus::Success)
+    }
+}
+
+fn fixture(name: &str) -> Vec<u8> {
+    std::fs::read(format!(
+        "{}/tests/fixtures/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+    .unwrap()
+}
+
+fn account(seed: u8) -> String {
+    Strkey::PublicKeyEd25519(ed25519::PublicKey([seed; 32])).to_string()
+}
+
+fn contributors() -> Contributors {
+    Contributors::new(HashMap::from([("contributor".to_string(), account(9))]))
+}
+
+fn setup_relayer() -> Relayer<StubRpc> {
+    let config = Config {
+        webhook_secret: SECRET.to_vec(),
+        secret_key: Strkey::PrivateKeyEd25519(ed25519::PrivateKey([7; 32])).to_string(),
+        contract_id: [1; 32],
+        network_passphrase: "Test SDF Network ; September 2015".to_string(),
+        rpc_url: "http://localhost:8000/soroban/rpc".to_string(),
+        base_fee: 100,
+        listen_addr: "127.0.0.1:0".to_string(),
+        contributors: contributors(),
+    };
+
+    Relayer::new(config, StubRpc::default()).unwrap()
+}
+
+fn string(value: &str) -> ScVal {
+    ScVal::String(ScString(value.try_into().unwrap()))
+}
+
+#[test]
+fn deliveries_with_bad_signatures_are_rejected() {
+    let relayer = setup_relayer();
+    let body = fixture("issues_opened");
+
+    assert!(matches!(
+        relayer.handle("issues", None, &body),
+        Err(Error::MissingSignature)
+    ));
+    assert!(matches!(
+        relayer.handle("issues", Some(&sign(b"other-secret", &body)), &body),
+        Err(Error::InvalidSignature)
+    ));
+    assert!(matches!(
+        relayer.handle("issues", Some("sha256=zz"), &body),
+        Err(Error::InvalidSignature)
+    ));
+    assert!(relayer.rpc().sent.borrow().is_empty());
+}
+
+#[test]
+fn opened_issue_is_submitted_as_signed_add_issues() {
+    let relayer = setup_relayer();
+    let body = fixture("issues_opened");
+
+    let submissions = relayer
+        .handle("issues", Some(&sign(SECRET, &body)), &body)
+        .unwrap();
+    assert_eq!(submissions.len(), 1);
+    assert_eq!(submissions[0].function, "add_issues");
+    assert_eq!(submissions[0].result.as_ref().unwrap(), "tx-1");
+
+    let sent = relayer.rpc().sent.borrow();
+    let TransactionEnvelope::Tx(envelope) = &sent[0] else {
+        panic!("expected a v1 envelope");
+    };
+    assert_eq!(envelope.signatures.len(), 1);
+    assert_eq!(envelope.tx.seq_num.0, 42);
+    assert_eq!(envelope.tx.fee, 100 + RESOURCE_FEE as u32);
+    assert!(matches!(envelope.tx.ext, TransactionExt::V1(_)));
+
+    let OperationBody::InvokeHostFunction(op) = &envelope.tx.operations[0].body else {
+        panic!("expected a contract invocation");
+    };
+    let HostFunction::InvokeContract(invocation) = &op.host_function else {
+        panic!("expected a contract invocation");
+    };
+    assert_eq!(invocation.function_name.0.to_string(), "add_issues");
+    assert_eq!(invocation.args[0], string("sdf/soroban-sdk"));
+    assert_eq!(
+        invocation.args[1],
+        ScVal::Vec(Some(vec![string("42")].try_into().unwrap()))
+    );
+}
+
+#[test]
+fn transactions_from_one_delivery_use_consecutive_sequences() {
+    let relayer = setup_relayer();
+    let body = fixture("pull_request_merged");
+
+    let submissions = relayer
+        .handle("pull_request", Some(&sign(SECRET, &body)), &body)
+        .unwrap();
+    assert_eq!(submissions.len(), 2);
+
+    //the stub keeps reporting 41, as RPC does until the transactions are applied
+    let sequences: Vec<i64> = relayer
+        .rpc()
+        .sent
+        .borrow()
+        .iter()
+        .map(|sent| {
+            let TransactionEnvelope::Tx(envelope) = sent else {
+                panic!("expected a v1 envelope");
+            };
+            envelope.tx.seq_num.0
+        })
+        .collect();
+    assert_eq!(sequences, vec![42, 43]);
+}
+
+#[test]
+fn failed_calls_do_not_hide_the_ones_applied() {
+    let relayer = setup_relayer();
+    let body = fixture("pull_request_merged");
+    relayer.rpc().failing.borrow_mut().push("tx-1".to_string());
+
+    let submissions = relayer
+        .handle("pull_request", Some(&sign(SECRET, &body)), &body)
+        .unwrap();
+    assert_eq!(submissions.len(), 2);
+    assert!(matches!(
+        &submissions[0].result,
+        Err(Error::TransactionFailed { hash, .. }) if hash == "tx-1"
+    ));
+    assert_eq!(submissions[1].result.as_ref().unwrap(), "tx-2");
+}
+
+#[test]
+fn congested_rpc_is_retried_before_giving_up() {
+    let relayer = setup_relayer();
+    let body = fixture("issues_opened");
+
+    relayer.rpc().congested.set(1);
+    let submissions = relayer
+        .handle("issues", Some(&sign(SECRET, &body)), &body)
+        .unwrap();
+    assert_eq!(submissions[0].result.as_ref().unwrap(), "tx-1");
+
+    relayer.rpc().congested.set(u32::MAX);
+    let submissions = relayer
+        .handle("issues", Some(&sign(SECRET, &body)), &body)
+        .unwrap();
+    assert!(matches!(submissions[0].result, Err(Error::TryAgainLater)));
+    assert_eq!(relayer.rpc().sent.borrow().len(), 1);
+}
+
+#[test]
+fn labeled_issue_maps_to_set_issue_labels() {
+    let event = parse_event("issues", &fixture("issues_labeled")).unwrap();
+
+    assert_eq!(
+        map_event(&event, &account(3), &contributors()),
+        vec![ContractCall::SetIssueLabels {
+            caller: account(3),
+            repo: "sdf/soroban-sdk".to_string(),
+            issue: "42".to_string(),
+            labels: vec!["good first issue".to_string(), "area:token".to_string()],
+        }]
+    );
+}
+
+#[test]
+fn merged_pull_request_completes_closed_issues() {
+    let event = parse_event("pull_request", &fixture("pull_request_merged")).unwrap();
+
+    let calls = map_event(&event, &account(3), &contributors());
+    assert_eq!(
+        calls,
+        vec![
+            ContractCall::CompleteIssue {
+                caller: account(3),
+                repo: "sdf/soroban-sdk".to_string(),
+                issue: "42".to_string(),
+                shares: vec![(account(9), 10_000)],
+            },
+            ContractCall::CompleteIssue {
+                caller: account(3),
+                repo: "sdf/soroban-sdk".to_string(),
+                issue: "43".to_string(),
+                shares: vec![(account(9), 10_000)],
+            },
+        ]
+    );
+    calls[0].args().unwrap();
+
+    //authors without a registered account are skipped
+    assert!(map_event(&event, &account(3), &Contributors::default()).is_empty());
+}
+
+#[test]
+fn pull_request_merged_into_another_branch_is_skipped() {
+    let mut body: serde_json::Value =
+        serde_json::from_slice(&fixture("pull_request_merged")).unwrap();
+    body["pull_request"]["base"]["ref"] = "scratch".into();
+    let event = parse_event("pull_request", body.to_string().as_bytes()).unwrap();
+    assert!(map_event(&event, &account(3), &contributors()).is_empty());
+}
+
+#[test]
+fn push_to_default_branch_confirms_repo_verification() {
+    let event = parse_event("push", &fixture("push_verification")).unwrap();
+
+    assert_eq!(
+        map_event(&event, &account(3), &contributors()),
+        vec![ContractCall::ConfirmRepoVerification {
+            repo: "sdf/soroban-sdk".to_string(),
+            requester: account(4),
+            nonce: 15_761_228_913_722_148_321,
+        }]
+    );
+
+    let mut body: serde_json::Value =
+        serde_json::from_slice(&fixture("push_verification")).unwrap();
+    body["ref"] = "refs/heads/feature".into();
+    let event = parse_event("push", body.to_string().as_bytes()).unwrap();
+    assert!(map_event(&event, &account(3), &contributors()).is_empty());
+}
+
+#[test]
+fn contract_and_account_addresses_are_accepted() {
+    let call = ContractCall::SetIssueLabels {
+        caller: Strkey::Contract(Contract([5; 32])).to_string(),
+        repo: "sdf/soroban-sdk".to_string(),
+        issue: "42".to_string(),
+        labels: Vec::new(),
+    };
+    call.args().unwrap();
+
+    let call = ContractCall::SetIssueLabels {
+        caller: "not-an-address".to_string(),
+        repo: "sdf/soroban-sdk".to_string(),
+        issue: "42".to_string(),
+        labels: Vec::new(),
+    };
+    assert!(matches!(call.args(), Err(Error::InvalidKey(_))));
+}
+
+#[test]
+fn reconciliation_reports_drift_and_plans_converging_calls() {
+    let mut export = GithubExport::default();
+    export.add_issues(&fixture("export_issues")).unwrap();
+    export.add_pulls(&fixture("export_pulls")).unwrap();
+
+    let mut state = OracleState::from_json(
+        br#"{"sdf/soroban-sdk": {"1": "unclaimed", "2": "unclaimed", "3": "unclaimed",
+            "4": "completed", "9": "unclaimed"}}"#,
+    )
+    .unwrap();
+
+    let reconciliation = reconcile(&export, &state, &account(3), &contributors(), None);
+    let sdk = || "sdf/soroban-sdk".to_string();
+    let cli = || "sdf/soroban-cli".to_string();
+    assert_eq!(
+        reconciliation.drift,
+        vec![
+            Drift::MissingRepo { repo: cli() },
+            Drift::MissingIssue {
+                repo: cli(),
+                issue: "1".to_string(),
+            },
+            Drift::StatusMismatch {
+                repo: sdk(),
+                issue: "2".to_string(),
+                github: Status::Completed,
+                chain: "unclaimed".to_string(),
+            },
+            Drift::UnknownIssue {
+                repo: sdk(),
+                issue: "3".to_string(),
+            },
+            Drift::StatusMismatch {
+                repo: sdk(),
+                issue: "4".to_string(),
+                github: Status::Unclaimed,
+                chain: "completed".to_string(),
+            },
+            Drift::MissingIssue {
+                repo: sdk(),
+                issue: "5".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        reconciliation.calls,
+        vec![
+            ContractCall::AddRepos { repos: vec![cli()] },
+            ContractCall::AddIssues {
+                repo: cli(),
+                issues: vec!["1".to_string()],
+            },
+            ContractCall::AddIssues {
+                repo: sdk(),
+                issues: vec!["5".to_string()],
+            },
+            ContractCall::RemoveIssues {
+                repo: sdk(),
+                issues: vec!["3".to_string()],
+            },
+            ContractCall::CompleteIssue {
+                caller: account(3),
+                repo: sdk(),
+                issue: "2".to_string(),
+                shares: vec![(account(9), 10_000)],
+            },
+        ]
+    );
+
+    //an escrowed issue is reported but never removed
+    state.mark_escrowed("sdf/soroban-sdk", "3");
+    let reconciliation = reconcile(&export, &state, &account(3), &contributors(), None);
+    assert!(reconciliation.drift.contains(&Drift::UnknownIssue {
+        repo: sdk(),
+        issue: "3".to_string(),
+    }));
+    assert!(!reconciliation
+        .calls
+        .iter()
+        .any(|call| matches!(call, ContractCall::RemoveIssues { .. })));
+
+    //once converged there is nothing left to do but the unfixable mismatch
+    let mut state = OracleState::from_json(
+        br#"{"sdf/soroban-sdk": {"1": "unclaimed", "2": "completed", "4": "completed",
+            "5": "unclaimed"}, "sdf/soroban-cli": {"1": "unclaimed"}}"#,
+    )
+    .unwrap();
+    let reconciliation = reconcile(&export, &state, &account(3), &contributors(), None);
+    assert_eq!(reconciliation.drift.len(), 1);
+    assert!(reconciliation.calls.is_empty());
+
+    //a completed issue that is open again holds a stale claim while its payout vests
+    assert_eq!(
+        reconcile::claim_candidates(&export, &state),
+        vec![(sdk(), "4".to_string())]
+    );
+    state.mark_claimed("sdf/soroban-sdk", "4", &account(9));
+    let reconciliation = reconcile(&export, &state, &account(3), &contributors(), None);
+    assert_eq!(
+        reconciliation.drift,
+        vec![Drift::StaleClaim {
+            repo: sdk(),
+            issue: "4".to_string(),
+            claimant: account(9),
+        }]
+    );
+    assert!(reconciliation.calls.is_empty());
+    let reconciliation = reconcile(
+        &export,
+        &state,
+        &account(3),
+        &contributors(),
+        Some(&account(3)),
+    );
+    assert_eq!(
+        reconciliation.calls,
+        vec![ContractCall::Clawback {
+            repo: sdk(),
+            issue: "4".to_string(),
+            recipient: account(3),
+        }]
+    );
+    reconciliation.calls[0].args().unwrap();
+}
@@ -1,26 +1,276 @@
-use soroban_sdk::storage::Instance;
-use soroban_sdk::{panic_with_error, Address, Env, Map, String};
+use soroban_sdk::storage::{Instance, Persistent};
+use soroban_sdk::{panic_with_error, Address, BytesN, Env, IntoVal, Map, String, Val, Vec};
 
-use crate::types::error::Error;
+use crate::types::admin_action::AdminProposal;
+use crate::types::error::{Error, ExtendedError};
+use crate::types::escrow::Escrow;
+use crate::types::grant::Grant;
+use crate::types::issue_proposal::{IssueProposal, ProposalDepositConfig};
+use crate::types::issue_ref::IssueRef;
+use crate::types::limits::Limits;
+use crate::types::org::Org;
+use crate::types::read_pricing::{ReadPricing, ReadUsage};
+use crate::types::record::ImportState;
+use crate::types::registry_archive::{ClearRequest, RegistryArchive};
+use crate::types::repo_challenge::RepoChallenge;
+use crate::types::repo_governance::{RepoGovernance, RepoProposal};
+use crate::types::repo_state::RepoState;
+use crate::types::signer_set::SignerSet;
+use crate::types::stats::Stats;
+use crate::types::subscription::{Notification, Subscription, SubscriptionConfig};
+use crate::types::vesting::{Vesting, VestingSchedule};
 
 // constants
 const REPOS: &str = "repos";
 const ADMIN_KEY: &str = "admin";
+const SIGNER_SET_KEY: &str = "signer_set";
+const ADMIN_PROPOSAL: &str = "admin_proposal";
+const ADMIN_PROPOSAL_COUNT_KEY: &str = "admin_proposal_count";
+const LIMITS_KEY: &str = "limits";
+const REPO_ISSUE_LIMIT: &str = "repo_issue_limit";
+const CLEAR_REQUEST_KEY: &str = "clear_request";
+const ARCHIVE: &str = "archive";
+const ARCHIVE_COUNT_KEY: &str = "archive_count";
+const IMPORT_STATE_KEY: &str = "import_state";
+const ATTESTOR_KEY: &str = "attestor";
+const UPGRADER_KEY: &str = "upgrader";
+const REPO_CHALLENGE: &str = "repo_challenge";
+const REPO_MAINTAINER: &str = "repo_maintainer";
+const ORG: &str = "org";
+const ORGS_KEY: &str = "orgs";
+const REPO_ORG: &str = "repo_org";
+const REPO_STATE: &str = "repo_state";
+const REPO_ALIAS: &str = "repo_alias";
+const REPO_GOVERNANCE_KEY: &str = "repo_governance";
+const VOTERS_KEY: &str = "voters";
+const REPO_PROPOSAL: &str = "repo_proposal";
+const REPO_PROPOSAL_COUNT_KEY: &str = "repo_proposal_count";
+const ESCROW: &str = "escrow";
+const DUST_RECIPIENT_KEY: &str = "dust_recipient";
+const GRANT: &str = "grant";
+const GRANT_COUNT_KEY: &str = "grant_count";
+const ISSUE_GRANTS: &str = "issue_grants";
+const ISSUE_LABELS: &str = "issue_labels";
+const ISSUE_CLAIMANT: &str = "issue_claimant";
+const REPO_ROOTS_KEY: &str = "repo_roots";
+const STATE_ROOT_KEY: &str = "state_root";
+const LABEL_INDEX: &str = "label_index";
+const ISSUE_BLOCKERS: &str = "issue_blockers";
+const ISSUE_DEPENDENTS: &str = "issue_dependents";
+const REPO_STATS: &str = "repo_stats";
+const GLOBAL_STATS_KEY: &str = "global_stats";
+const REPO_CLAIMS: &str = "repo_claims";
+const CLAIMS: &str = "claims";
+const VESTING_SCHEDULE: &str = "vesting_schedule";
+const VESTING: &str = "vesting";
+const PROPOSAL_DEPOSIT_KEY: &str = "proposal_deposit";
+const ISSUE_PROPOSAL: &str = "issue_proposal";
+const ISSUE_PROPOSAL_COUNT_KEY: &str = "issue_proposal_count";
+const PENDING_ISSUE_PROPOSALS_KEY: &str = "pending_issue_proposals";
+const SUBSCRIPTION_CONFIG_KEY: &str = "subscription_config";
+const SUBSCRIPTION: &str = "subscription";
+const SUBSCRIPTION_COUNT_KEY: &str = "subscription_count";
+const REPO_SUBSCRIPTIONS: &str = "repo_subscriptions";
+const NOTIFICATION: &str = "notification";
+const NOTIFICATION_COUNT_KEY: &str = "notification_count";
+const READ_PRICING_KEY: &str = "read_pricing";
+const READ_USAGE: &str = "read_usage";
+const READ_CREDIT: &str = "read_credit";
+const READ_REVENUE_KEY: &str = "read_revenue";
 
 pub trait EnvExtensions {
     // admin
     fn get_admin(&self) -> Option<Address>;
     fn set_admin(&self, admin: &Address);
 
+    // signers
+    fn get_signer_set(&self) -> Option<SignerSet>;
+    fn set_signer_set(&self, signer_set: &SignerSet);
+    fn remove_signer_set(&self);
+    fn get_admin_proposal(&self, proposal_id: u32) -> Option<AdminProposal>;
+    fn set_admin_proposal(&self, proposal: &AdminProposal);
+    fn remove_admin_proposal(&self, proposal_id: u32);
+    fn get_admin_proposal_count(&self) -> u32;
+    fn set_admin_proposal_count(&self, count: u32);
+
     // initialization
     fn is_initialized(&self) -> bool;
 
     // authorization
     fn panic_if_not_admin(&self);
+    fn panic_if_not_single_admin(&self);
 
     // repos
     fn get_repos(&self) -> Map<String, Map<String, String>>;
     fn set_repos(&self, repos: Map<String, Map<String, String>>);
+    fn get_issue_claimant(&self, repo: &String, issue: &String) -> Option<Address>;
+    fn set_issue_claimant(&self, repo: &String, issue: &String, claimant: &Address);
+    fn remove_issue_claimant(&self, repo: &String, issue: &String);
+
+    // commitments
+    fn get_repo_roots(&self) -> Map<String, BytesN<32>>;
+    fn set_repo_roots(&self, roots: &Map<String, BytesN<32>>);
+    fn get_state_root(&self) -> Option<BytesN<32>>;
+    fn set_state_root(&self, root: &BytesN<32>);
+
+    // attestation
+    fn get_attestor(&self) -> Option<Address>;
+    fn set_attestor(&self, attestor: &Address);
+
+    // upgrades
+    fn get_upgrader(&self) -> Option<Address>;
+    fn set_upgrader(&self, upgrader: &Option<Address>);
+
+    // repo ownership
+    fn get_repo_challenge(&self, repo: &String, requester: &Address) -> Option<RepoChallenge>;
+    fn set_repo_challenge(&self, repo: &String, challenge: &RepoChallenge);
+    fn remove_repo_challenge(&self, repo: &String, requester: &Address);
+    fn get_repo_maintainer(&self, repo: &String) -> Option<Address>;
+    fn set_repo_maintainer(&self, repo: &String, maintainer: &Address);
+    fn remove_repo_maintainer(&self, repo: &String);
+
+    // orgs
+    fn get_org(&self, org: &String) -> Option<Org>;
+    fn set_org(&self, org: &Org);
+    fn get_orgs(&self) -> Vec<String>;
+    fn set_orgs(&self, orgs: &Vec<String>);
+    fn get_repo_org(&self, repo: &String) -> Option<String>;
+    fn set_repo_org(&self, repo: &String, org: &String);
+    fn remove_repo_org(&self, repo: &String);
+
+    // repo state
+    fn get_repo_state(&self, repo: &String) -> RepoState;
+    fn set_repo_state(&self, repo: &String, state: RepoState);
+    fn remove_repo_state(&self, repo: &String);
+
+    // ttl
+    fn extend_instance_ttl(&self, extend_to: u32);
+
+    // clearing
+    fn get_clear_request(&self) -> Option<ClearRequest>;
+    fn set_clear_request(&self, request: &ClearRequest);
+    fn remove_clear_request(&self);
+    fn get_archive(&self, archive_id: u32) -> Option<RegistryArchive>;
+    fn set_archive(&self, archive_id: u32, archive: &RegistryArchive);
+    fn get_archive_count(&self) -> u32;
+    fn set_archive_count(&self, count: u32);
+
+    // restoring
+    fn get_import_state(&self) -> Option<ImportState>;
+    fn set_import_state(&self, state: &ImportState);
+    fn remove_import_state(&self);
+    fn panic_if_importing(&self);
+
+    // aliases
+    fn get_repo_alias(&self, repo: &String) -> Option<String>;
+    fn set_repo_alias(&self, repo: &String, target: &String);
+    fn remove_repo_alias(&self, repo: &String);
+
+    // limits
+    fn get_limits(&self) -> Option<Limits>;
+    fn set_limits(&self, limits: &Limits);
+    fn get_repo_issue_limit(&self, repo: &String) -> Option<u32>;
+    fn set_repo_issue_limit(&self, repo: &String, limit: u32);
+    fn remove_repo_issue_limit(&self, repo: &String);
+
+    // repo governance
+    fn get_repo_governance(&self) -> RepoGovernance;
+    fn set_repo_governance(&self, governance: &RepoGovernance);
+    fn get_voters(&self) -> Vec<Address>;
+    fn set_voters(&self, voters: &Vec<Address>);
+    fn get_repo_proposal(&self, proposal_id: u32) -> Option<RepoProposal>;
+    fn set_repo_proposal(&self, proposal: &RepoProposal);
+    fn remove_repo_proposal(&self, proposal_id: u32);
+    fn get_repo_proposal_count(&self) -> u32;
+    fn set_repo_proposal_count(&self, count: u32);
+
+    // escrow
+    fn get_escrow(&self, repo: &String, issue: &String) -> Option<Escrow>;
+    fn set_escrow(&self, repo: &String, issue: &String, escrow: &Escrow);
+    fn remove_escrow(&self, repo: &String, issue: &String);
+
+    // payouts
+    fn get_dust_recipient(&self) -> Option<Address>;
+    fn set_dust_recipient(&self, recipient: &Address);
+
+    // grants
+    fn get_grant(&self, grant_id: u32) -> Option<Grant>;
+    fn set_grant(&self, grant_id: u32, grant: &Grant);
+    fn get_grant_count(&self) -> u32;
+    fn set_grant_count(&self, count: u32);
+    fn get_issue_grants(&self, repo: &String, issue: &String) -> Vec<u32>;
+    fn set_issue_grants(&self, repo: &String, issue: &String, grant_ids: &Vec<u32>);
+    fn remove_issue_grants(&self, repo: &String, issue: &String);
+
+    // labels
+    fn get_issue_labels(&self, repo: &String, issue: &String) -> Vec<String>;
+    fn set_issue_labels(&self, repo: &String, issue: &String, labels: &Vec<String>);
+    fn remove_issue_labels(&self, repo: &String, issue: &String);
+    fn get_label_index(&self, label: &String) -> Vec<IssueRef>;
+    fn set_label_index(&self, label: &String, issue_refs: &Vec<IssueRef>);
+
+    // dependencies
+    fn get_issue_blockers(&self, issue_ref: &IssueRef) -> Vec<IssueRef>;
+    fn set_issue_blockers(&self, issue_ref: &IssueRef, blockers: &Vec<IssueRef>);
+    fn get_issue_dependents(&self, issue_ref: &IssueRef) -> Vec<IssueRef>;
+    fn set_issue_dependents(&self, issue_ref: &IssueRef, dependents: &Vec<IssueRef>);
+
+    // stats
+    fn get_repo_stats(&self, repo: &String) -> Stats;
+    fn set_repo_stats(&self, repo: &String, stats: &Stats);
+    fn remove_repo_stats(&self, repo: &String);
+    fn get_global_stats(&self) -> Stats;
+    fn set_global_stats(&self, stats: &Stats);
+    fn get_repo_claims(&self, repo: &String) -> Map<Address, u32>;
+    fn set_repo_claims(&self, repo: &String, claims: &Map<Address, u32>);
+    fn remove_repo_claims(&self, repo: &String);
+    fn get_claims(&self, claimant: &Address) -> u32;
+    fn set_claims(&self, claimant: &Address, claims: u32);
+
+    // vesting
+    fn get_vesting_schedule(&self, repo: &String, issue: &String) -> Option<VestingSchedule>;
+    fn set_vesting_schedule(&self, repo: &String, issue: &String, schedule: &VestingSchedule);
+    fn remove_vesting_schedule(&self, repo: &String, issue: &String);
+    fn get_vesting(&self, repo: &String, issue: &String) -> Option<Vesting>;
+    fn set_vesting(&self, repo: &String, issue: &String, vesting: &Vesting);
+    fn remove_vesting(&self, repo: &String, issue: &String);
+
+    // issue proposals
+    fn get_proposal_deposit(&self) -> Option<ProposalDepositConfig>;
+    fn set_proposal_deposit(&self, config: &ProposalDepositConfig);
+    fn get_issue_proposal(&self, proposal_id: u32) -> Option<IssueProposal>;
+    fn set_issue_proposal(&self, proposal: &IssueProposal);
+    fn remove_issue_proposal(&self, proposal_id: u32);
+    fn get_issue_proposal_count(&self) -> u32;
+    fn set_issue_proposal_count(&self, count: u32);
+    fn get_pending_issue_proposals(&self) -> Vec<u32>;
+    fn set_pending_issue_proposals(&self, proposal_ids: &Vec<u32>);
+
+    // subscriptions
+    fn get_subscription_config(&self) -> Option<SubscriptionConfig>;
+    fn set_subscription_config(&self, config: &SubscriptionConfig);
+    fn get_subscription(&self, subscription_id: u32) -> Option<Subscription>;
+    fn set_subscription(&self, subscription: &Subscription);
+    fn remove_subscription(&self, subscription_id: u32);
+    fn get_subscription_count(&self) -> u32;
+    fn set_subscription_count(&self, count: u32);
+    fn get_repo_subscriptions(&self, repo: &String) -> Vec<u32>;
+    fn set_repo_subscriptions(&self, repo: &String, subscription_ids: &Vec<u32>);
+    fn get_notification(&self, notification_id: u32) -> Option<Notification>;
+    fn set_notification(&self, notification: &Notification);
+    fn remove_notification(&self, notification_id: u32);
+    fn get_notification_count(&self) -> u32;
+    fn set_notification_count(&self, count: u32);
+
+    // paid reads
+    fn get_read_pricing(&self) -> Option<ReadPricing>;
+    fn set_read_pricing(&self, pricing: &Option<ReadPricing>);
+    fn get_read_usage(&self, consumer: &Address, period_start_ledger: u32) -> Option<ReadUsage>;
+    fn set_read_usage(&self, consumer: &Address, usage: &ReadUsage, period_end_ledger: u32);
+    fn get_read_credit(&self, consumer: &Address) -> Map<Address, i128>;
+    fn set_read_credit(&self, consumer: &Address, credit: &Map<Address, i128>);
+    fn get_read_revenue(&self) -> Map<Address, i128>;
+    fn set_read_revenue(&self, revenue: &Map<Address, i128>);
 }
 
 impl EnvExtensions for Env {
@@ -33,6 +283,34 @@ impl EnvExtensions for Env {
     fn set_admin(&self, admin: &Address) {
         get_instance_storage(self).set(&ADMIN_KEY, admin);
     }
+
+    // signers
+    fn get_signer_set(&self) -> Option<SignerSet> {
+        get_instance_storage(self).get(&SIGNER_SET_KEY)
+    }
+    fn set_signer_set(&self, signer_set: &SignerSet) {
+        get_instance_storage(self).set(&SIGNER_SET_KEY, signer_set);
+    }
+    fn remove_signer_set(&self) {
+        get_instance_storage(self).remove(&SIGNER_SET_KEY);
+    }
+    fn get_admin_proposal(&self, proposal_id: u32) -> Option<AdminProposal> {
+        get_persistent_storage(self).get(&(ADMIN_PROPOSAL, proposal_id))
+    }
+    fn set_admin_proposal(&self, proposal: &AdminProposal) {
+        set_persistent(self, &(ADMIN_PROPOSAL, proposal.id), proposal);
+    }
+    fn remove_admin_proposal(&self, proposal_id: u32) {
+        get_persistent_storage(self).remove(&(ADMIN_PROPOSAL, proposal_id));
+    }
+    fn get_admin_proposal_count(&self) -> u32 {
+        get_instance_storage(self)
+            .get(&ADMIN_PROPOSAL_COUNT_KEY)
+            .unwrap_or(0)
+    }
+    fn set_admin_proposal_count(&self, count: u32) {
+        get_instance_storage(self).set(&ADMIN_PROPOSAL_COUNT_KEY, &count);
+    }
     fn is_initialized(&self) -> bool {
         get_instance_storage(self).has(&ADMIN_KEY)
     }
@@ -45,6 +323,13 @@ impl EnvExtensions for Env {
         }
         admin.unwrap().require_auth()
     }
+    fn panic_if_not_single_admin(&self) {
+        self.panic_if_not_admin();
+        //once a signer set is configured sensitive actions go through admin proposals
+        if self.get_signer_set().is_some() {
+            panic_with_error!(self, Error::MultisigRequired);
+        }
+    }
 
     // repos
     fn get_repos(&self) -> Map<String, Map<String, String>> {
@@ -55,8 +340,557 @@ impl EnvExtensions for Env {
     fn set_repos(&self, repos: Map<String, Map<String, String>>) {
         get_instance_storage(self).set(&REPOS, &repos);
     }
+    fn get_issue_claimant(&self, repo: &String, issue: &String) -> Option<Address> {
+        get_persistent_storage(self).get(&(ISSUE_CLAIMANT, repo.clone(), issue.clone()))
+    }
+    fn set_issue_claimant(&self, repo: &String, issue: &String, claimant: &Address) {
+        set_persistent(
+            self,
+            &(ISSUE_CLAIMANT, repo.clone(), issue.clone()),
+            claimant,
+        );
+    }
+    fn remove_issue_claimant(&self, repo: &String, issue: &String) {
+        get_persistent_storage(self).remove(&(ISSUE_CLAIMANT, repo.clone(), issue.clone()));
+    }
+
+    // commitments
+    fn get_repo_roots(&self) -> Map<String, BytesN<32>> {
+        get_instance_storage(self)
+            .get(&REPO_ROOTS_KEY)
+            .unwrap_or_else(|| Map::new(self))
+    }
+    fn set_repo_roots(&self, roots: &Map<String, BytesN<32>>) {
+        get_instance_storage(self).set(&REPO_ROOTS_KEY, roots);
+    }
+    fn get_state_root(&self) -> Option<BytesN<32>> {
+        get_instance_storage(self).get(&STATE_ROOT_KEY)
+    }
+    fn set_state_root(&self, root: &BytesN<32>) {
+        get_instance_storage(self).set(&STATE_ROOT_KEY, root);
+    }
+
+    // attestation
+    fn get_attestor(&self) -> Option<Address> {
+        get_instance_storage(self).get(&ATTESTOR_KEY)
+    }
+    fn set_attestor(&self, attestor: &Address) {
+        get_instance_storage(self).set(&ATTESTOR_KEY, attestor);
+    }
+
+    // upgrades
+    fn get_upgrader(&self) -> Option<Address> {
+        get_instance_storage(self).get(&UPGRADER_KEY)
+    }
+    fn set_upgrader(&self, upgrader: &Option<Address>) {
+        match upgrader {
+            Some(upgrader) => get_instance_storage(self).set(&UPGRADER_KEY, upgrader),
+            None => get_instance_storage(self).remove(&UPGRADER_KEY),
+        }
+    }
+
+    // repo ownership
+    fn get_repo_challenge(&self, repo: &String, requester: &Address) -> Option<RepoChallenge> {
+        self.storage()
+            .temporary()
+            .get(&(REPO_CHALLENGE, repo.clone(), requester.clone()))
+    }
+    //a challenge is only worth keeping until it expires
+    fn set_repo_challenge(&self, repo: &String, challenge: &RepoChallenge) {
+        let key = (REPO_CHALLENGE, repo.clone(), challenge.requester.clone());
+        let ttl = challenge.expires_at_ledger - self.ledger().sequence();
+        self.storage().temporary().set(&key, challenge);
+        self.storage().temporary().extend_ttl(&key, ttl, ttl);
+    }
+    fn remove_repo_challenge(&self, repo: &String, requester: &Address) {
+        self.storage()
+            .temporary()
+            .remove(&(REPO_CHALLENGE, repo.clone(), requester.clone()));
+    }
+    fn get_repo_maintainer(&self, repo: &String) -> Option<Address> {
+        get_instance_storage(self).get(&(REPO_MAINTAINER, repo.clone()))
+    }
+    fn set_repo_maintainer(&self, repo: &String, maintainer: &Address) {
+        get_instance_storage(self).set(&(REPO_MAINTAINER, repo.clone()), maintainer);
+    }
+    fn remove_repo_maintainer(&self, repo: &String) {
+        get_instance_storage(self).remove(&(REPO_MAINTAINER, repo.clone()));
+    }
+
+    // orgs
+    fn get_org(&self, org: &String) -> Option<Org> {
+        get_instance_storage(self).get(&(ORG, org.clone()))
+    }
+    fn set_org(&self, org: &Org) {
+        get_instance_storage(self).set(&(ORG, org.name.clone()), org);
+    }
+    fn get_orgs(&self) -> Vec<String> {
+        get_instance_storage(self)
+            .get(&ORGS_KEY)
+            .unwrap_or_else(|| Vec::new(self))
+    }
+    fn set_orgs(&self, orgs: &Vec<String>) {
+        get_instance_storage(self).set(&ORGS_KEY, orgs);
+    }
+    fn get_repo_org(&self, repo: &String) -> Option<String> {
+        get_instance_storage(self).get(&(REPO_ORG, repo.clone()))
+    }
+    fn set_repo_org(&self, repo: &String, org: &String) {
+        get_instance_storage(self).set(&(REPO_ORG, repo.clone()), org);
+    }
+    fn remove_repo_org(&self, repo: &String) {
+        get_instance_storage(self).remove(&(REPO_ORG, repo.clone()));
+    }
+
+    // repo state
+    fn get_repo_state(&self, repo: &String) -> RepoState {
+        get_instance_storage(self)
+            .get(&(REPO_STATE, repo.clone()))
+            .unwrap_or(RepoState::Active)
+    }
+    fn set_repo_state(&self, repo: &String, state: RepoState) {
+        get_instance_storage(self).set(&(REPO_STATE, repo.clone()), &state);
+    }
+    fn remove_repo_state(&self, repo: &String) {
+        get_instance_storage(self).remove(&(REPO_STATE, repo.clone()));
+    }
+
+    // ttl
+    fn extend_instance_ttl(&self, extend_to: u32) {
+        get_instance_storage(self).extend_ttl(extend_to, extend_to);
+    }
+
+    // clearing
+    fn get_clear_request(&self) -> Option<ClearRequest> {
+        get_instance_storage(self).get(&CLEAR_REQUEST_KEY)
+    }
+    fn set_clear_request(&self, request: &ClearRequest) {
+        get_instance_storage(self).set(&CLEAR_REQUEST_KEY, request);
+    }
+    fn remove_clear_request(&self) {
+        get_instance_storage(self).remove(&CLEAR_REQUEST_KEY);
+    }
+    fn get_archive(&self, archive_id: u32) -> Option<RegistryArchive> {
+        get_persistent_storage(self).get(&(ARCHIVE, archive_id))
+    }
+    fn set_archive(&self, archive_id: u32, archive: &RegistryArchive) {
+        let storage = get_persistent_storage(self);
+        let max_ttl = self.storage().max_ttl();
+        storage.set(&(ARCHIVE, archive_id), archive);
+        storage.extend_ttl(&(ARCHIVE, archive_id), max_ttl, max_ttl);
+    }
+    fn get_archive_count(&self) -> u32 {
+        get_instance_storage(self)
+            .get(&ARCHIVE_COUNT_KEY)
+            .unwrap_or(0)
+    }
+    fn set_archive_count(&self, count: u32) {
+        get_instance_storage(self).set(&ARCHIVE_COUNT_KEY, &count);
+    }
+
+    // restoring
+    fn get_import_state(&self) -> Option<ImportState> {
+        get_instance_storage(self).get(&IMPORT_STATE_KEY)
+    }
+    fn set_import_state(&self, state: &ImportState) {
+        get_instance_storage(self).set(&IMPORT_STATE_KEY, state);
+    }
+    fn remove_import_state(&self) {
+        get_instance_storage(self).remove(&IMPORT_STATE_KEY);
+    }
+    fn panic_if_importing(&self) {
+        if get_instance_storage(self).has(&IMPORT_STATE_KEY) {
+            panic_with_error!(self, ExtendedError::ImportInProgress);
+        }
+    }
+
+    // aliases
+    fn get_repo_alias(&self, repo: &String) -> Option<String> {
+        get_instance_storage(self).get(&(REPO_ALIAS, repo.clone()))
+    }
+    fn set_repo_alias(&self, repo: &String, target: &String) {
+        get_instance_storage(self).set(&(REPO_ALIAS, repo.clone()), target);
+    }
+    fn remove_repo_alias(&self, repo: &String) {
+        get_instance_storage(self).remove(&(REPO_ALIAS, repo.clone()));
+    }
+
+    // limits
+    fn get_limits(&self) -> Option<Limits> {
+        get_instance_storage(self).get(&LIMITS_KEY)
+    }
+    fn set_limits(&self, limits: &Limits) {
+        get_instance_storage(self).set(&LIMITS_KEY, limits);
+    }
+    fn get_repo_issue_limit(&self, repo: &String) -> Option<u32> {
+        get_instance_storage(self).get(&(REPO_ISSUE_LIMIT, repo.clone()))
+    }
+    fn set_repo_issue_limit(&self, repo: &String, limit: u32) {
+        get_instance_storage(self).set(&(REPO_ISSUE_LIMIT, repo.clone()), &limit);
+    }
+    fn remove_repo_issue_limit(&self, repo: &String) {
+        get_instance_storage(self).remove(&(REPO_ISSUE_LIMIT, repo.clone()));
+    }
+
+    // repo governance
+    fn get_repo_governance(&self) -> RepoGovernance {
+        get_instance_storage(self)
+            .get(&REPO_GOVERNANCE_KEY)
+            .unwrap_or(RepoGovernance::Admin)
+    }
+    fn set_repo_governance(&self, governance: &RepoGovernance) {
+        get_instance_storage(self).set(&REPO_GOVERNANCE_KEY, governance);
+    }
+    fn get_voters(&self) -> Vec<Address> {
+        get_instance_storage(self)
+            .get(&VOTERS_KEY)
+            .unwrap_or_else(|| Vec::new(self))
+    }
+    fn set_voters(&self, voters: &Vec<Address>) {
+        get_instance_storage(self).set(&VOTERS_KEY, voters);
+    }
+    fn get_repo_proposal(&self, proposal_id: u32) -> Option<RepoProposal> {
+        get_persistent_storage(self).get(&(REPO_PROPOSAL, proposal_id))
+    }
+    fn set_repo_proposal(&self, proposal: &RepoProposal) {
+        set_persistent(self, &(REPO_PROPOSAL, proposal.id), proposal);
+    }
+    fn remove_repo_proposal(&self, proposal_id: u32) {
+        get_persistent_storage(self).remove(&(REPO_PROPOSAL, proposal_id));
+    }
+    fn get_repo_proposal_count(&self) -> u32 {
+        get_instance_storage(self)
+            .get(&REPO_PROPOSAL_COUNT_KEY)
+            .unwrap_or(0)
+    }
+    fn set_repo_proposal_count(&self, count: u32) {
+        get_instance_storage(self).set(&REPO_PROPOSAL_COUNT_KEY, &count);
+    }
+
+    // escrow
+    fn get_escrow(&self, repo: &String, issue: &String) -> Option<Escrow> {
+        get_persistent_storage(self).get(&(ESCROW, repo.clone(), issue.clone()))
+    }
+    fn set_escrow(&self, repo: &String, issue: &String, escrow: &Escrow) {
+        set_persistent(self, &(ESCROW, repo.clone(), issue.clone()), escrow);
+    }
+    fn remove_escrow(&self, repo: &String, issue: &String) {
+        get_persistent_storage(self).remove(&(ESCROW, repo.clone(), issue.clone()));
+    }
+
+    // payouts
+    fn get_dust_recipient(&self) -> Option<Address> {
+        get_instance_storage(self).get(&DUST_RECIPIENT_KEY)
+    }
+    fn set_dust_recipient(&self, recipient: &Address) {
+        get_instance_storage(self).set(&DUST_RECIPIENT_KEY, recipient);
+    }
+
+    // grants
+    fn get_grant(&self, grant_id: u32) -> Option<Grant> {
+        get_persistent_storage(self).get(&(GRANT, grant_id))
+    }
+    fn set_grant(&self, grant_id: u32, grant: &Grant) {
+        set_persistent(self, &(GRANT, grant_id), grant);
+    }
+    fn get_grant_count(&self) -> u32 {
+        get_instance_storage(self)
+            .get(&GRANT_COUNT_KEY)
+            .unwrap_or(0)
+    }
+    fn set_grant_count(&self, count: u32) {
+        get_instance_storage(self).set(&GRANT_COUNT_KEY, &count);
+    }
+    fn get_issue_grants(&self, repo: &String, issue: &String) -> Vec<u32> {
+        get_persistent_storage(self)
+            .get(&(ISSUE_GRANTS, repo.clone(), issue.clone()))
+            .unwrap_or_else(|| Vec::new(self))
+    }
+    fn set_issue_grants(&self, repo: &String, issue: &String, grant_ids: &Vec<u32>) {
+        set_persistent(
+            self,
+            &(ISSUE_GRANTS, repo.clone(), issue.clone()),
+            grant_ids,
+        );
+    }
+    fn remove_issue_grants(&self, repo: &String, issue: &String) {
+        get_persistent_storage(self).remove(&(ISSUE_GRANTS, repo.clone(), issue.clone()));
+    }
+
+    // labels
+    fn get_issue_labels(&self, repo: &String, issue: &String) -> Vec<String> {
+        get_persistent_storage(self)
+            .get(&(ISSUE_LABELS, repo.clone(), issue.clone()))
+            .unwrap_or_else(|| Vec::new(self))
+    }
+    fn set_issue_labels(&self, repo: &String, issue: &String, labels: &Vec<String>) {
+        if labels.is_empty() {
+            self.remove_issue_labels(repo, issue);
+        } else {
+            set_persistent(self, &(ISSUE_LABELS, repo.clone(), issue.clone()), labels);
+        }
+    }
+    fn remove_issue_labels(&self, repo: &String, issue: &String) {
+        get_persistent_storage(self).remove(&(ISSUE_LABELS, repo.clone(), issue.clone()));
+    }
+    fn get_label_index(&self, label: &String) -> Vec<IssueRef> {
+        get_persistent_storage(self)
+            .get(&(LABEL_INDEX, label.clone()))
+            .unwrap_or_else(|| Vec::new(self))
+    }
+    fn set_label_index(&self, label: &String, issue_refs: &Vec<IssueRef>) {
+        if issue_refs.is_empty() {
+            get_persistent_storage(self).remove(&(LABEL_INDEX, label.clone()));
+        } else {
+            set_persistent(self, &(LABEL_INDEX, label.clone()), issue_refs);
+        }
+    }
+
+    // dependencies
+    fn get_issue_blockers(&self, issue_ref: &IssueRef) -> Vec<IssueRef> {
+        get_persistent_storage(self)
+            .get(&(ISSUE_BLOCKERS, issue_ref.clone()))
+            .unwrap_or_else(|| Vec::new(self))
+    }
+    fn set_issue_blockers(&self, issue_ref: &IssueRef, blockers: &Vec<IssueRef>) {
+        if blockers.is_empty() {
+            get_persistent_storage(self).remove(&(ISSUE_BLOCKERS, issue_ref.clone()));
+        } else {
+            set_persistent(self, &(ISSUE_BLOCKERS, issue_ref.clone()), blockers);
+        }
+    }
+    fn get_issue_dependents(&self, issue_ref: &IssueRef) -> Vec<IssueRef> {
+        get_persistent_storage(self)
+            .get(&(ISSUE_DEPENDENTS, issue_ref.clone()))
+            .unwrap_or_else(|| Vec::new(self))
+    }
+    fn set_issue_dependents(&self, issue_ref: &IssueRef, dependents: &Vec<IssueRef>) {
+        if dependents.is_empty() {
+            get_persistent_storage(self).remove(&(ISSUE_DEPENDENTS, issue_ref.clone()));
+        } else {
+            set_persistent(self, &(ISSUE_DEPENDENTS, issue_ref.clone()), dependents);
+        }
+    }
+
+    // stats
+    fn get_repo_stats(&self, repo: &String) -> Stats {
+        get_instance_storage(self)
+            .get(&(REPO_STATS, repo.clone()))
+            .unwrap_or_else(|| Stats::new(self))
+    }
+    fn set_repo_stats(&self, repo: &String, stats: &Stats) {
+        get_instance_storage(self).set(&(REPO_STATS, repo.clone()), stats);
+    }
+    fn remove_repo_stats(&self, repo: &String) {
+        get_instance_storage(self).remove(&(REPO_STATS, repo.clone()));
+    }
+    fn get_global_stats(&self) -> Stats {
+        get_instance_storage(self)
+            .get(&GLOBAL_STATS_KEY)
+            .unwrap_or_else(|| Stats::new(self))
+    }
+    fn set_global_stats(&self, stats: &Stats) {
+        get_instance_storage(self).set(&GLOBAL_STATS_KEY, stats);
+    }
+    fn get_repo_claims(&self, repo: &String) -> Map<Address, u32> {
+        get_persistent_storage(self)
+            .get(&(REPO_CLAIMS, repo.clone()))
+            .unwrap_or_else(|| Map::new(self))
+    }
+    fn set_repo_claims(&self, repo: &String, claims: &Map<Address, u32>) {
+        set_persistent(self, &(REPO_CLAIMS, repo.clone()), claims);
+    }
+    fn remove_repo_claims(&self, repo: &String) {
+        get_persistent_storage(self).remove(&(REPO_CLAIMS, repo.clone()));
+    }
+    fn get_claims(&self, claimant: &Address) -> u32 {
+        get_persistent_storage(self)
+            .get(&(CLAIMS, claimant.clone()))
+            .unwrap_or(0)
+    }
+    fn set_claims(&self, claimant: &Address, claims: u32) {
+        let key = (CLAIMS, claimant.clone());
+        if claims == 0 {
+            get_persistent_storage(self).remove(&key);
+        } else {
+            set_persistent(self, &key, &claims);
+        }
+    }
+
+    // vesting
+    fn get_vesting_schedule(&self, repo: &String, issue: &String) -> Option<VestingSchedule> {
+        get_persistent_storage(self).get(&(VESTING_SCHEDULE, repo.clone(), issue.clone()))
+    }
+    fn set_vesting_schedule(&self, repo: &String, issue: &String, schedule: &VestingSchedule) {
+        set_persistent(
+            self,
+            &(VESTING_SCHEDULE, repo.clone(), issue.clone()),
+            schedule,
+        );
+    }
+    fn remove_vesting_schedule(&self, repo: &String, issue: &String) {
+        get_persistent_storage(self).remove(&(VESTING_SCHEDULE, repo.clone(), issue.clone()));
+    }
+    fn get_vesting(&self, repo: &String, issue: &String) -> Option<Vesting> {
+        get_persistent_storage(self).get(&(VESTING, repo.clone(), issue.clone()))
+    }
+    fn set_vesting(&self, repo: &String, issue: &String, vesting: &Vesting) {
+        set_persistent(self, &(VESTING, repo.clone(), issue.clone()), vesting);
+    }
+    fn remove_vesting(&self, repo: &String, issue: &String) {
+        get_persistent_storage(self).remove(&(VESTING, repo.clone(), issue.clone()));
+    }
+
+    // issue proposals
+    fn get_proposal_deposit(&self) -> Option<ProposalDepositConfig> {
+        get_instance_storage(self).get(&PROPOSAL_DEPOSIT_KEY)
+    }
+    fn set_proposal_deposit(&self, config: &ProposalDepositConfig) {
+        get_instance_storage(self).set(&PROPOSAL_DEPOSIT_KEY, config);
+    }
+    fn get_issue_proposal(&self, proposal_id: u32) -> Option<IssueProposal> {
+        get_persistent_storage(self).get(&(ISSUE_PROPOSAL, proposal_id))
+    }
+    fn set_issue_proposal(&self, proposal: &IssueProposal) {
+        set_persistent(self, &(ISSUE_PROPOSAL, proposal.id), proposal);
+    }
+    fn remove_issue_proposal(&self, proposal_id: u32) {
+        get_persistent_storage(self).remove(&(ISSUE_PROPOSAL, proposal_id));
+    }
+    fn get_issue_proposal_count(&self) -> u32 {
+        get_instance_storage(self)
+            .get(&ISSUE_PROPOSAL_COUNT_KEY)
+            .unwrap_or(0)
+    }
+    fn set_issue_proposal_count(&self, count: u32) {
+        get_instance_storage(self).set(&ISSUE_PROPOSAL_COUNT_KEY, &count);
+    }
+    fn get_pending_issue_proposals(&self) -> Vec<u32> {
+        get_persistent_storage(self)
+            .get(&PENDING_ISSUE_PROPOSALS_KEY)
+            .unwrap_or_else(|| Vec::new(self))
+    }
+    fn set_pending_issue_proposals(&self, proposal_ids: &Vec<u32>) {
+        set_persistent(self, &PENDING_ISSUE_PROPOSALS_KEY, proposal_ids);
+    }
+
+    // subscriptions
+    fn get_subscription_config(&self) -> Option<SubscriptionConfig> {
+        get_instance_storage(self).get(&SUBSCRIPTION_CONFIG_KEY)
+    }
+    fn set_subscription_config(&self, config: &SubscriptionConfig) {
+        get_instance_storage(self).set(&SUBSCRIPTION_CONFIG_KEY, config);
+    }
+    fn get_subscription(&self, subscription_id: u32) -> Option<Subscription> {
+        get_persistent_storage(self).get(&(SUBSCRIPTION, subscription_id))
+    }
+    fn set_subscription(&self, subscription: &Subscription) {
+        set_persistent(self, &(SUBSCRIPTION, subscription.id), subscription);
+    }
+    fn remove_subscription(&self, subscription_id: u32) {
+        get_persistent_storage(self).remove(&(SUBSCRIPTION, subscription_id));
+    }
+    fn get_subscription_count(&self) -> u32 {
+        get_instance_storage(self)
+            .get(&SUBSCRIPTION_COUNT_KEY)
+            .unwrap_or(0)
+    }
+    fn set_subscription_count(&self, count: u32) {
+        get_instance_storage(self).set(&SUBSCRIPTION_COUNT_KEY, &count);
+    }
+    fn get_repo_subscriptions(&self, repo: &String) -> Vec<u32> {
+        get_persistent_storage(self)
+            .get(&(REPO_SUBSCRIPTIONS, repo.clone()))
+            .unwrap_or_else(|| Vec::new(self))
+    }
+    fn set_repo_subscriptions(&self, repo: &String, subscription_ids: &Vec<u32>) {
+        if subscription_ids.is_empty() {
+            get_persistent_storage(self).remove(&(REPO_SUBSCRIPTIONS, repo.clone()));
+        } else {
+            set_persistent(self, &(REPO_SUBSCRIPTIONS, repo.clone()), subscription_ids);
+        }
+    }
+    fn get_notification(&self, notification_id: u32) -> Option<Notification> {
+        get_persistent_storage(self).get(&(NOTIFICATION, notification_id))
+    }
+    fn set_notification(&self, notification: &Notification) {
+        set_persistent(self, &(NOTIFICATION, notification.id), notification);
+    }
+    fn remove_notification(&self, notification_id: u32) {
+        get_persistent_storage(self).remove(&(NOTIFICATION, notification_id));
+    }
+    fn get_notification_count(&self) -> u32 {
+        get_instance_storage(self)
+            .get(&NOTIFICATION_COUNT_KEY)
+            .unwrap_or(0)
+    }
+    fn set_notification_count(&self, count: u32) {
+        get_instance_storage(self).set(&NOTIFICATION_COUNT_KEY, &count);
+    }
+
+    // paid reads
+    fn get_read_pricing(&self) -> Option<ReadPricing> {
+        get_instance_storage(self).get(&READ_PRICING_KEY)
+    }
+    fn set_read_pricing(&self, pricing: &Option<ReadPricing>) {
+        match pricing {
+            Some(pricing) => get_instance_storage(self).set(&READ_PRICING_KEY, pricing),
+            None => get_instance_storage(self).remove(&READ_PRICING_KEY),
+        }
+    }
+    fn get_read_usage(&self, consumer: &Address, period_start_ledger: u32) -> Option<ReadUsage> {
+        self.storage()
+            .temporary()
+            .get(&(READ_USAGE, consumer.clone(), period_start_ledger))
+    }
+    //usage is only worth keeping until its period ends
+    fn set_read_usage(&self, consumer: &Address, usage: &ReadUsage, period_end_ledger: u32) {
+        let key = (READ_USAGE, consumer.clone(), usage.period_start_ledger);
+        let ttl = period_end_ledger - self.ledger().sequence();
+        self.storage().temporary().set(&key, usage);
+        self.storage().temporary().extend_ttl(&key, ttl, ttl);
+    }
+    fn get_read_credit(&self, consumer: &Address) -> Map<Address, i128> {
+        get_persistent_storage(self)
+            .get(&(READ_CREDIT, consumer.clone()))
+            .unwrap_or_else(|| Map::new(self))
+    }
+    fn set_read_credit(&self, consumer: &Address, credit: &Map<Address, i128>) {
+        let key = (READ_CREDIT, consumer.clone());
+        if credit.is_empty() {
+            get_persistent_storage(self).remove(&key);
+        } else {
+            set_persistent(self, &key, credit);
+        }
+    }
+    fn get_read_revenue(&self) -> Map<Address, i128> {
+        get_instance_storage(self)
+            .get(&READ_REVENUE_KEY)
+            .unwrap_or_else(|| Map::new(self))
+    }
+    fn set_read_revenue(&self, revenue: &Map<Address, i128>) {
+        get_instance_storage(self).set(&READ_REVENUE_KEY, revenue);
+    }
 }
 
 fn get_instance_storage(e: &Env) -> Instance {
     e.storage().instance()
 }
+
+// archives can grow as large as the whole registry, and per-address, per-issue and
+// per-id entries grow with use, so they are kept out of instance storage
+fn get_persistent_storage(e: &Env) -> Persistent {
+    e.storage().persistent()
+}
+
+// keeps a persistent entry alive for as long as it is still being written to
+fn set_persistent<K, V>(e: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    let storage = get_persistent_storage(e);
+    let max_ttl = e.storage().max_ttl();
+    storage.set(key, value);
+    storage.extend_ttl(key, max_ttl / 2, max_ttl);
+}
@@ -1,19 +1,82 @@
 #![no_std]
 use crate::extensions::env_extensions::EnvExtensions;
+use soroban_sdk::xdr::ToXdr;
 use soroban_sdk::{
-    contract, contractimpl, log, panic_with_error, Address, BytesN, Env, Map, String, Vec,
+    contract, log, panic_with_error, symbol_short, token, vec, Address, BytesN, Env, IntoVal, Map,
+    String, Symbol, Vec,
 };
+use types::admin_action::{AdminAction, AdminProposal};
 use types::config_data::ConfigData;
-use types::error::Error;
+use types::error::{Error, ExtendedError};
+use types::escrow::Escrow;
+use types::forge::Forge;
+use types::grant::{Grant, GrantData, GrantProgress, Milestone};
+use types::issue_proposal::{IssueProposal, ProposalDepositConfig};
+use types::issue_ref::IssueRef;
+use types::limits::Limits;
+use types::merkle::{
+    empty_root, fold_path, hash_leaf, merkle_path, merkle_root, repo_leaf, repo_step, IssueLeaf,
+    MerkleStep,
+};
+use types::org::Org;
+use types::read_pricing::{ReadPricing, ReadUsage};
+use types::record::{chunk_checksum, ExportChunk, ImportState, Record};
+use types::registry_archive::{ClearRequest, RegistryArchive};
+use types::repo_challenge::RepoChallenge;
+use types::repo_governance::{RepoChange, RepoGovernance, RepoProposal, VotingConfig};
+use types::repo_state::RepoState;
+use types::signer_set::SignerSet;
+use types::stats::Stats;
+use types::subscription::{Notification, Subscription, SubscriptionConfig};
+use types::vesting::{VestedShare, Vesting, VestingSchedule};
 
 mod extensions;
 mod test;
-mod types;
+pub mod types;
+
+// issue statuses
+const UNCLAIMED: &str = "unclaimed";
+const COMPLETED: &str = "completed";
+
+// limits used when none have been stored yet
+const DEFAULT_MAX_REPOS: u32 = 256;
+const DEFAULT_MAX_ISSUES_PER_REPO: u32 = 512;
+
+// roughly one day of ledgers between requesting and confirming a clear
+const CLEAR_DELAY_LEDGERS: u32 = 17_280;
+
+// how long a repo ownership challenge stays open for the attestor
+const CHALLENGE_EXPIRY_LEDGERS: u32 = 17_280;
+
+// longest repo key, including the forge prefix
+const MAX_REPO_KEY_LEN: usize = 256;
+
+// most labels a single issue can carry
+const MAX_LABELS_PER_ISSUE: u32 = 16;
+
+// payout shares are expressed in basis points of the escrowed amount
+const TOTAL_BASIS_POINTS: u32 = 10_000;
+
+// a completion queues a notification per subscriber, so the number of
+// subscribers per repo bounds the entries a completion writes
+const MAX_SUBSCRIPTIONS_PER_REPO: u32 = 8;
+
+// consecutive failed callbacks after which a subscription is dropped
+const MAX_CALLBACK_FAILURES: u32 = 3;
 
 #[contract]
 pub struct GithubOracleContract;
 
-#[contractimpl]
+// `library` keeps only the client, so crates linking the oracle do not export its
+// functions. Test builds still register them, which needs the full contractimpl.
+#[cfg_attr(
+    any(not(feature = "library"), feature = "testutils"),
+    soroban_sdk::contractimpl
+)]
+#[cfg_attr(
+    all(feature = "library", not(feature = "testutils")),
+    soroban_sdk::contractclient(name = "GithubOracleContractClient", impl_only = true)
+)]
 impl GithubOracleContract {
     pub fn config(e: Env, config: ConfigData) {
         log!(&e, "config: {:?}", config);
@@ -21,9 +84,19 @@ impl GithubOracleContract {
         if e.is_initialized() {
             e.panic_with_error(Error::AlreadyInitialized);
         }
+        e.panic_if_importing();
         e.set_admin(&config.admin);
+        Self::__set_limits(
+            &e,
+            Limits {
+                max_repos: config.max_repos,
+                max_issues_per_repo: config.max_issues_per_repo,
+            },
+        );
+        Self::__set_repo_governance(&e, config.repo_governance);
 
-        Self::__add_repos(&e, config.repos);
+        let repo_keys = Self::__checked_repo_keys(&e, &config.repos);
+        Self::__add_repos(&e, repo_keys);
     }
 
     pub fn version(_e: Env) -> u32 {
@@ -41,38 +114,458 @@ impl GithubOracleContract {
 
     pub fn add_repos(e: Env, repos: Vec<String>) {
         e.panic_if_not_admin();
-        Self::__add_repos(&e, repos);
+        Self::__panic_if_repo_voting(&e);
+        //bare names are GitHub repos, other forges go through `add_forge_repos`
+        Self::__add_repos(&e, Self::__repo_keys(&e, Forge::GitHub, &repos));
+    }
+
+    pub fn add_forge_repos(e: Env, forge: Forge, repos: Vec<String>) {
+        e.panic_if_not_admin();
+        Self::__panic_if_repo_voting(&e);
+
+        Self::__add_repos(&e, Self::__repo_keys(&e, forge, &repos));
     }
 
     pub fn add_issues(e: Env, repo_name: String, issues: Vec<String>) {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
         e.panic_if_not_admin();
         Self::__add_issues(&e, repo_name, issues);
     }
 
-    pub fn clear_repos(e: Env) {
+    pub fn get_state_hash(e: Env) -> BytesN<32> {
+        Self::__state_hash(&e, &e.get_repos())
+    }
+
+    pub fn state_root(e: Env) -> BytesN<32> {
+        e.get_state_root().unwrap_or_else(|| empty_root(&e))
+    }
+
+    //once reads are priced, proofs are only handed out through `get_issue_proof_paid`
+    pub fn get_issue_proof(e: Env, repo_name: String, issue: String) -> Vec<MerkleStep> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        if e.get_read_pricing().is_some() {
+            panic_with_error!(&e, ExtendedError::PaidReadRequired);
+        }
+        Self::__issue_proof(&e, &repo_name, &issue)
+    }
+
+    //the premium read, charged to the consumer once reads are priced
+    pub fn get_issue_proof_paid(
+        e: Env,
+        consumer: Address,
+        repo_name: String,
+        issue: String,
+    ) -> Vec<MerkleStep> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        Self::__charge_read(&e, &consumer);
+        Self::__issue_proof(&e, &repo_name, &issue)
+    }
+
+    //folding a proof only needs the public state root, so verification stays free
+    pub fn verify_issue_proof(e: Env, leaf: IssueLeaf, proof: Vec<MerkleStep>) -> bool {
+        fold_path(&e, hash_leaf(&e, &leaf), &proof) == Self::state_root(e)
+    }
+
+    //commits repos tracked before the state root existed
+    pub fn rebuild_state_root(e: Env) {
+        e.panic_if_not_admin();
+        Self::__commit_repos(&e, &e.get_repos().keys());
+    }
+
+    pub fn request_clear(e: Env, state_hash: BytesN<32>) {
+        e.panic_if_not_single_admin();
+        Self::__request_clear(&e, state_hash);
+    }
+
+    pub fn cancel_clear(e: Env) {
         e.panic_if_not_admin();
-        e.set_repos(Map::new(&e));
+        e.remove_clear_request();
+    }
+
+    pub fn confirm_clear(e: Env, state_hash: BytesN<32>) -> u32 {
+        e.panic_if_not_single_admin();
+        Self::__confirm_clear(&e, state_hash)
+    }
+
+    pub fn get_clear_request(e: Env) -> Option<ClearRequest> {
+        e.get_clear_request()
+    }
+
+    pub fn get_archive(e: Env, archive_id: u32) -> Option<RegistryArchive> {
+        e.get_archive(archive_id)
+    }
+
+    pub fn get_archive_count(e: Env) -> u32 {
+        e.get_archive_count()
+    }
+
+    //open to anyone, so the registry can still be recovered after the admin key is lost.
+    //pending issue proposals, repo aliases, archives, read credit and queued notifications are
+    //not exported: settle the proposals first, aliases only matter to the old address, archives
+    //stay readable there, consumers withdraw their credit from it and notifications are
+    //dispatched from it
+    pub fn export_chunk(e: Env, cursor: u32, checksum: BytesN<32>) -> ExportChunk {
+        let (records, next_cursor) = Self::__export_chunk(&e, cursor);
+        ExportChunk {
+            cursor,
+            checksum: chunk_checksum(&e, &checksum, &records),
+            state_hash: Self::__state_hash(&e, &e.get_repos()),
+            records,
+            next_cursor,
+        }
+    }
+
+    pub fn begin_import(e: Env, importer: Address) {
+        importer.require_auth();
+        if e.is_initialized() {
+            panic_with_error!(&e, Error::AlreadyInitialized);
+        }
+        e.panic_if_importing();
+        e.set_import_state(&ImportState {
+            importer,
+            checksum: BytesN::from_array(&e, &[0; 32]),
+            chunks: 0,
+            deferred_roles: Vec::new(&e),
+            state_hash: BytesN::from_array(&e, &[0; 32]),
+            complete: false,
+        });
+    }
+
+    pub fn import_chunk(e: Env, chunk: ExportChunk) {
+        let state = Self::__get_import_state(&e);
+        state.importer.require_auth();
+        Self::__import_chunk(&e, state, chunk);
+    }
+
+    pub fn finish_import(e: Env) {
+        let state = Self::__get_import_state(&e);
+        state.importer.require_auth();
+        Self::__finish_import(&e, state);
+    }
+
+    pub fn get_import_state(e: Env) -> Option<ImportState> {
+        e.get_import_state()
     }
 
     pub fn remove_repos(e: Env, repos: Vec<String>) {
         e.panic_if_not_admin();
+        Self::__panic_if_repo_voting(&e);
         Self::__remove_repos(&e, repos);
     }
 
+    //`old_name` is the registry key, `new_name` the bare name on the repo's forge
+    pub fn rename_repo(e: Env, old_name: String, new_name: String) {
+        e.panic_if_not_admin();
+        Self::__rename_repo(&e, old_name, new_name);
+    }
+
     pub fn remove_issues(e: Env, repo_name: String, issues: Vec<String>) {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
         e.panic_if_not_admin();
         Self::__remove_issues(&e, repo_name, issues);
     }
 
+    pub fn set_limits(e: Env, limits: Limits) {
+        e.panic_if_not_single_admin();
+        Self::__set_limits(&e, limits);
+    }
+
+    pub fn get_limits(e: Env) -> Limits {
+        Self::__get_limits(&e)
+    }
+
+    pub fn set_repo_issue_limit(e: Env, repo_name: String, limit: Option<u32>) {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.panic_if_not_single_admin();
+        Self::__set_repo_issue_limit(&e, repo_name, limit);
+    }
+
+    pub fn get_repo_issue_limit(e: Env, repo_name: String) -> u32 {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        Self::__get_repo_issue_limit(&e, &repo_name)
+    }
+
+    pub fn set_attestor(e: Env, attestor: Address) {
+        e.panic_if_not_single_admin();
+        e.set_attestor(&attestor);
+    }
+
+    pub fn attestor(e: Env) -> Option<Address> {
+        e.get_attestor()
+    }
+
+    //returns the nonce to commit, the challenge is then kept under the repo's registry key
+    pub fn request_repo_verification(
+        e: Env,
+        requester: Address,
+        forge: Forge,
+        repo_name: String,
+    ) -> u64 {
+        requester.require_auth();
+        Self::__request_repo_verification(&e, requester, forge, repo_name)
+    }
+
+    pub fn confirm_repo_verification(e: Env, repo_name: String, requester: Address, nonce: u64) {
+        let attestor = e.get_attestor();
+        if attestor.is_none() {
+            panic_with_error!(&e, Error::AttestorMissing);
+        }
+        attestor.unwrap().require_auth();
+        Self::__confirm_repo_verification(&e, repo_name, requester, nonce);
+    }
+
+    pub fn get_repo_challenge(
+        e: Env,
+        repo_name: String,
+        requester: Address,
+    ) -> Option<RepoChallenge> {
+        e.get_repo_challenge(&repo_name, &requester)
+    }
+
+    pub fn get_repo_maintainer(e: Env, repo_name: String) -> Option<Address> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.get_repo_maintainer(&repo_name)
+    }
+
+    pub fn create_org(e: Env, org_name: String, maintainers: Vec<Address>) {
+        e.panic_if_not_admin();
+        if e.get_org(&org_name).is_some() {
+            panic_with_error!(&e, ExtendedError::OrgAlreadyExists);
+        }
+
+        e.set_org(&Org {
+            name: org_name.clone(),
+            maintainers,
+            repos: Vec::new(&e),
+            budget_token: None,
+            budget: 0,
+        });
+        let mut orgs = e.get_orgs();
+        orgs.push_back(org_name);
+        e.set_orgs(&orgs);
+    }
+
+    pub fn set_org_maintainers(e: Env, org_name: String, maintainers: Vec<Address>) {
+        e.panic_if_not_single_admin();
+        Self::__set_org_maintainers(&e, org_name, maintainers);
+    }
+
+    pub fn add_org_repos(e: Env, org_name: String, repos: Vec<String>) {
+        e.panic_if_not_admin();
+        Self::__add_org_repos(&e, org_name, repos);
+    }
+
+    pub fn remove_org_repos(e: Env, org_name: String, repos: Vec<String>) {
+        e.panic_if_not_admin();
+        let org = Self::__get_org(&e, &org_name);
+        for repo in repos.iter() {
+            let repo = Self::__resolve_repo(&e, repo);
+            if !org.repos.contains(repo.clone()) {
+                panic_with_error!(&e, Error::RepoMissing);
+            }
+            Self::__remove_repo_from_org(&e, &repo);
+        }
+    }
+
+    pub fn get_org(e: Env, org_name: String) -> Org {
+        Self::__get_org(&e, &org_name)
+    }
+
+    pub fn get_orgs(e: Env) -> Vec<String> {
+        e.get_orgs()
+    }
+
+    pub fn get_org_repos(e: Env, org_name: String) -> Vec<String> {
+        Self::__get_org(&e, &org_name).repos
+    }
+
+    pub fn get_repo_org(e: Env, repo_name: String) -> Option<String> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.get_repo_org(&repo_name)
+    }
+
+    pub fn get_org_issue_counts(e: Env, org_name: String) -> Map<String, u32> {
+        let org = Self::__get_org(&e, &org_name);
+        let repos = e.get_repos();
+
+        let mut counts = Map::new(&e);
+        for repo in org.repos.iter() {
+            let issues = Self::__get_repo_issues(&e, &repos, &repo);
+            counts.set(repo, issues.len());
+        }
+
+        counts
+    }
+
+    pub fn set_repo_state(e: Env, repo_name: String, state: RepoState) {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.panic_if_not_admin();
+        Self::__set_repo_state(&e, &repo_name, state);
+    }
+
+    pub fn get_repo_state(e: Env, repo_name: String) -> RepoState {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.get_repo_state(&repo_name)
+    }
+
+    pub fn set_org_repos_state(e: Env, caller: Address, org_name: String, state: RepoState) {
+        let org = Self::__get_org(&e, &org_name);
+        Self::__panic_if_not_org_manager(&e, &caller, &org);
+
+        for repo in org.repos.iter() {
+            Self::__set_repo_state(&e, &repo, state);
+        }
+    }
+
+    pub fn fund_org(e: Env, funder: Address, org_name: String, token: Address, amount: i128) {
+        funder.require_auth();
+        e.panic_if_importing();
+        if amount <= 0 {
+            panic_with_error!(&e, Error::InvalidAmount);
+        }
+
+        let mut org = Self::__get_org(&e, &org_name);
+        if org
+            .budget_token
+            .as_ref()
+            .is_some_and(|budget_token| *budget_token != token)
+        {
+            panic_with_error!(&e, Error::EscrowTokenMismatch);
+        }
+
+        token::Client::new(&e, &token).transfer(&funder, &e.current_contract_address(), &amount);
+
+        org.budget_token = Some(token);
+        org.budget += amount;
+        e.set_org(&org);
+    }
+
+    pub fn fund_issue_from_org(
+        e: Env,
+        caller: Address,
+        org_name: String,
+        repo_name: String,
+        issue: String,
+        amount: i128,
+    ) {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        let mut org = Self::__get_org(&e, &org_name);
+        Self::__panic_if_not_org_manager(&e, &caller, &org);
+        if !org.repos.contains(repo_name.clone()) {
+            panic_with_error!(&e, Error::RepoMissing);
+        }
+
+        if org.budget_token.is_none() || amount > org.budget {
+            panic_with_error!(&e, Error::InvalidAmount);
+        }
+
+        //the tokens already sit in the contract, so this only moves them between balances
+        Self::__credit_escrow(
+            &e,
+            &repo_name,
+            &issue,
+            &org.budget_token.clone().unwrap(),
+            amount,
+        );
+        org.budget -= amount;
+        e.set_org(&org);
+    }
+
+    pub fn withdraw_org_budget(
+        e: Env,
+        caller: Address,
+        org_name: String,
+        recipient: Address,
+        amount: i128,
+    ) {
+        let mut org = Self::__get_org(&e, &org_name);
+        Self::__panic_if_not_org_manager(&e, &caller, &org);
+        if amount <= 0 || org.budget_token.is_none() || amount > org.budget {
+            panic_with_error!(&e, Error::InvalidAmount);
+        }
+
+        let token = org.budget_token.clone().unwrap();
+        token::Client::new(&e, &token).transfer(&e.current_contract_address(), &recipient, &amount);
+
+        org.budget -= amount;
+        //an emptied budget can be refilled with a different token
+        if org.budget == 0 {
+            org.budget_token = None;
+        }
+        e.set_org(&org);
+    }
+
+    pub fn get_repo_governance(e: Env) -> RepoGovernance {
+        e.get_repo_governance()
+    }
+
+    pub fn register_voters(e: Env, voters: Vec<Address>) {
+        e.panic_if_not_single_admin();
+        Self::__register_voters(&e, voters);
+    }
+
+    pub fn remove_voters(e: Env, voters: Vec<Address>) {
+        e.panic_if_not_single_admin();
+        Self::__remove_voters(&e, voters);
+    }
+
+    pub fn get_voters(e: Env) -> Vec<Address> {
+        e.get_voters()
+    }
+
+    pub fn propose_repo_change(e: Env, proposer: Address, change: RepoChange) -> u32 {
+        proposer.require_auth();
+        Self::__propose_repo_change(&e, proposer, change)
+    }
+
+    pub fn vote_repo_change(e: Env, voter: Address, proposal_id: u32, support: bool) {
+        voter.require_auth();
+        Self::__vote_repo_change(&e, voter, proposal_id, support);
+    }
+
+    pub fn execute_repo_change(e: Env, proposal_id: u32) {
+        Self::__execute_repo_change(&e, proposal_id);
+    }
+
+    //open to anyone once the proposal can no longer pass, or once it passed but
+    //was left unexecuted for another voting period
+    pub fn expire_repo_change(e: Env, proposal_id: u32) {
+        Self::__expire_repo_change(&e, proposal_id);
+    }
+
+    pub fn get_repo_proposal(e: Env, proposal_id: u32) -> RepoProposal {
+        Self::__get_repo_proposal(&e, proposal_id)
+    }
+
     pub fn get_repos(e: Env) -> Vec<String> {
         e.get_repos().keys()
     }
 
+    pub fn get_repos_by_forge(e: Env, forge: Forge) -> Vec<String> {
+        let mut repos = Vec::new(&e);
+        for repo in e.get_repos().keys().iter() {
+            if Self::__repo_forge(&e, &repo) == forge {
+                repos.push_back(repo);
+            }
+        }
+
+        repos
+    }
+
+    pub fn repo_key(e: Env, forge: Forge, repo_name: String) -> String {
+        Self::__repo_key(&e, forge, &repo_name)
+    }
+
+    pub fn get_repo_forge(e: Env, repo_name: String) -> Forge {
+        Self::__repo_forge(&e, &repo_name)
+    }
+
     pub fn get_repos_and_issues(e: Env) -> Map<String, Map<String, String>> {
         e.get_repos()
     }
 
     pub fn get_issues_for_repo(e: Env, repo_name: String) -> Map<String, String> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
         let issues = e.get_repos().get(repo_name);
 
         if issues.is_none() {
@@ -82,76 +575,2843 @@ impl GithubOracleContract {
         issues.unwrap()
     }
 
-    pub fn update_contract(e: Env, wasm_hash: BytesN<32>) {
-        e.panic_if_not_admin();
-        e.deployer().update_current_contract_wasm(wasm_hash)
+    pub fn set_issue_labels(
+        e: Env,
+        caller: Address,
+        repo_name: String,
+        issue: String,
+        labels: Vec<String>,
+    ) {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        Self::__panic_if_not_repo_manager(&e, &caller, &repo_name);
+        Self::__set_issue_labels(&e, repo_name, issue, labels);
     }
 
-    fn __add_repos(e: &Env, repos: Vec<String>) {
-        let mut current_repos = e.get_repos();
-        for repo in repos.iter() {
-            //check if the asset has been already added
-            if current_repos.contains_key(repo.clone()) {
-                panic_with_error!(&e, Error::RepoAlreadyExists);
-            }
+    pub fn get_issue_labels(e: Env, repo_name: String, issue: String) -> Vec<String> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.get_issue_labels(&repo_name, &issue)
+    }
 
-            current_repos.set(repo.clone(), Map::new(e));
-        }
-        if current_repos.len() >= 256 {
-            panic_with_error!(&e, Error::RepoLimitExceeded);
+    //pages resume after the last issue returned, which stays valid while the index changes
+    pub fn issues_by_label(
+        e: Env,
+        label: String,
+        after: Option<IssueRef>,
+        limit: u32,
+    ) -> Vec<IssueRef> {
+        let index = e.get_label_index(&label);
+        let start = match after {
+            Some(after) => match index.binary_search(after) {
+                Ok(position) => position + 1,
+                Err(position) => position,
+            },
+            None => 0,
+        };
+
+        let repos = e.get_repos();
+        let unclaimed = String::from_str(&e, UNCLAIMED);
+        let mut page = Vec::new(&e);
+        for issue_ref in index.iter().skip(start as usize) {
+            if page.len() >= limit {
+                break;
+            }
+            let status = repos
+                .get(issue_ref.repo.clone())
+                .and_then(|issues| issues.get(issue_ref.issue.clone()));
+            if status.as_ref() == Some(&unclaimed) {
+                page.push_back(issue_ref);
+            }
         }
-        e.set_repos(current_repos);
+
+        page
     }
 
-    fn __add_issues(e: &Env, repo: String, issues: Vec<String>) {
-        let mut current_repos = e.get_repos();
-        if !current_repos.contains_key(repo.clone()) {
-            panic_with_error!(&e, Error::RepoMissing);
-        }
+    pub fn add_issue_dependency(e: Env, caller: Address, issue: IssueRef, blocker: IssueRef) {
+        let issue = Self::__resolve_issue_ref(&e, issue);
+        let blocker = Self::__resolve_issue_ref(&e, blocker);
+        Self::__panic_if_not_repo_manager(&e, &caller, &issue.repo);
+        Self::__add_issue_dependency(&e, issue, blocker);
+    }
 
-        let mut current_issues = current_repos.get(repo.clone()).unwrap();
-        for issue in issues.iter() {
-            //check if the issue has been already added
-            if current_issues.contains_key(issue.clone()) {
-                panic_with_error!(&e, Error::IssueAlreadyExists);
-            }
+    pub fn remove_issue_dependency(e: Env, caller: Address, issue: IssueRef, blocker: IssueRef) {
+        let issue = Self::__resolve_issue_ref(&e, issue);
+        let blocker = Self::__resolve_issue_ref(&e, blocker);
+        Self::__panic_if_not_repo_manager(&e, &caller, &issue.repo);
+        Self::__unlink_issues(&e, &issue, &blocker);
+    }
 
-            current_issues.set(issue.clone(), String::from_str(e, "unclaimed"));
+    pub fn get_issue_blockers(e: Env, issue: IssueRef) -> Vec<IssueRef> {
+        e.get_issue_blockers(&Self::__resolve_issue_ref(&e, issue))
+    }
+
+    pub fn get_issue_dependents(e: Env, issue: IssueRef) -> Vec<IssueRef> {
+        e.get_issue_dependents(&Self::__resolve_issue_ref(&e, issue))
+    }
+
+    pub fn repo_stats(e: Env, repo_name: String) -> Stats {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        Self::__get_repo_issues(&e, &e.get_repos(), &repo_name);
+        e.get_repo_stats(&repo_name)
+    }
+
+    pub fn global_stats(e: Env) -> Stats {
+        e.get_global_stats()
+    }
+
+    pub fn set_proposal_deposit(e: Env, config: ProposalDepositConfig) {
+        e.panic_if_not_admin();
+        //a free proposal would let anyone fill the pending list at no cost
+        if config.amount <= 0 || config.expiry_ledgers == 0 {
+            panic_with_error!(&e, Error::InvalidAmount);
         }
-        if current_issues.len() >= 512 {
-            panic_with_error!(&e, Error::IssueLimitExceeded);
+        e.set_proposal_deposit(&config);
+    }
+
+    pub fn get_proposal_deposit(e: Env) -> Option<ProposalDepositConfig> {
+        e.get_proposal_deposit()
+    }
+
+    pub fn propose_issue(e: Env, proposer: Address, repo_name: String, issue: String) -> u32 {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        proposer.require_auth();
+        Self::__propose_issue(&e, proposer, repo_name, issue)
+    }
+
+    pub fn approve_issue_proposal(e: Env, caller: Address, proposal_id: u32) {
+        let proposal = Self::__get_issue_proposal(&e, proposal_id);
+        Self::__panic_if_not_repo_manager(&e, &caller, &proposal.repo);
+        Self::__approve_issue_proposal(&e, proposal_id);
+    }
+
+    pub fn reject_issue_proposal(e: Env, caller: Address, proposal_id: u32, slash: bool) {
+        let proposal = Self::__get_issue_proposal(&e, proposal_id);
+        Self::__panic_if_not_repo_manager(&e, &caller, &proposal.repo);
+        Self::__reject_issue_proposal(&e, proposal_id, slash);
+    }
+
+    pub fn expire_issue_proposal(e: Env, proposal_id: u32) {
+        let proposal = Self::__get_issue_proposal(&e, proposal_id);
+        if e.ledger().sequence() <= proposal.expires_at_ledger {
+            panic_with_error!(&e, Error::ProposalNotExpired);
         }
-        current_repos.set(repo.clone(), current_issues);
-        e.set_repos(current_repos);
+
+        Self::__close_issue_proposal(&e, &proposal, &proposal.proposer);
     }
 
-    fn __remove_repos(e: &Env, repos: Vec<String>) {
-        let mut current_repos = e.get_repos();
-        for repo in repos.iter() {
-            if !current_repos.contains_key(repo.clone()) {
-                panic_with_error!(&e, Error::RepoMissing);
+    pub fn get_issue_proposal(e: Env, proposal_id: u32) -> IssueProposal {
+        Self::__get_issue_proposal(&e, proposal_id)
+    }
+
+    pub fn get_issue_proposals(e: Env, cursor: u32, limit: u32) -> Vec<IssueProposal> {
+        let mut proposals = Vec::new(&e);
+        for proposal_id in e.get_pending_issue_proposals().iter() {
+            if proposals.len() >= limit {
+                break;
+            }
+            if proposal_id >= cursor {
+                proposals.push_back(e.get_issue_proposal(proposal_id).unwrap());
             }
+        }
 
-            current_repos.remove(repo.clone());
+        proposals
+    }
+
+    pub fn set_subscription_config(e: Env, config: SubscriptionConfig) {
+        e.panic_if_not_admin();
+        //free subscriptions would let anyone take every slot of a repo
+        if config.fee <= 0 || config.duration_ledgers == 0 {
+            panic_with_error!(&e, Error::InvalidAmount);
         }
-        e.set_repos(current_repos);
+        e.set_subscription_config(&config);
     }
 
-    fn __remove_issues(e: &Env, repo: String, issues: Vec<String>) {
-        let mut current_repos = e.get_repos();
-        if !current_repos.contains_key(repo.clone()) {
-            panic_with_error!(&e, Error::RepoMissing);
+    pub fn get_subscription_config(e: Env) -> Option<SubscriptionConfig> {
+        e.get_subscription_config()
+    }
+
+    pub fn subscribe(e: Env, subscriber: Address, repo_name: String, issue: Option<String>) -> u32 {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        subscriber.require_auth();
+        Self::__subscribe(&e, subscriber, repo_name, issue)
+    }
+
+    pub fn unsubscribe(e: Env, subscription_id: u32) {
+        let subscription = Self::__get_subscription(&e, subscription_id);
+        subscription.subscriber.require_auth();
+        Self::__drop_subscription(&e, &subscription);
+    }
+
+    //lets the admin drop a subscriber whose callback keeps exhausting the budget,
+    //after which its queued notifications are discarded instead of dispatched
+    pub fn remove_subscription(e: Env, subscription_id: u32) {
+        e.panic_if_not_admin();
+        let subscription = Self::__get_subscription(&e, subscription_id);
+        Self::__drop_subscription(&e, &subscription);
+    }
+
+    pub fn get_subscription(e: Env, subscription_id: u32) -> Subscription {
+        Self::__get_subscription(&e, subscription_id)
+    }
+
+    //open to anyone: each notification is dispatched in its own call, so a callback
+    //that exhausts the budget only fails its own dispatch. Returns whether the
+    //subscriber took the update.
+    pub fn dispatch_notification(e: Env, notification_id: u32) -> bool {
+        Self::__dispatch_notification(&e, notification_id)
+    }
+
+    pub fn get_notification(e: Env, notification_id: u32) -> Notification {
+        Self::__get_notification(&e, notification_id)
+    }
+
+    pub fn get_subscriptions(e: Env, repo_name: String) -> Vec<Subscription> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        let mut subscriptions = Vec::new(&e);
+        for subscription_id in e.get_repo_subscriptions(&repo_name).iter() {
+            subscriptions.push_back(e.get_subscription(subscription_id).unwrap());
         }
+        subscriptions
+    }
 
-        let mut current_issues = current_repos.get(repo.clone()).unwrap();
-        for issue in issues.iter() {
-            if !current_issues.contains_key(issue.clone()) {
-                panic_with_error!(&e, Error::IssueMissing);
+    //without pricing every read is free
+    pub fn set_read_pricing(e: Env, pricing: Option<ReadPricing>) {
+        e.panic_if_not_admin();
+        if let Some(pricing) = &pricing {
+            if pricing.fee < 0 || pricing.period_ledgers == 0 {
+                panic_with_error!(&e, Error::InvalidAmount);
             }
-
-            current_issues.remove(issue.clone());
         }
-        current_repos.set(repo.clone(), current_issues);
-        e.set_repos(current_repos);
+        e.set_read_pricing(&pricing);
+    }
+
+    pub fn get_read_pricing(e: Env) -> Option<ReadPricing> {
+        e.get_read_pricing()
+    }
+
+    pub fn deposit_read_credit(e: Env, consumer: Address, amount: i128) {
+        consumer.require_auth();
+        Self::__deposit_read_credit(&e, consumer, amount);
+    }
+
+    //credit stays withdrawable in the token it was deposited in, even after a price change
+    pub fn withdraw_read_credit(e: Env, consumer: Address, token: Address, amount: i128) {
+        consumer.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&e, Error::InvalidAmount);
+        }
+        Self::__debit_read_credit(&e, &consumer, &token, amount);
+        token::Client::new(&e, &token).transfer(&e.current_contract_address(), &consumer, &amount);
+    }
+
+    pub fn get_read_credit(e: Env, consumer: Address) -> Map<Address, i128> {
+        e.get_read_credit(&consumer)
+    }
+
+    //usage in the current period; earlier periods expire with their free tier
+    pub fn get_read_usage(e: Env, consumer: Address) -> Option<ReadUsage> {
+        let pricing = e.get_read_pricing()?;
+        e.get_read_usage(&consumer, Self::__read_period_start(&e, &pricing))
+    }
+
+    pub fn get_read_revenue(e: Env) -> Map<Address, i128> {
+        e.get_read_revenue()
+    }
+
+    pub fn withdraw_read_revenue(e: Env, token: Address, to: Address) -> i128 {
+        e.panic_if_not_admin();
+        let mut revenue = e.get_read_revenue();
+        let amount = revenue.get(token.clone()).unwrap_or(0);
+        if amount == 0 {
+            panic_with_error!(&e, Error::NothingToWithdraw);
+        }
+
+        revenue.remove(token.clone());
+        e.set_read_revenue(&revenue);
+        token::Client::new(&e, &token).transfer(&e.current_contract_address(), &to, &amount);
+        e.events()
+            .publish((symbol_short!("revenue"), token), (to, amount));
+        amount
+    }
+
+    pub fn fund_issue(
+        e: Env,
+        funder: Address,
+        repo_name: String,
+        issue: String,
+        token: Address,
+        amount: i128,
+    ) {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        funder.require_auth();
+        Self::__fund_issue(&e, funder, repo_name, issue, token, amount);
+    }
+
+    pub fn get_escrow(e: Env, repo_name: String, issue: String) -> Option<Escrow> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.get_escrow(&repo_name, &issue)
+    }
+
+    pub fn set_dust_recipient(e: Env, recipient: Address) {
+        e.panic_if_not_single_admin();
+        e.set_dust_recipient(&recipient);
+    }
+
+    pub fn dust_recipient(e: Env) -> Option<Address> {
+        e.get_dust_recipient()
+    }
+
+    //a repo manager completes the issue, or the attestor, whose auth covers the shares it attests
+    pub fn complete_issue(
+        e: Env,
+        caller: Address,
+        repo_name: String,
+        issue: String,
+        shares: Vec<(Address, u32)>,
+    ) {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        if Some(caller.clone()) == e.get_attestor() {
+            e.panic_if_importing();
+            caller.require_auth();
+        } else {
+            Self::__panic_if_not_repo_manager(&e, &caller, &repo_name);
+        }
+        Self::__complete_issue(&e, repo_name, issue, shares);
+    }
+
+    pub fn set_vesting_schedule(
+        e: Env,
+        repo_name: String,
+        issue: String,
+        schedule: VestingSchedule,
+    ) {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.panic_if_not_admin();
+        if schedule.upfront_basis_points > TOTAL_BASIS_POINTS || schedule.duration_ledgers == 0 {
+            panic_with_error!(&e, Error::InvalidVestingSchedule);
+        }
+        if Self::__get_issue_status(&e, &repo_name, &issue) == String::from_str(&e, COMPLETED) {
+            panic_with_error!(&e, Error::IssueAlreadyCompleted);
+        }
+        e.set_vesting_schedule(&repo_name, &issue, &schedule);
+    }
+
+    pub fn get_vesting_schedule(
+        e: Env,
+        repo_name: String,
+        issue: String,
+    ) -> Option<VestingSchedule> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.get_vesting_schedule(&repo_name, &issue)
+    }
+
+    pub fn get_vesting(e: Env, repo_name: String, issue: String) -> Option<Vesting> {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.get_vesting(&repo_name, &issue)
+    }
+
+    pub fn withdraw_vested(e: Env, contributor: Address, repo_name: String, issue: String) -> i128 {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        contributor.require_auth();
+        Self::__withdraw_vested(&e, contributor, repo_name, issue)
+    }
+
+    pub fn clawback(e: Env, repo_name: String, issue: String, recipient: Address) -> i128 {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.panic_if_not_admin();
+        Self::__clawback(&e, repo_name, issue, recipient)
+    }
+
+    //returns an issue's escrow so the issue can be removed
+    pub fn refund_escrow(e: Env, repo_name: String, issue: String, recipient: Address) -> i128 {
+        let repo_name = Self::__resolve_repo(&e, repo_name);
+        e.panic_if_not_admin();
+        Self::__refund_escrow(&e, repo_name, issue, recipient)
+    }
+
+    pub fn create_grant(e: Env, grant: GrantData) -> u32 {
+        e.panic_if_not_admin();
+        grant.funder.require_auth();
+        Self::__create_grant(&e, grant)
+    }
+
+    pub fn cancel_grant(e: Env, grant_id: u32) {
+        e.panic_if_not_admin();
+        Self::__cancel_grant(&e, grant_id);
+    }
+
+    pub fn get_grant(e: Env, grant_id: u32) -> Grant {
+        Self::__get_grant(&e, grant_id)
+    }
+
+    pub fn get_grant_count(e: Env) -> u32 {
+        e.get_grant_count()
+    }
+
+    pub fn get_grant_progress(e: Env, grant_id: u32) -> GrantProgress {
+        let grant = Self::__get_grant(&e, grant_id);
+        let repos = e.get_repos();
+
+        let mut progress = GrantProgress {
+            released_milestones: grant.released_milestones,
+            total_milestones: grant.milestones.len(),
+            completed_issues: 0,
+            total_issues: 0,
+            released_amount: 0,
+            total_amount: 0,
+        };
+        for (index, milestone) in grant.milestones.iter().enumerate() {
+            for issue_ref in milestone.issues.iter() {
+                if Self::__is_issue_completed(&e, &repos, &issue_ref.repo, &issue_ref.issue) {
+                    progress.completed_issues += 1;
+                }
+                progress.total_issues += 1;
+            }
+            if (index as u32) < grant.released_milestones {
+                progress.released_amount += milestone.amount;
+            }
+            progress.total_amount += milestone.amount;
+        }
+
+        progress
+    }
+
+    pub fn set_signers(e: Env, signer_set: SignerSet) {
+        e.panic_if_not_single_admin();
+        Self::__set_signers(&e, signer_set);
+    }
+
+    pub fn get_signers(e: Env) -> Option<SignerSet> {
+        e.get_signer_set()
+    }
+
+    pub fn propose_admin_action(e: Env, proposer: Address, action: AdminAction) -> u32 {
+        proposer.require_auth();
+        Self::__propose_admin_action(&e, proposer, action)
+    }
+
+    pub fn approve_admin_action(e: Env, signer: Address, proposal_id: u32) {
+        signer.require_auth();
+        Self::__approve_admin_action(&e, signer, proposal_id);
+    }
+
+    pub fn execute_admin_action(e: Env, proposal_id: u32) {
+        Self::__execute_admin_action(&e, proposal_id);
+    }
+
+    //open to anyone once the proposal has expired
+    pub fn expire_admin_action(e: Env, proposal_id: u32) {
+        let proposal = Self::__get_admin_proposal(&e, proposal_id);
+        if e.ledger().sequence() <= proposal.expires_at_ledger {
+            panic_with_error!(&e, Error::ProposalNotExpired);
+        }
+
+        e.remove_admin_proposal(proposal_id);
+    }
+
+    pub fn get_admin_proposal(e: Env, proposal_id: u32) -> AdminProposal {
+        Self::__get_admin_proposal(&e, proposal_id)
+    }
+
+    //while an upgrader is set, upgrades go through it instead of the admin
+    pub fn update_contract(e: Env, wasm_hash: BytesN<32>) {
+        match e.get_upgrader() {
+            Some(upgrader) => upgrader.require_auth(),
+            None => e.panic_if_not_single_admin(),
+        }
+        e.deployer().update_current_contract_wasm(wasm_hash)
+    }
+
+    pub fn set_upgrader(e: Env, upgrader: Option<Address>) {
+        e.panic_if_not_single_admin();
+        e.set_upgrader(&upgrader);
+    }
+
+    pub fn get_upgrader(e: Env) -> Option<Address> {
+        e.get_upgrader()
+    }
+
+    fn __add_repos(e: &Env, repos: Vec<String>) {
+        let mut current_repos = e.get_repos();
+        Self::__check_limit(
+            e,
+            symbol_short!("repos"),
+            current_repos.len() + repos.len(),
+            Self::__get_limits(e).max_repos,
+            Error::RepoLimitExceeded,
+        );
+
+        for repo in repos.iter() {
+            //check if the asset has been already added
+            if current_repos.contains_key(repo.clone()) {
+                panic_with_error!(&e, Error::RepoAlreadyExists);
+            }
+            //a name that still redirects to a tracked repo cannot be reused
+            if e.get_repo_alias(&repo).is_some() {
+                if current_repos.contains_key(Self::__resolve_repo(e, repo.clone())) {
+                    panic_with_error!(&e, Error::RepoAlreadyExists);
+                }
+                e.remove_repo_alias(&repo);
+            }
+
+            current_repos.set(repo.clone(), Map::new(e));
+            e.events().publish((symbol_short!("repo_add"), repo), ());
+        }
+        e.set_repos(current_repos);
+        Self::__commit_repos(e, &repos);
+    }
+
+    fn __add_issues(e: &Env, repo: String, issues: Vec<String>) {
+        let mut current_repos = e.get_repos();
+        if !current_repos.contains_key(repo.clone()) {
+            panic_with_error!(&e, Error::RepoMissing);
+        }
+
+        Self::__require_repo_active(e, &repo);
+
+        let mut current_issues = current_repos.get(repo.clone()).unwrap();
+        Self::__check_limit(
+            e,
+            symbol_short!("issues"),
+            current_issues.len() + issues.len(),
+            Self::__get_repo_issue_limit(e, &repo),
+            Error::IssueLimitExceeded,
+        );
+
+        for issue in issues.iter() {
+            //check if the issue has been already added
+            if current_issues.contains_key(issue.clone()) {
+                panic_with_error!(&e, Error::IssueAlreadyExists);
+            }
+
+            current_issues.set(issue.clone(), String::from_str(e, UNCLAIMED));
+        }
+        current_repos.set(repo.clone(), current_issues);
+        e.set_repos(current_repos);
+        Self::__commit_repos(e, &Vec::from_array(e, [repo.clone()]));
+        Self::__update_stats(e, &repo, |stats| stats.unclaimed_issues += issues.len());
+        e.events()
+            .publish((symbol_short!("issue_add"), repo), issues);
+    }
+
+    fn __remove_repos(e: &Env, repos: Vec<String>) {
+        let mut current_repos = e.get_repos();
+        let mut removed = Vec::new(e);
+        for repo in repos.iter() {
+            let repo = Self::__resolve_repo(e, repo);
+            removed.push_back(repo.clone());
+            if !current_repos.contains_key(repo.clone()) {
+                panic_with_error!(&e, Error::RepoMissing);
+            }
+
+            let issues = current_repos.get(repo.clone()).unwrap().keys();
+            for issue in issues.iter() {
+                Self::__panic_if_funds_held(e, &repo, &issue);
+            }
+            for issue in issues.iter() {
+                Self::__forget_issue(e, &repo, &issue);
+            }
+            current_repos.remove(repo.clone());
+            Self::__drop_repo_stats(e, &repo);
+            Self::__forget_repo(e, &repo);
+            e.events().publish((symbol_short!("repo_rm"), repo), ());
+        }
+        e.set_repos(current_repos);
+        Self::__commit_repos(e, &removed);
+    }
+
+    fn __remove_issues(e: &Env, repo: String, issues: Vec<String>) {
+        let mut current_repos = e.get_repos();
+        if !current_repos.contains_key(repo.clone()) {
+            panic_with_error!(&e, Error::RepoMissing);
+        }
+
+        let mut current_issues = current_repos.get(repo.clone()).unwrap();
+        for issue in issues.iter() {
+            let status = current_issues.get(issue.clone());
+            if status.is_none() {
+                panic_with_error!(&e, Error::IssueMissing);
+            }
+            Self::__panic_if_funds_held(e, &repo, &issue);
+
+            current_issues.remove(issue.clone());
+            Self::__forget_issue(e, &repo, &issue);
+            if status.unwrap() == String::from_str(e, COMPLETED) {
+                Self::__update_stats(e, &repo, |stats| {
+                    stats.completed_issues = stats.completed_issues.saturating_sub(1)
+                });
+            } else {
+                Self::__update_stats(e, &repo, |stats| {
+                    stats.unclaimed_issues = stats.unclaimed_issues.saturating_sub(1)
+                });
+            }
+        }
+        current_repos.set(repo.clone(), current_issues);
+        e.set_repos(current_repos);
+        Self::__commit_repos(e, &Vec::from_array(e, [repo.clone()]));
+        e.events()
+            .publish((symbol_short!("issue_rm"), repo), issues);
+    }
+
+    fn __fund_issue(
+        e: &Env,
+        funder: Address,
+        repo: String,
+        issue: String,
+        token: Address,
+        amount: i128,
+    ) {
+        Self::__credit_escrow(e, &repo, &issue, &token, amount);
+        token::Client::new(e, &token).transfer(&funder, &e.current_contract_address(), &amount);
+    }
+
+    fn __credit_escrow(e: &Env, repo: &String, issue: &String, token: &Address, amount: i128) {
+        if amount <= 0 {
+            panic_with_error!(&e, Error::InvalidAmount);
+        }
+
+        Self::__require_repo_active(e, repo);
+        let status = Self::__get_issue_status(e, repo, issue);
+        if status == String::from_str(e, COMPLETED) {
+            panic_with_error!(&e, Error::IssueAlreadyCompleted);
+        }
+
+        let mut escrow = e.get_escrow(repo, issue).unwrap_or(Escrow {
+            token: token.clone(),
+            amount: 0,
+        });
+        if escrow.token != *token {
+            panic_with_error!(&e, Error::EscrowTokenMismatch);
+        }
+
+        escrow.amount += amount;
+        e.set_escrow(repo, issue, &escrow);
+        Self::__update_stats(e, repo, |stats| stats.add_escrowed(token, amount));
+        e.events().publish(
+            (symbol_short!("funded"), repo.clone(), issue.clone()),
+            (token.clone(), amount),
+        );
+    }
+
+    fn __complete_issue(e: &Env, repo: String, issue: String, shares: Vec<(Address, u32)>) {
+        let mut current_repos = e.get_repos();
+        let mut current_issues = Self::__get_repo_issues(e, &current_repos, &repo);
+        Self::__require_repo_active(e, &repo);
+        let status = current_issues.get(issue.clone());
+        if status.is_none() {
+            panic_with_error!(&e, Error::IssueMissing);
+        }
+        if status.unwrap() == String::from_str(e, COMPLETED) {
+            panic_with_error!(&e, Error::IssueAlreadyCompleted);
+        }
+
+        //shares must be non-empty, non-zero and cover the whole escrow
+        let mut total_basis_points: u32 = 0;
+        for (_, basis_points) in shares.iter() {
+            if basis_points == 0 {
+                panic_with_error!(&e, Error::InvalidShares);
+            }
+            total_basis_points += basis_points;
+        }
+        if shares.is_empty() || total_basis_points != TOTAL_BASIS_POINTS {
+            panic_with_error!(&e, Error::InvalidShares);
+        }
+
+        let issue_ref = IssueRef {
+            repo: repo.clone(),
+            issue: issue.clone(),
+        };
+        for blocker in e.get_issue_blockers(&issue_ref).iter() {
+            if !Self::__is_issue_completed(e, &current_repos, &blocker.repo, &blocker.issue) {
+                panic_with_error!(&e, ExtendedError::IssueBlocked);
+            }
+        }
+
+        if let Some(escrow) = e.get_escrow(&repo, &issue) {
+            Self::__pay_out(e, &repo, &issue, escrow, shares.clone());
+            e.remove_escrow(&repo, &issue);
+        }
+
+        current_issues.set(issue.clone(), String::from_str(e, COMPLETED));
+        current_repos.set(repo.clone(), current_issues);
+        e.set_repos(current_repos);
+        let claimant = shares.get_unchecked(0).0;
+        e.set_issue_claimant(&repo, &issue, &claimant);
+        Self::__count_claimant(e, &repo, &claimant);
+        Self::__commit_repos(e, &Vec::from_array(e, [repo.clone()]));
+        //issues tracked before stats existed were never counted, hence the saturation
+        Self::__update_stats(e, &repo, |stats| {
+            stats.unclaimed_issues = stats.unclaimed_issues.saturating_sub(1);
+            stats.completed_issues += 1;
+        });
+
+        e.events().publish(
+            (symbol_short!("complete"), repo.clone(), issue.clone()),
+            shares,
+        );
+
+        for grant_id in e.get_issue_grants(&repo, &issue).iter() {
+            Self::__release_grant_milestones(e, grant_id);
+        }
+
+        Self::__queue_notifications(e, &repo, &issue, &String::from_str(e, COMPLETED));
+    }
+
+    fn __pay_out(
+        e: &Env,
+        repo: &String,
+        issue: &String,
+        escrow: Escrow,
+        shares: Vec<(Address, u32)>,
+    ) {
+        let token_client = token::Client::new(e, &escrow.token);
+        let contract = e.current_contract_address();
+
+        let schedule = e.get_vesting_schedule(repo, issue);
+
+        let mut payouts: Vec<(Address, i128)> = Vec::new(e);
+        let mut vested_shares: Map<Address, VestedShare> = Map::new(e);
+        let mut paid: i128 = 0;
+        let mut transferred: i128 = 0;
+        for (recipient, basis_points) in shares.iter() {
+            let amount = escrow.amount * basis_points as i128 / TOTAL_BASIS_POINTS as i128;
+
+            //with a vesting schedule only the upfront part is paid on completion
+            let upfront = match &schedule {
+                Some(schedule) => {
+                    amount * schedule.upfront_basis_points as i128 / TOTAL_BASIS_POINTS as i128
+                }
+                None => amount,
+            };
+            if upfront > 0 {
+                token_client.transfer(&contract, &recipient, &upfront);
+                transferred += upfront;
+            }
+            if amount > upfront {
+                let mut vested_share =
+                    vested_shares.get(recipient.clone()).unwrap_or(VestedShare {
+                        total: 0,
+                        withdrawn: 0,
+                    });
+                vested_share.total += amount - upfront;
+                vested_shares.set(recipient.clone(), vested_share);
+            }
+            paid += amount;
+            payouts.push_back((recipient, amount));
+        }
+
+        if let Some(schedule) = schedule {
+            if !vested_shares.is_empty() {
+                let start_ledger = e.ledger().sequence();
+                let end_ledger = start_ledger + schedule.duration_ledgers;
+                e.set_vesting(
+                    repo,
+                    issue,
+                    &Vesting {
+                        token: escrow.token.clone(),
+                        start_ledger,
+                        end_ledger,
+                        shares: vested_shares,
+                    },
+                );
+                e.events().publish(
+                    (symbol_short!("vesting"), repo.clone(), issue.clone()),
+                    (start_ledger, end_ledger),
+                );
+            }
+        }
+
+        //rounding leftovers go to the dust recipient, falling back to the admin
+        let dust = escrow.amount - paid;
+        if dust > 0 {
+            let dust_recipient = e.get_dust_recipient().or_else(|| e.get_admin()).unwrap();
+            token_client.transfer(&contract, &dust_recipient, &dust);
+            payouts.push_back((dust_recipient, dust));
+            transferred += dust;
+        }
+
+        //vested parts stay counted as escrowed until they are withdrawn
+        Self::__update_stats(e, repo, |stats| {
+            stats.add_escrowed(&escrow.token, -transferred);
+            stats.add_paid_out(&escrow.token, transferred);
+        });
+
+        e.events().publish(
+            (symbol_short!("payout"), repo.clone(), issue.clone()),
+            (escrow.token, payouts),
+        );
+    }
+
+    fn __get_repo_issues(
+        e: &Env,
+        repos: &Map<String, Map<String, String>>,
+        repo: &String,
+    ) -> Map<String, String> {
+        let issues = repos.get(repo.clone());
+        if issues.is_none() {
+            panic_with_error!(&e, Error::RepoMissing);
+        }
+
+        issues.unwrap()
+    }
+
+    fn __get_issue_status(e: &Env, repo: &String, issue: &String) -> String {
+        let status = Self::__get_repo_issues(e, &e.get_repos(), repo).get(issue.clone());
+        if status.is_none() {
+            panic_with_error!(&e, Error::IssueMissing);
+        }
+
+        status.unwrap()
+    }
+
+    fn __create_grant(e: &Env, mut data: GrantData) -> u32 {
+        if data.milestones.is_empty() {
+            panic_with_error!(&e, Error::InvalidGrant);
+        }
+
+        let repos = e.get_repos();
+        let mut total_amount: i128 = 0;
+        for (index, mut milestone) in data.milestones.clone().iter().enumerate() {
+            if milestone.issues.is_empty() || milestone.amount <= 0 {
+                panic_with_error!(&e, Error::InvalidGrant);
+            }
+            milestone.issues = Self::__resolve_issue_refs(e, &milestone.issues);
+            data.milestones.set(index as u32, milestone.clone());
+            for issue_ref in milestone.issues.iter() {
+                //make sure the issue is tracked
+                let issues = Self::__get_repo_issues(e, &repos, &issue_ref.repo);
+                if !issues.contains_key(issue_ref.issue.clone()) {
+                    panic_with_error!(&e, Error::IssueMissing);
+                }
+            }
+            total_amount += milestone.amount;
+        }
+
+        token::Client::new(e, &data.token).transfer(
+            &data.funder,
+            &e.current_contract_address(),
+            &total_amount,
+        );
+        //grants span repos, so their funds only show up in the global stats
+        Self::__update_global_stats(e, |stats| stats.add_escrowed(&data.token, total_amount));
+
+        let grant_id = e.get_grant_count();
+        e.set_grant_count(grant_id + 1);
+        e.set_grant(
+            grant_id,
+            &Grant {
+                funder: data.funder,
+                grantee: data.grantee,
+                token: data.token,
+                milestones: data.milestones.clone(),
+                released_milestones: 0,
+                cancelled: false,
+            },
+        );
+
+        Self::__index_grant_issues(e, grant_id, &data.milestones);
+
+        //milestones made of already completed issues are released right away
+        Self::__release_grant_milestones(e, grant_id);
+
+        grant_id
+    }
+
+    fn __index_grant_issues(e: &Env, grant_id: u32, milestones: &Vec<Milestone>) {
+        for milestone in milestones.iter() {
+            for issue_ref in milestone.issues.iter() {
+                let mut grant_ids = e.get_issue_grants(&issue_ref.repo, &issue_ref.issue);
+                if !grant_ids.contains(grant_id) {
+                    grant_ids.push_back(grant_id);
+                    e.set_issue_grants(&issue_ref.repo, &issue_ref.issue, &grant_ids);
+                }
+            }
+        }
+    }
+
+    fn __cancel_grant(e: &Env, grant_id: u32) {
+        let mut grant = Self::__get_grant(e, grant_id);
+        if grant.cancelled {
+            panic_with_error!(&e, Error::GrantCancelled);
+        }
+
+        let mut remaining: i128 = 0;
+        for milestone in grant
+            .milestones
+            .iter()
+            .skip(grant.released_milestones as usize)
+        {
+            remaining += milestone.amount;
+        }
+        if remaining > 0 {
+            token::Client::new(e, &grant.token).transfer(
+                &e.current_contract_address(),
+                &grant.funder,
+                &remaining,
+            );
+            Self::__update_global_stats(e, |stats| stats.add_escrowed(&grant.token, -remaining));
+        }
+
+        grant.cancelled = true;
+        e.set_grant(grant_id, &grant);
+    }
+
+    fn __release_grant_milestones(e: &Env, grant_id: u32) {
+        let mut grant = Self::__get_grant(e, grant_id);
+        if grant.cancelled {
+            return;
+        }
+
+        let repos = e.get_repos();
+        let token_client = token::Client::new(e, &grant.token);
+        while grant.released_milestones < grant.milestones.len() {
+            let milestone = grant.milestones.get(grant.released_milestones).unwrap();
+            for issue_ref in milestone.issues.iter() {
+                if !Self::__is_issue_completed(e, &repos, &issue_ref.repo, &issue_ref.issue) {
+                    e.set_grant(grant_id, &grant);
+                    return;
+                }
+            }
+
+            token_client.transfer(
+                &e.current_contract_address(),
+                &grant.grantee,
+                &milestone.amount,
+            );
+            Self::__update_global_stats(e, |stats| {
+                stats.add_escrowed(&grant.token, -milestone.amount);
+                stats.add_paid_out(&grant.token, milestone.amount);
+            });
+            e.events().publish(
+                (
+                    symbol_short!("tranche"),
+                    grant_id,
+                    grant.released_milestones,
+                ),
+                (grant.grantee.clone(), grant.token.clone(), milestone.amount),
+            );
+            grant.released_milestones += 1;
+        }
+        e.set_grant(grant_id, &grant);
+    }
+
+    fn __get_grant(e: &Env, grant_id: u32) -> Grant {
+        let grant = e.get_grant(grant_id);
+        if grant.is_none() {
+            panic_with_error!(&e, Error::GrantMissing);
+        }
+
+        grant.unwrap()
+    }
+
+    fn __is_issue_completed(
+        e: &Env,
+        repos: &Map<String, Map<String, String>>,
+        repo: &String,
+        issue: &String,
+    ) -> bool {
+        repos
+            .get(repo.clone())
+            .and_then(|issues| issues.get(issue.clone()))
+            .is_some_and(|status| status == String::from_str(e, COMPLETED))
+    }
+
+    fn __withdraw_vested(e: &Env, contributor: Address, repo: String, issue: String) -> i128 {
+        if e.get_repo_state(&repo) == RepoState::Paused {
+            panic_with_error!(&e, ExtendedError::RepoInactive);
+        }
+
+        let mut vesting = Self::__get_vesting(e, &repo, &issue);
+        let share = vesting.shares.get(contributor.clone());
+        if share.is_none() {
+            panic_with_error!(&e, Error::NothingToWithdraw);
+        }
+
+        let mut share = share.unwrap();
+        let amount = Self::__vested_amount(e, &vesting, &share) - share.withdrawn;
+        if amount <= 0 {
+            panic_with_error!(&e, Error::NothingToWithdraw);
+        }
+
+        token::Client::new(e, &vesting.token).transfer(
+            &e.current_contract_address(),
+            &contributor,
+            &amount,
+        );
+
+        share.withdrawn += amount;
+        vesting.shares.set(contributor, share);
+        e.set_vesting(&repo, &issue, &vesting);
+        Self::__update_stats(e, &repo, |stats| {
+            stats.add_escrowed(&vesting.token, -amount);
+            stats.add_paid_out(&vesting.token, amount);
+        });
+
+        amount
+    }
+
+    fn __clawback(e: &Env, repo: String, issue: String, recipient: Address) -> i128 {
+        let mut vesting = Self::__get_vesting(e, &repo, &issue);
+
+        //whatever has vested so far stays withdrawable, the rest is returned
+        let mut clawed_back: i128 = 0;
+        for (contributor, mut share) in vesting.shares.iter() {
+            let vested = Self::__vested_amount(e, &vesting, &share);
+            clawed_back += share.total - vested;
+            share.total = vested;
+            vesting.shares.set(contributor, share);
+        }
+        vesting.end_ledger = e.ledger().sequence().max(vesting.start_ledger);
+        e.set_vesting(&repo, &issue, &vesting);
+
+        if clawed_back > 0 {
+            token::Client::new(e, &vesting.token).transfer(
+                &e.current_contract_address(),
+                &recipient,
+                &clawed_back,
+            );
+            Self::__update_stats(e, &repo, |stats| {
+                stats.add_escrowed(&vesting.token, -clawed_back)
+            });
+        }
+        e.events().publish(
+            (symbol_short!("clawback"), repo, issue),
+            (recipient, clawed_back),
+        );
+
+        clawed_back
+    }
+
+    fn __refund_escrow(e: &Env, repo: String, issue: String, recipient: Address) -> i128 {
+        Self::__get_issue_status(e, &repo, &issue);
+        let escrow = e.get_escrow(&repo, &issue);
+        if escrow.is_none() {
+            panic_with_error!(&e, Error::NothingToWithdraw);
+        }
+
+        let escrow = escrow.unwrap();
+        e.remove_escrow(&repo, &issue);
+        token::Client::new(e, &escrow.token).transfer(
+            &e.current_contract_address(),
+            &recipient,
+            &escrow.amount,
+        );
+        Self::__update_stats(e, &repo, |stats| {
+            stats.add_escrowed(&escrow.token, -escrow.amount)
+        });
+        e.events().publish(
+            (symbol_short!("refunded"), repo, issue),
+            (recipient, escrow.amount),
+        );
+
+        escrow.amount
+    }
+
+    fn __vested_amount(e: &Env, vesting: &Vesting, share: &VestedShare) -> i128 {
+        let now = e.ledger().sequence();
+        if now >= vesting.end_ledger {
+            return share.total;
+        }
+
+        let elapsed = now.saturating_sub(vesting.start_ledger) as i128;
+        let duration = (vesting.end_ledger - vesting.start_ledger) as i128;
+        share.total * elapsed / duration
+    }
+
+    fn __get_vesting(e: &Env, repo: &String, issue: &String) -> Vesting {
+        let vesting = e.get_vesting(repo, issue);
+        if vesting.is_none() {
+            panic_with_error!(&e, Error::VestingMissing);
+        }
+
+        vesting.unwrap()
+    }
+
+    fn __propose_issue(e: &Env, proposer: Address, repo: String, issue: String) -> u32 {
+        let deposit_config = e.get_proposal_deposit();
+        if deposit_config.is_none() {
+            panic_with_error!(&e, Error::ProposalsDisabled);
+        }
+        let deposit_config = deposit_config.unwrap();
+
+        let issues = Self::__get_repo_issues(e, &e.get_repos(), &repo);
+        Self::__require_repo_active(e, &repo);
+        if issues.contains_key(issue.clone()) {
+            panic_with_error!(&e, Error::IssueAlreadyExists);
+        }
+
+        //check if the issue is already waiting for review
+        let mut pending = e.get_pending_issue_proposals();
+        for proposal_id in pending.iter() {
+            let proposal = e.get_issue_proposal(proposal_id).unwrap();
+            if proposal.repo == repo && proposal.issue == issue {
+                panic_with_error!(&e, Error::ProposalAlreadyExists);
+            }
+        }
+
+        token::Client::new(e, &deposit_config.token).transfer(
+            &proposer,
+            &e.current_contract_address(),
+            &deposit_config.amount,
+        );
+
+        let proposal_id = e.get_issue_proposal_count();
+        e.set_issue_proposal_count(proposal_id + 1);
+        e.set_issue_proposal(&IssueProposal {
+            id: proposal_id,
+            proposer,
+            repo,
+            issue,
+            token: deposit_config.token,
+            deposit: deposit_config.amount,
+            expires_at_ledger: e.ledger().sequence() + deposit_config.expiry_ledgers,
+        });
+        pending.push_back(proposal_id);
+        e.set_pending_issue_proposals(&pending);
+
+        proposal_id
+    }
+
+    fn __approve_issue_proposal(e: &Env, proposal_id: u32) {
+        let proposal = Self::__get_issue_proposal(e, proposal_id);
+        if e.ledger().sequence() > proposal.expires_at_ledger {
+            panic_with_error!(&e, Error::ProposalExpired);
+        }
+
+        Self::__add_issues(
+            e,
+            proposal.repo.clone(),
+            Vec::from_array(e, [proposal.issue.clone()]),
+        );
+        Self::__close_issue_proposal(e, &proposal, &proposal.proposer);
+    }
+
+    fn __reject_issue_proposal(e: &Env, proposal_id: u32, slash: bool) {
+        let proposal = Self::__get_issue_proposal(e, proposal_id);
+
+        //a slashed deposit is kept by the admin instead of being refunded
+        let deposit_recipient = if slash {
+            e.get_admin().unwrap()
+        } else {
+            proposal.proposer.clone()
+        };
+        Self::__close_issue_proposal(e, &proposal, &deposit_recipient);
+    }
+
+    fn __close_issue_proposal(e: &Env, proposal: &IssueProposal, deposit_recipient: &Address) {
+        if proposal.deposit > 0 {
+            token::Client::new(e, &proposal.token).transfer(
+                &e.current_contract_address(),
+                deposit_recipient,
+                &proposal.deposit,
+            );
+        }
+
+        let mut pending = e.get_pending_issue_proposals();
+        if let Some(index) = pending.first_index_of(proposal.id) {
+            pending.remove(index);
+        }
+        e.set_pending_issue_proposals(&pending);
+        e.remove_issue_proposal(proposal.id);
+    }
+
+    fn __get_issue_proposal(e: &Env, proposal_id: u32) -> IssueProposal {
+        let proposal = e.get_issue_proposal(proposal_id);
+        if proposal.is_none() {
+            panic_with_error!(&e, Error::ProposalMissing);
+        }
+
+        proposal.unwrap()
+    }
+
+    fn __deposit_read_credit(e: &Env, consumer: Address, amount: i128) {
+        let pricing = e.get_read_pricing();
+        if pricing.is_none() {
+            panic_with_error!(&e, ExtendedError::ReadPricingMissing);
+        }
+        if amount <= 0 {
+            panic_with_error!(&e, Error::InvalidAmount);
+        }
+        let token = pricing.unwrap().token;
+        token::Client::new(e, &token).transfer(&consumer, &e.current_contract_address(), &amount);
+
+        let mut credit = e.get_read_credit(&consumer);
+        credit.set(
+            token.clone(),
+            credit.get(token.clone()).unwrap_or(0) + amount,
+        );
+        e.set_read_credit(&consumer, &credit);
+        e.events()
+            .publish((symbol_short!("credit"), consumer), (token, amount));
+    }
+
+    fn __debit_read_credit(e: &Env, consumer: &Address, token: &Address, amount: i128) {
+        let mut credit = e.get_read_credit(consumer);
+        let balance = credit.get(token.clone()).unwrap_or(0);
+        if balance < amount {
+            panic_with_error!(&e, ExtendedError::InsufficientCredit);
+        }
+        if balance == amount {
+            credit.remove(token.clone());
+        } else {
+            credit.set(token.clone(), balance - amount);
+        }
+        e.set_read_credit(consumer, &credit);
+    }
+
+    //periods are aligned to the ledger so usage can be keyed by the period it counts towards
+    fn __read_period_start(e: &Env, pricing: &ReadPricing) -> u32 {
+        let ledger = e.ledger().sequence();
+        ledger - ledger % pricing.period_ledgers
+    }
+
+    //counts the read against the consumer's free tier, then charges for it
+    fn __charge_read(e: &Env, consumer: &Address) {
+        let Some(pricing) = e.get_read_pricing() else {
+            return;
+        };
+        consumer.require_auth();
+
+        let period_start_ledger = Self::__read_period_start(e, &pricing);
+        let mut usage = e
+            .get_read_usage(consumer, period_start_ledger)
+            .unwrap_or(ReadUsage {
+                period_start_ledger,
+                reads: 0,
+            });
+        usage.reads += 1;
+        e.set_read_usage(
+            consumer,
+            &usage,
+            period_start_ledger + pricing.period_ledgers,
+        );
+
+        if usage.reads <= pricing.free_reads || pricing.fee == 0 {
+            return;
+        }
+        Self::__debit_read_credit(e, consumer, &pricing.token, pricing.fee);
+        let mut revenue = e.get_read_revenue();
+        revenue.set(
+            pricing.token.clone(),
+            revenue.get(pricing.token.clone()).unwrap_or(0) + pricing.fee,
+        );
+        e.set_read_revenue(&revenue);
+    }
+
+    fn __subscribe(e: &Env, subscriber: Address, repo: String, issue: Option<String>) -> u32 {
+        let config = e.get_subscription_config();
+        if config.is_none() {
+            panic_with_error!(&e, ExtendedError::SubscriptionsDisabled);
+        }
+        let config = config.unwrap();
+
+        let issues = Self::__get_repo_issues(e, &e.get_repos(), &repo);
+        if let Some(issue) = &issue {
+            match issues.get(issue.clone()) {
+                None => panic_with_error!(&e, Error::IssueMissing),
+                Some(status) if status == String::from_str(e, COMPLETED) => {
+                    panic_with_error!(&e, Error::IssueAlreadyCompleted)
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut subscription_ids = e.get_repo_subscriptions(&repo);
+        if subscription_ids.len() >= MAX_SUBSCRIPTIONS_PER_REPO {
+            panic_with_error!(&e, ExtendedError::SubscriptionLimitExceeded);
+        }
+
+        if config.fee > 0 {
+            token::Client::new(e, &config.token).transfer(
+                &subscriber,
+                &e.get_admin().unwrap(),
+                &config.fee,
+            );
+        }
+
+        let subscription_id = e.get_subscription_count();
+        e.set_subscription_count(subscription_id + 1);
+        e.set_subscription(&Subscription {
+            id: subscription_id,
+            subscriber,
+            repo: repo.clone(),
+            issue,
+            expires_at_ledger: e.ledger().sequence() + config.duration_ledgers,
+            failures: 0,
+        });
+        subscription_ids.push_back(subscription_id);
+        e.set_repo_subscriptions(&repo, &subscription_ids);
+
+        e.events()
+            .publish((symbol_short!("subscribe"), repo), subscription_id);
+        subscription_id
+    }
+
+    //callbacks run later through `dispatch_notification`, so a subscriber can
+    //never hold up a completion
+    fn __queue_notifications(e: &Env, repo: &String, issue: &String, status: &String) {
+        for subscription_id in e.get_repo_subscriptions(repo).iter() {
+            let subscription = e.get_subscription(subscription_id).unwrap();
+            if e.ledger().sequence() > subscription.expires_at_ledger {
+                Self::__drop_subscription(e, &subscription);
+                continue;
+            }
+            if subscription
+                .issue
+                .as_ref()
+                .is_some_and(|watched| watched != issue)
+            {
+                continue;
+            }
+
+            let notification_id = e.get_notification_count();
+            e.set_notification_count(notification_id + 1);
+            e.set_notification(&Notification {
+                id: notification_id,
+                subscription_id,
+                subscriber: subscription.subscriber,
+                repo: repo.clone(),
+                issue: issue.clone(),
+                status: status.clone(),
+            });
+            e.events()
+                .publish((symbol_short!("queued"), subscription_id), notification_id);
+        }
+    }
+
+    fn __dispatch_notification(e: &Env, notification_id: u32) -> bool {
+        let notification = Self::__get_notification(e, notification_id);
+        e.remove_notification(notification_id);
+        //the subscription was dropped since, so nobody is waiting for the update
+        let Some(mut subscription) = e.get_subscription(notification.subscription_id) else {
+            return false;
+        };
+
+        //a failing callback is recorded instead of reverting the dispatch
+        let callback = Symbol::new(e, "on_oracle_update");
+        let args = vec![
+            e,
+            notification.repo.into_val(e),
+            notification.issue.into_val(e),
+            notification.status.into_val(e),
+        ];
+        let result = e.try_invoke_contract::<(), soroban_sdk::Error>(
+            &notification.subscriber,
+            &callback,
+            args,
+        );
+        let delivered = matches!(result, Ok(Ok(())));
+        if delivered {
+            subscription.failures = 0;
+        } else {
+            subscription.failures += 1;
+            e.events().publish(
+                (symbol_short!("cb_failed"), subscription.id),
+                subscription.repo.clone(),
+            );
+        }
+
+        //an issue only completes once, so its subscriptions are done either way
+        if subscription.issue.is_some() || subscription.failures >= MAX_CALLBACK_FAILURES {
+            Self::__drop_subscription(e, &subscription);
+        } else {
+            e.set_subscription(&subscription);
+        }
+        delivered
+    }
+
+    fn __get_notification(e: &Env, notification_id: u32) -> Notification {
+        let notification = e.get_notification(notification_id);
+        if notification.is_none() {
+            panic_with_error!(&e, ExtendedError::NotificationMissing);
+        }
+
+        notification.unwrap()
+    }
+
+    fn __drop_subscription(e: &Env, subscription: &Subscription) {
+        let mut subscription_ids = e.get_repo_subscriptions(&subscription.repo);
+        if let Some(index) = subscription_ids.first_index_of(subscription.id) {
+            subscription_ids.remove(index);
+        }
+        e.set_repo_subscriptions(&subscription.repo, &subscription_ids);
+        e.remove_subscription(subscription.id);
+    }
+
+    fn __get_subscription(e: &Env, subscription_id: u32) -> Subscription {
+        let subscription = e.get_subscription(subscription_id);
+        if subscription.is_none() {
+            panic_with_error!(&e, ExtendedError::SubscriptionMissing);
+        }
+
+        subscription.unwrap()
+    }
+
+    fn __set_limits(e: &Env, limits: Limits) {
+        if limits.max_repos == 0 || limits.max_issues_per_repo == 0 {
+            panic_with_error!(&e, Error::InvalidLimits);
+        }
+        e.set_limits(&limits);
+    }
+
+    fn __get_limits(e: &Env) -> Limits {
+        e.get_limits().unwrap_or(Limits {
+            max_repos: DEFAULT_MAX_REPOS,
+            max_issues_per_repo: DEFAULT_MAX_ISSUES_PER_REPO,
+        })
+    }
+
+    fn __get_repo_issue_limit(e: &Env, repo: &String) -> u32 {
+        e.get_repo_issue_limit(repo)
+            .unwrap_or_else(|| Self::__get_limits(e).max_issues_per_repo)
+    }
+
+    //the `limit` event reaches simulations and diagnostics, the failed call keeps nothing
+    fn __check_limit(e: &Env, kind: Symbol, count: u32, limit: u32, error: Error) {
+        if count > limit {
+            e.events()
+                .publish((symbol_short!("limit"), kind), (count, limit));
+            panic_with_error!(&e, error);
+        }
+    }
+
+    fn __set_repo_issue_limit(e: &Env, repo: String, limit: Option<u32>) {
+        Self::__get_repo_issues(e, &e.get_repos(), &repo);
+        match limit {
+            Some(0) => panic_with_error!(&e, Error::InvalidLimits),
+            Some(limit) => e.set_repo_issue_limit(&repo, limit),
+            None => e.remove_repo_issue_limit(&repo),
+        }
+    }
+
+    fn __set_signers(e: &Env, signer_set: SignerSet) {
+        let mut total_weight: u32 = 0;
+        for (_, weight) in signer_set.signers.iter() {
+            if weight == 0 {
+                panic_with_error!(&e, Error::InvalidSignerSet);
+            }
+            total_weight += weight;
+        }
+        if signer_set.threshold == 0
+            || signer_set.threshold > total_weight
+            || signer_set.proposal_expiry_ledgers == 0
+        {
+            panic_with_error!(&e, Error::InvalidSignerSet);
+        }
+
+        e.set_signer_set(&signer_set);
+    }
+
+    fn __get_signer_set(e: &Env) -> SignerSet {
+        let signer_set = e.get_signer_set();
+        if signer_set.is_none() {
+            panic_with_error!(&e, Error::MultisigNotConfigured);
+        }
+
+        signer_set.unwrap()
+    }
+
+    fn __propose_admin_action(e: &Env, proposer: Address, action: AdminAction) -> u32 {
+        let signer_set = Self::__get_signer_set(e);
+        if !signer_set.signers.contains_key(proposer.clone()) {
+            panic_with_error!(&e, Error::NotSigner);
+        }
+
+        let proposal_id = e.get_admin_proposal_count();
+        e.set_admin_proposal_count(proposal_id + 1);
+        e.set_admin_proposal(&AdminProposal {
+            id: proposal_id,
+            action,
+            approvals: Vec::from_array(e, [proposer]),
+            expires_at_ledger: e.ledger().sequence() + signer_set.proposal_expiry_ledgers,
+        });
+
+        proposal_id
+    }
+
+    fn __approve_admin_action(e: &Env, signer: Address, proposal_id: u32) {
+        let signer_set = Self::__get_signer_set(e);
+        if !signer_set.signers.contains_key(signer.clone()) {
+            panic_with_error!(&e, Error::NotSigner);
+        }
+
+        let mut proposal = Self::__get_open_admin_proposal(e, proposal_id);
+        if !proposal.approvals.contains(signer.clone()) {
+            proposal.approvals.push_back(signer);
+            e.set_admin_proposal(&proposal);
+        }
+    }
+
+    fn __execute_admin_action(e: &Env, proposal_id: u32) {
+        let signer_set = Self::__get_signer_set(e);
+        let proposal = Self::__get_open_admin_proposal(e, proposal_id);
+
+        //weights are taken from the current signer set, so removed signers no longer count
+        let mut approved_weight: u32 = 0;
+        for signer in proposal.approvals.iter() {
+            approved_weight += signer_set.signers.get(signer).unwrap_or(0);
+        }
+        if approved_weight < signer_set.threshold {
+            panic_with_error!(&e, Error::ThresholdNotMet);
+        }
+
+        //drop the proposal before running it so it can never be replayed
+        e.remove_admin_proposal(proposal_id);
+
+        match proposal.action {
+            AdminAction::RequestClear(state_hash) => Self::__request_clear(e, state_hash),
+            AdminAction::ConfirmClear(state_hash) => {
+                Self::__confirm_clear(e, state_hash);
+            }
+            AdminAction::UpdateContract(wasm_hash) => {
+                e.deployer().update_current_contract_wasm(wasm_hash)
+            }
+            AdminAction::SetAdmin(admin) => e.set_admin(&admin),
+            AdminAction::SetSigners(signer_set) => Self::__set_signers(e, signer_set),
+            AdminAction::RemoveSigners => e.remove_signer_set(),
+            AdminAction::SetLimits(limits) => Self::__set_limits(e, limits),
+            AdminAction::SetRepoIssueLimit(repo, limit) => {
+                Self::__set_repo_issue_limit(e, repo, limit)
+            }
+            AdminAction::SetUpgrader(upgrader) => e.set_upgrader(&upgrader),
+            AdminAction::SetAttestor(attestor) => e.set_attestor(&attestor),
+            AdminAction::RegisterVoters(voters) => Self::__register_voters(e, voters),
+            AdminAction::RemoveVoters(voters) => Self::__remove_voters(e, voters),
+            AdminAction::SetOrgMaintainers(org_name, maintainers) => {
+                Self::__set_org_maintainers(e, org_name, maintainers)
+            }
+            AdminAction::SetDustRecipient(recipient) => e.set_dust_recipient(&recipient),
+        }
+
+        e.events()
+            .publish((symbol_short!("executed"), proposal_id), ());
+    }
+
+    fn __get_admin_proposal(e: &Env, proposal_id: u32) -> AdminProposal {
+        let proposal = e.get_admin_proposal(proposal_id);
+        if proposal.is_none() {
+            panic_with_error!(&e, Error::AdminProposalMissing);
+        }
+
+        proposal.unwrap()
+    }
+
+    fn __get_open_admin_proposal(e: &Env, proposal_id: u32) -> AdminProposal {
+        let proposal = Self::__get_admin_proposal(e, proposal_id);
+        if e.ledger().sequence() > proposal.expires_at_ledger {
+            panic_with_error!(&e, Error::AdminProposalExpired);
+        }
+
+        proposal
+    }
+
+    fn __set_repo_governance(e: &Env, governance: RepoGovernance) {
+        if let RepoGovernance::Voting(voting_config) = &governance {
+            if voting_config.voting_period_ledgers == 0 || voting_config.quorum < 0 {
+                panic_with_error!(&e, Error::InvalidVotingConfig);
+            }
+        }
+        e.set_repo_governance(&governance);
+    }
+
+    fn __register_voters(e: &Env, voters: Vec<Address>) {
+        let mut current_voters = e.get_voters();
+        for voter in voters.iter() {
+            if !current_voters.contains(voter.clone()) {
+                current_voters.push_back(voter);
+            }
+        }
+        e.set_voters(&current_voters);
+    }
+
+    fn __remove_voters(e: &Env, voters: Vec<Address>) {
+        let mut current_voters = e.get_voters();
+        for voter in voters.iter() {
+            if let Some(index) = current_voters.first_index_of(voter) {
+                current_voters.remove(index);
+            }
+        }
+        e.set_voters(&current_voters);
+    }
+
+    fn __panic_if_repo_voting(e: &Env) {
+        if let RepoGovernance::Voting(_) = e.get_repo_governance() {
+            panic_with_error!(&e, Error::RepoVotingActive);
+        }
+    }
+
+    fn __propose_repo_change(e: &Env, proposer: Address, change: RepoChange) -> u32 {
+        let voting_config = match e.get_repo_governance() {
+            RepoGovernance::Voting(voting_config) => voting_config,
+            RepoGovernance::Admin => panic_with_error!(&e, Error::RepoVotingDisabled),
+        };
+
+        let voters = e.get_voters();
+        if !voters.contains(proposer.clone()) {
+            panic_with_error!(&e, Error::NotVoter);
+        }
+
+        let repos = e.get_repos();
+        match &change {
+            RepoChange::Add(forge, repo) => {
+                if repos.contains_key(Self::__repo_key(e, *forge, repo)) {
+                    panic_with_error!(&e, Error::RepoAlreadyExists);
+                }
+            }
+            RepoChange::Remove(repo) => {
+                if !repos.contains_key(Self::__resolve_repo(e, repo.clone())) {
+                    panic_with_error!(&e, Error::RepoMissing);
+                }
+            }
+        }
+
+        //snapshot the voting weights so later transfers cannot change the outcome
+        let mut weights: Map<Address, i128> = Map::new(e);
+        for voter in voters.iter() {
+            let weight = match &voting_config.weight_token {
+                Some(weight_token) => token::Client::new(e, weight_token).balance(&voter),
+                None => 1,
+            };
+            if weight > 0 {
+                weights.set(voter, weight);
+            }
+        }
+
+        let proposal_id = e.get_repo_proposal_count();
+        e.set_repo_proposal_count(proposal_id + 1);
+        e.set_repo_proposal(&RepoProposal {
+            id: proposal_id,
+            proposer,
+            change,
+            weights,
+            voted: Vec::new(e),
+            votes_for: 0,
+            votes_against: 0,
+            end_ledger: e.ledger().sequence() + voting_config.voting_period_ledgers,
+        });
+
+        proposal_id
+    }
+
+    fn __vote_repo_change(e: &Env, voter: Address, proposal_id: u32, support: bool) {
+        let mut proposal = Self::__get_repo_proposal(e, proposal_id);
+        if e.ledger().sequence() > proposal.end_ledger {
+            panic_with_error!(&e, Error::VotingClosed);
+        }
+
+        let weight = proposal.weights.get(voter.clone());
+        if weight.is_none() {
+            panic_with_error!(&e, Error::NotVoter);
+        }
+        if proposal.voted.contains(voter.clone()) {
+            panic_with_error!(&e, Error::AlreadyVoted);
+        }
+
+        if support {
+            proposal.votes_for += weight.unwrap();
+        } else {
+            proposal.votes_against += weight.unwrap();
+        }
+        proposal.voted.push_back(voter);
+        e.set_repo_proposal(&proposal);
+    }
+
+    fn __execute_repo_change(e: &Env, proposal_id: u32) {
+        let voting_config = match e.get_repo_governance() {
+            RepoGovernance::Voting(voting_config) => voting_config,
+            RepoGovernance::Admin => panic_with_error!(&e, Error::RepoVotingDisabled),
+        };
+
+        let proposal = Self::__get_repo_proposal(e, proposal_id);
+        if e.ledger().sequence() <= proposal.end_ledger {
+            panic_with_error!(&e, Error::VotingOpen);
+        }
+        if !Self::__repo_change_passed(&proposal, &voting_config) {
+            panic_with_error!(&e, Error::RepoProposalRejected);
+        }
+
+        //dropping the proposal before running it means it can never be replayed
+        e.remove_repo_proposal(proposal_id);
+
+        match proposal.change {
+            RepoChange::Add(forge, repo) => {
+                let repo = Self::__repo_key(e, forge, &repo);
+                Self::__add_repos(e, Vec::from_array(e, [repo]));
+            }
+            RepoChange::Remove(repo) => Self::__remove_repos(e, Vec::from_array(e, [repo])),
+        }
+    }
+
+    fn __expire_repo_change(e: &Env, proposal_id: u32) {
+        let proposal = Self::__get_repo_proposal(e, proposal_id);
+        if e.ledger().sequence() <= proposal.end_ledger {
+            panic_with_error!(&e, Error::VotingOpen);
+        }
+        //a passed change stays executable for one more voting period
+        if let RepoGovernance::Voting(voting_config) = e.get_repo_governance() {
+            if Self::__repo_change_passed(&proposal, &voting_config)
+                && e.ledger().sequence()
+                    <= proposal.end_ledger + voting_config.voting_period_ledgers
+            {
+                panic_with_error!(&e, Error::ProposalNotExpired);
+            }
+        }
+
+        e.remove_repo_proposal(proposal_id);
+    }
+
+    fn __repo_change_passed(proposal: &RepoProposal, voting_config: &VotingConfig) -> bool {
+        proposal.votes_for > proposal.votes_against
+            && proposal.votes_for + proposal.votes_against >= voting_config.quorum
+    }
+
+    fn __get_repo_proposal(e: &Env, proposal_id: u32) -> RepoProposal {
+        let proposal = e.get_repo_proposal(proposal_id);
+        if proposal.is_none() {
+            panic_with_error!(&e, Error::RepoProposalMissing);
+        }
+
+        proposal.unwrap()
+    }
+
+    fn __commit_repos(e: &Env, repos: &Vec<String>) {
+        let current_repos = e.get_repos();
+        let mut roots = e.get_repo_roots();
+        for repo in repos.iter() {
+            match current_repos.get(repo.clone()) {
+                Some(issues) => {
+                    let leaves = Self::__issue_leaf_hashes(e, &repo, &issues);
+                    roots.set(repo, merkle_root(e, &leaves));
+                }
+                None => {
+                    roots.remove(repo);
+                }
+            }
+        }
+
+        e.set_state_root(&merkle_root(e, &Self::__repo_leaf_hashes(e, &roots)));
+        e.set_repo_roots(&roots);
+    }
+
+    //repo leaves are the leaves of the state root, in registry order
+    fn __repo_leaf_hashes(e: &Env, roots: &Map<String, BytesN<32>>) -> Vec<BytesN<32>> {
+        let mut leaves = Vec::new(e);
+        for (repo, root) in roots.iter() {
+            leaves.push_back(repo_leaf(e, &repo, &root));
+        }
+        leaves
+    }
+
+    fn __issue_leaf_hashes(
+        e: &Env,
+        repo: &String,
+        issues: &Map<String, String>,
+    ) -> Vec<BytesN<32>> {
+        let mut leaves = Vec::new(e);
+        for (issue, status) in issues.iter() {
+            let leaf = IssueLeaf {
+                repo: repo.clone(),
+                claimant: e.get_issue_claimant(repo, &issue),
+                issue,
+                status,
+            };
+            leaves.push_back(hash_leaf(e, &leaf));
+        }
+
+        leaves
+    }
+
+    fn __issue_proof(e: &Env, repo: &String, issue: &String) -> Vec<MerkleStep> {
+        let issues = Self::__get_repo_issues(e, &e.get_repos(), repo);
+        let issue_index = issues.keys().first_index_of(issue.clone());
+        if issue_index.is_none() {
+            panic_with_error!(&e, Error::IssueMissing);
+        }
+
+        let roots = e.get_repo_roots();
+        let repo_index = roots.keys().first_index_of(repo.clone());
+        if repo_index.is_none() {
+            //tracked since before the state root existed
+            panic_with_error!(&e, Error::RepoMissing);
+        }
+
+        let mut proof = merkle_path(
+            e,
+            &Self::__issue_leaf_hashes(e, repo, &issues),
+            issue_index.unwrap(),
+        );
+        proof.push_back(repo_step(e, repo));
+        proof.append(&merkle_path(
+            e,
+            &Self::__repo_leaf_hashes(e, &roots),
+            repo_index.unwrap(),
+        ));
+        proof
+    }
+
+    fn __state_hash(e: &Env, repos: &Map<String, Map<String, String>>) -> BytesN<32> {
+        e.crypto().sha256(&repos.clone().to_xdr(e)).to_bytes()
+    }
+
+    fn __export_chunk(e: &Env, cursor: u32) -> (Vec<Record>, Option<u32>) {
+        //chunk 0 holds roles and settings, chunk n the n-th repo and its issues
+        let repos = e.get_repos();
+        let mut records = Vec::new(e);
+        if cursor == 0 {
+            if let Some(admin) = e.get_admin() {
+                records.push_back(Record::Admin(admin));
+            }
+            if let Some(signer_set) = e.get_signer_set() {
+                records.push_back(Record::Signers(signer_set));
+            }
+            if let Some(attestor) = e.get_attestor() {
+                records.push_back(Record::Attestor(attestor));
+            }
+            if let Some(upgrader) = e.get_upgrader() {
+                records.push_back(Record::Upgrader(upgrader));
+            }
+            let voters = e.get_voters();
+            if !voters.is_empty() {
+                records.push_back(Record::Voters(voters));
+            }
+            if let Some(limits) = e.get_limits() {
+                records.push_back(Record::Limits(limits));
+            }
+            records.push_back(Record::RepoGovernance(e.get_repo_governance()));
+            if let Some(recipient) = e.get_dust_recipient() {
+                records.push_back(Record::DustRecipient(recipient));
+            }
+            if let Some(config) = e.get_proposal_deposit() {
+                records.push_back(Record::ProposalDeposit(config));
+            }
+            if let Some(config) = e.get_subscription_config() {
+                records.push_back(Record::SubscriptionConfig(config));
+            }
+            if let Some(pricing) = e.get_read_pricing() {
+                records.push_back(Record::ReadPricing(pricing));
+            }
+            let revenue = e.get_read_revenue();
+            if !revenue.is_empty() {
+                records.push_back(Record::ReadRevenue(revenue));
+            }
+            records.push_back(Record::GlobalStats(e.get_global_stats()));
+            for org_name in e.get_orgs().iter() {
+                records.push_back(Record::Org(e.get_org(&org_name).unwrap()));
+            }
+        } else if cursor <= repos.len() {
+            let Some((repo, issues)) = repos.iter().nth(cursor as usize - 1) else {
+                return (records, None);
+            };
+            records.push_back(Record::Repo(repo.clone(), issues.clone()));
+            let state = e.get_repo_state(&repo);
+            if state != RepoState::Active {
+                records.push_back(Record::RepoState(repo.clone(), state));
+            }
+            if let Some(maintainer) = e.get_repo_maintainer(&repo) {
+                records.push_back(Record::RepoMaintainer(repo.clone(), maintainer));
+            }
+            if let Some(limit) = e.get_repo_issue_limit(&repo) {
+                records.push_back(Record::RepoIssueLimit(repo.clone(), limit));
+            }
+            records.push_back(Record::RepoStats(repo.clone(), e.get_repo_stats(&repo)));
+            for issue in issues.keys().iter() {
+                if let Some(escrow) = e.get_escrow(&repo, &issue) {
+                    records.push_back(Record::Escrow(repo.clone(), issue.clone(), escrow));
+                }
+                if let Some(claimant) = e.get_issue_claimant(&repo, &issue) {
+                    records.push_back(Record::Claimant(repo.clone(), issue.clone(), claimant));
+                }
+                if let Some(vesting) = e.get_vesting(&repo, &issue) {
+                    records.push_back(Record::Vesting(repo.clone(), issue.clone(), vesting));
+                }
+                if let Some(schedule) = e.get_vesting_schedule(&repo, &issue) {
+                    records.push_back(Record::VestingSchedule(
+                        repo.clone(),
+                        issue.clone(),
+                        schedule,
+                    ));
+                }
+                let labels = e.get_issue_labels(&repo, &issue);
+                if !labels.is_empty() {
+                    records.push_back(Record::Labels(repo.clone(), issue.clone(), labels));
+                }
+                let issue_ref = IssueRef {
+                    repo: repo.clone(),
+                    issue,
+                };
+                let blockers = e.get_issue_blockers(&issue_ref);
+                if !blockers.is_empty() {
+                    records.push_back(Record::Blockers(issue_ref, blockers));
+                }
+            }
+            for subscription_id in e.get_repo_subscriptions(&repo).iter() {
+                records.push_back(Record::Subscription(
+                    e.get_subscription(subscription_id).unwrap(),
+                ));
+            }
+        } else {
+            //grants come after the repos, whose issues they refer to
+            let grant_id = cursor - repos.len() - 1;
+            if let Some(grant) = e.get_grant(grant_id) {
+                records.push_back(Record::Grant(grant_id, grant));
+            }
+        }
+
+        let next_cursor = if cursor < repos.len() + e.get_grant_count() {
+            Some(cursor + 1)
+        } else {
+            None
+        };
+        (records, next_cursor)
+    }
+
+    fn __get_import_state(e: &Env) -> ImportState {
+        let state = e.get_import_state();
+        if state.is_none() {
+            panic_with_error!(&e, ExtendedError::ImportMissing);
+        }
+
+        state.unwrap()
+    }
+
+    fn __import_chunk(e: &Env, mut state: ImportState, chunk: ExportChunk) {
+        //chaining the checksums rejects chunks that are corrupted, skipped or replayed
+        //chunks are exported one cursor at a time from 0, so that is the order they come in
+        if state.complete
+            || chunk.cursor != state.chunks
+            || chunk.checksum != chunk_checksum(e, &state.checksum, &chunk.records)
+        {
+            panic_with_error!(&e, ExtendedError::ChecksumMismatch);
+        }
+        //pages are positional, so they only fit together when taken from one snapshot
+        if state.chunks > 0 && state.state_hash != chunk.state_hash {
+            panic_with_error!(&e, Error::StateHashMismatch);
+        }
+
+        let mut committed = Vec::new(e);
+        for record in chunk.records.iter() {
+            match record {
+                Record::Admin(_)
+                | Record::Signers(_)
+                | Record::Attestor(_)
+                | Record::Upgrader(_)
+                | Record::Voters(_) => state.deferred_roles.push_back(record),
+                Record::Limits(limits) => e.set_limits(&limits),
+                Record::RepoGovernance(governance) => e.set_repo_governance(&governance),
+                Record::DustRecipient(recipient) => e.set_dust_recipient(&recipient),
+                Record::ProposalDeposit(config) => e.set_proposal_deposit(&config),
+                Record::SubscriptionConfig(config) => e.set_subscription_config(&config),
+                Record::ReadPricing(pricing) => e.set_read_pricing(&Some(pricing)),
+                Record::ReadRevenue(revenue) => {
+                    for (token, amount) in revenue.iter() {
+                        Self::__pull_import_funds(e, &state.importer, &token, amount);
+                    }
+                    e.set_read_revenue(&revenue);
+                }
+                Record::GlobalStats(stats) => e.set_global_stats(&stats),
+                Record::Org(org) => {
+                    if let Some(budget_token) = org.budget_token.as_ref() {
+                        Self::__pull_import_funds(e, &state.importer, budget_token, org.budget);
+                    }
+                    let mut orgs = e.get_orgs();
+                    if !orgs.contains(org.name.clone()) {
+                        orgs.push_back(org.name.clone());
+                        e.set_orgs(&orgs);
+                    }
+                    for repo in org.repos.iter() {
+                        e.set_repo_org(&repo, &org.name);
+                    }
+                    e.set_org(&org);
+                }
+                Record::Repo(repo, issues) => {
+                    let mut repos = e.get_repos();
+                    repos.set(repo.clone(), issues);
+                    e.set_repos(repos);
+                    committed.push_back(repo);
+                }
+                Record::Claimant(repo, issue, claimant) => {
+                    //the stats records already carry the claimant counts
+                    e.set_issue_claimant(&repo, &issue, &claimant);
+                    Self::__add_claim(e, &repo, &claimant);
+                    committed.push_back(repo);
+                }
+                Record::RepoState(repo, repo_state) => e.set_repo_state(&repo, repo_state),
+                Record::RepoMaintainer(repo, maintainer) => {
+                    e.set_repo_maintainer(&repo, &maintainer)
+                }
+                Record::RepoIssueLimit(repo, limit) => e.set_repo_issue_limit(&repo, limit),
+                Record::RepoStats(repo, stats) => e.set_repo_stats(&repo, &stats),
+                Record::Escrow(repo, issue, escrow) => {
+                    Self::__pull_import_funds(e, &state.importer, &escrow.token, escrow.amount);
+                    e.set_escrow(&repo, &issue, &escrow);
+                }
+                Record::Labels(repo, issue, labels) => {
+                    let issue_ref = IssueRef {
+                        repo: repo.clone(),
+                        issue: issue.clone(),
+                    };
+                    for label in labels.iter() {
+                        Self::__index_label(e, &label, &issue_ref);
+                    }
+                    e.set_issue_labels(&repo, &issue, &labels);
+                }
+                Record::Blockers(issue_ref, blockers) => {
+                    for blocker in blockers.iter() {
+                        let mut dependents = e.get_issue_dependents(&blocker);
+                        dependents.push_back(issue_ref.clone());
+                        e.set_issue_dependents(&blocker, &dependents);
+                    }
+                    e.set_issue_blockers(&issue_ref, &blockers);
+                }
+                Record::Subscription(subscription) => {
+                    let mut subscription_ids = e.get_repo_subscriptions(&subscription.repo);
+                    subscription_ids.push_back(subscription.id);
+                    e.set_repo_subscriptions(&subscription.repo, &subscription_ids);
+                    if subscription.id >= e.get_subscription_count() {
+                        e.set_subscription_count(subscription.id + 1);
+                    }
+                    e.set_subscription(&subscription);
+                }
+                Record::Vesting(repo, issue, vesting) => {
+                    for (_, share) in vesting.shares.iter() {
+                        let unwithdrawn = share.total - share.withdrawn;
+                        Self::__pull_import_funds(e, &state.importer, &vesting.token, unwithdrawn);
+                    }
+                    e.set_vesting(&repo, &issue, &vesting);
+                }
+                Record::VestingSchedule(repo, issue, schedule) => {
+                    e.set_vesting_schedule(&repo, &issue, &schedule)
+                }
+                Record::Grant(grant_id, grant) => {
+                    if !grant.cancelled {
+                        for milestone in grant
+                            .milestones
+                            .iter()
+                            .skip(grant.released_milestones as usize)
+                        {
+                            Self::__pull_import_funds(
+                                e,
+                                &state.importer,
+                                &grant.token,
+                                milestone.amount,
+                            );
+                        }
+                    }
+                    Self::__index_grant_issues(e, grant_id, &grant.milestones);
+                    if grant_id >= e.get_grant_count() {
+                        e.set_grant_count(grant_id + 1);
+                    }
+                    e.set_grant(grant_id, &grant);
+                }
+            }
+        }
+
+        //the state root is rebuilt once the chunk's issues and claimants are all in
+        Self::__commit_repos(e, &committed);
+
+        state.checksum = chunk.checksum;
+        state.state_hash = chunk.state_hash;
+        state.complete = chunk.next_cursor.is_none();
+        state.chunks += 1;
+        e.set_import_state(&state);
+    }
+
+    //an imported balance is only backed once the importer has sent the tokens for it
+    fn __pull_import_funds(e: &Env, importer: &Address, token: &Address, amount: i128) {
+        if amount > 0 {
+            token::Client::new(e, token).transfer(importer, &e.current_contract_address(), &amount);
+        }
+    }
+
+    fn __finish_import(e: &Env, state: ImportState) {
+        if !state.complete {
+            panic_with_error!(&e, ExtendedError::ImportInProgress);
+        }
+        //the restored registry has to hash to the snapshot it was exported from
+        if state.state_hash != Self::__state_hash(e, &e.get_repos()) {
+            panic_with_error!(&e, Error::StateHashMismatch);
+        }
+
+        let mut admin = state.importer.clone();
+        for record in state.deferred_roles.iter() {
+            match record {
+                Record::Admin(imported_admin) => admin = imported_admin,
+                Record::Signers(signer_set) => e.set_signer_set(&signer_set),
+                Record::Attestor(attestor) => e.set_attestor(&attestor),
+                Record::Upgrader(upgrader) => e.set_upgrader(&Some(upgrader)),
+                Record::Voters(voters) => e.set_voters(&voters),
+                _ => {}
+            }
+        }
+
+        //setting the admin is what opens the contract for writes
+        e.set_admin(&admin);
+        e.remove_import_state();
+        e.events().publish(
+            (symbol_short!("imported"), state.chunks),
+            Self::__state_hash(e, &e.get_repos()),
+        );
+    }
+
+    fn __request_clear(e: &Env, state_hash: BytesN<32>) {
+        if state_hash != Self::__state_hash(e, &e.get_repos()) {
+            panic_with_error!(&e, Error::StateHashMismatch);
+        }
+
+        e.set_clear_request(&ClearRequest {
+            state_hash,
+            executable_at_ledger: e.ledger().sequence() + CLEAR_DELAY_LEDGERS,
+        });
+        //keep the contract alive until the clear can be confirmed
+        e.extend_instance_ttl(2 * CLEAR_DELAY_LEDGERS);
+    }
+
+    fn __confirm_clear(e: &Env, state_hash: BytesN<32>) -> u32 {
+        let request = e.get_clear_request();
+        if request.is_none() {
+            panic_with_error!(&e, Error::ClearRequestMissing);
+        }
+
+        let request = request.unwrap();
+        if e.ledger().sequence() < request.executable_at_ledger {
+            panic_with_error!(&e, Error::ClearDelayPending);
+        }
+
+        //the registry must not have changed since the clear was requested
+        let repos = e.get_repos();
+        let current_state_hash = Self::__state_hash(e, &repos);
+        if state_hash != request.state_hash || state_hash != current_state_hash {
+            panic_with_error!(&e, Error::StateHashMismatch);
+        }
+
+        for (repo, issues) in repos.iter() {
+            for issue in issues.keys().iter() {
+                Self::__panic_if_funds_held(e, &repo, &issue);
+            }
+        }
+
+        for (repo, issues) in repos.iter() {
+            for issue in issues.keys().iter() {
+                Self::__forget_issue(e, &repo, &issue);
+            }
+            Self::__drop_repo_stats(e, &repo);
+            Self::__forget_repo(e, &repo);
+        }
+
+        let archive_id = e.get_archive_count();
+        e.set_archive_count(archive_id + 1);
+        e.set_archive(
+            archive_id,
+            &RegistryArchive {
+                repos,
+                state_hash,
+                archived_at_ledger: e.ledger().sequence(),
+            },
+        );
+
+        e.remove_clear_request();
+        e.set_repos(Map::new(e));
+        Self::__commit_repos(e, &e.get_repo_roots().keys());
+        e.events()
+            .publish((symbol_short!("cleared"), archive_id), current_state_hash);
+
+        archive_id
+    }
+
+    fn __request_repo_verification(
+        e: &Env,
+        requester: Address,
+        forge: Forge,
+        repo_name: String,
+    ) -> u64 {
+        Self::__panic_if_repo_voting(e);
+        let repo = Self::__repo_key(e, forge, &repo_name);
+        if e.get_repos().contains_key(repo.clone()) {
+            panic_with_error!(&e, Error::RepoAlreadyExists);
+        }
+
+        //every requester gets a challenge of their own, so nobody can hold a
+        //repo's name against others by renewing theirs
+        let nonce: u64 = e.prng().gen();
+        e.set_repo_challenge(
+            &repo,
+            &RepoChallenge {
+                requester,
+                nonce,
+                expires_at_ledger: e.ledger().sequence() + CHALLENGE_EXPIRY_LEDGERS,
+            },
+        );
+        //keep the contract alive while the attestor checks the repo
+        e.extend_instance_ttl(2 * CHALLENGE_EXPIRY_LEDGERS);
+
+        nonce
+    }
+
+    fn __confirm_repo_verification(e: &Env, repo: String, requester: Address, nonce: u64) {
+        let challenge = e.get_repo_challenge(&repo, &requester);
+        if challenge.is_none() {
+            panic_with_error!(&e, Error::ChallengeMissing);
+        }
+
+        let challenge = challenge.unwrap();
+        if e.ledger().sequence() > challenge.expires_at_ledger {
+            panic_with_error!(&e, Error::ChallengeMissing);
+        }
+        if challenge.nonce != nonce {
+            panic_with_error!(&e, Error::NonceMismatch);
+        }
+
+        Self::__add_repos(e, Vec::from_array(e, [repo.clone()]));
+        e.set_repo_maintainer(&repo, &challenge.requester);
+        e.remove_repo_challenge(&repo, &requester);
+        e.events()
+            .publish((symbol_short!("verified"), repo), challenge.requester);
+    }
+
+    //no forge allows `:` in a repo path, so a name can never pass for another forge's key
+    fn __repo_key(e: &Env, forge: Forge, name: &String) -> String {
+        let prefix = forge.key_prefix().as_bytes();
+        let key_len = prefix.len() + name.len() as usize;
+        if name.is_empty() || key_len > MAX_REPO_KEY_LEN {
+            panic_with_error!(&e, ExtendedError::InvalidRepoName);
+        }
+
+        let mut key = [0u8; MAX_REPO_KEY_LEN];
+        key[..prefix.len()].copy_from_slice(prefix);
+        name.copy_into_slice(&mut key[prefix.len()..key_len]);
+        if key[prefix.len()..key_len].contains(&b':') {
+            panic_with_error!(&e, ExtendedError::InvalidRepoName);
+        }
+        String::from_bytes(e, &key[..key_len])
+    }
+
+    fn __repo_keys(e: &Env, forge: Forge, names: &Vec<String>) -> Vec<String> {
+        let mut keys = Vec::new(e);
+        for name in names.iter() {
+            keys.push_back(Self::__repo_key(e, forge, &name));
+        }
+
+        keys
+    }
+
+    //config takes registry keys, so each one is split and rebuilt to check its name
+    fn __checked_repo_keys(e: &Env, keys: &Vec<String>) -> Vec<String> {
+        let mut checked = Vec::new(e);
+        for key in keys.iter() {
+            let key_len = key.len() as usize;
+            if key_len > MAX_REPO_KEY_LEN {
+                panic_with_error!(&e, ExtendedError::InvalidRepoName);
+            }
+
+            let forge = Self::__repo_forge(e, &key);
+            let prefix_len = forge.key_prefix().len();
+            let mut bytes = [0u8; MAX_REPO_KEY_LEN];
+            key.copy_into_slice(&mut bytes[..key_len]);
+            let name = String::from_bytes(e, &bytes[prefix_len..key_len]);
+            checked.push_back(Self::__repo_key(e, forge, &name));
+        }
+
+        checked
+    }
+
+    fn __repo_forge(_e: &Env, repo: &String) -> Forge {
+        //keys are capped when added, a longer one predates forges and is a GitHub repo
+        let key_len = repo.len() as usize;
+        if key_len > MAX_REPO_KEY_LEN {
+            return Forge::GitHub;
+        }
+
+        let mut key = [0u8; MAX_REPO_KEY_LEN];
+        repo.copy_into_slice(&mut key[..key_len]);
+        for forge in [Forge::GitLab, Forge::Gitea] {
+            if key[..key_len].starts_with(forge.key_prefix().as_bytes()) {
+                return forge;
+            }
+        }
+
+        Forge::GitHub
+    }
+
+    fn __set_org_maintainers(e: &Env, org_name: String, maintainers: Vec<Address>) {
+        let mut org = Self::__get_org(e, &org_name);
+        org.maintainers = maintainers;
+        e.set_org(&org);
+    }
+
+    fn __get_org(e: &Env, org: &String) -> Org {
+        let org = e.get_org(org);
+        if org.is_none() {
+            panic_with_error!(&e, ExtendedError::OrgMissing);
+        }
+
+        org.unwrap()
+    }
+
+    fn __panic_if_not_org_manager(e: &Env, caller: &Address, org: &Org) {
+        e.panic_if_importing();
+        caller.require_auth();
+        if Some(caller.clone()) != e.get_admin() && !org.maintainers.contains(caller.clone()) {
+            panic_with_error!(&e, Error::Unauthorized);
+        }
+    }
+
+    fn __add_org_repos(e: &Env, org_name: String, repos: Vec<String>) {
+        let mut org = Self::__get_org(e, &org_name);
+        let current_repos = e.get_repos();
+        for repo in repos.iter() {
+            let repo = Self::__resolve_repo(e, repo);
+            if !current_repos.contains_key(repo.clone()) {
+                panic_with_error!(&e, Error::RepoMissing);
+            }
+            //a repo belongs to at most one org
+            if e.get_repo_org(&repo).is_some() {
+                panic_with_error!(&e, Error::RepoAlreadyExists);
+            }
+
+            e.set_repo_org(&repo, &org_name);
+            org.repos.push_back(repo);
+        }
+        e.set_org(&org);
+    }
+
+    fn __remove_repo_from_org(e: &Env, repo: &String) {
+        if let Some(org_name) = e.get_repo_org(repo) {
+            let mut org = e.get_org(&org_name).unwrap();
+            if let Some(index) = org.repos.first_index_of(repo.clone()) {
+                org.repos.remove(index);
+            }
+            e.set_org(&org);
+            e.remove_repo_org(repo);
+        }
+    }
+
+    fn __set_repo_state(e: &Env, repo: &String, state: RepoState) {
+        Self::__get_repo_issues(e, &e.get_repos(), repo);
+        e.set_repo_state(repo, state);
+        e.events()
+            .publish((symbol_short!("repo_st"), repo.clone()), state);
+    }
+
+    fn __require_repo_active(e: &Env, repo: &String) {
+        e.panic_if_importing();
+        if e.get_repo_state(repo) != RepoState::Active {
+            panic_with_error!(&e, ExtendedError::RepoInactive);
+        }
+    }
+
+    fn __rename_repo(e: &Env, old: String, new: String) {
+        let mut current_repos = e.get_repos();
+        let issues = Self::__get_repo_issues(e, &current_repos, &old);
+        //a repo is renamed on its own forge, so the new name is keyed like the old one
+        let new = Self::__repo_key(e, Self::__repo_forge(e, &old), &new);
+        if current_repos.contains_key(new.clone()) {
+            panic_with_error!(&e, Error::RepoAlreadyExists);
+        }
+
+        //the new name may only be taken by an alias that already leads back to the old one
+        if e.get_repo_alias(&new).is_some() {
+            if Self::__resolve_repo(e, new.clone()) != old {
+                panic_with_error!(&e, Error::RepoAlreadyExists);
+            }
+            e.remove_repo_alias(&new);
+        }
+
+        current_repos.remove(old.clone());
+        current_repos.set(new.clone(), issues.clone());
+        e.set_repos(current_repos);
+
+        if let Some(limit) = e.get_repo_issue_limit(&old) {
+            e.set_repo_issue_limit(&new, limit);
+        }
+        if let Some(maintainer) = e.get_repo_maintainer(&old) {
+            e.set_repo_maintainer(&new, &maintainer);
+        }
+        e.set_repo_stats(&new, &e.get_repo_stats(&old));
+        let claims = e.get_repo_claims(&old);
+        if !claims.is_empty() {
+            e.set_repo_claims(&new, &claims);
+        }
+        let state = e.get_repo_state(&old);
+        if state != RepoState::Active {
+            e.set_repo_state(&new, state);
+        }
+        if let Some(org_name) = e.get_repo_org(&old) {
+            let mut org = e.get_org(&org_name).unwrap();
+            if let Some(index) = org.repos.first_index_of(old.clone()) {
+                org.repos.set(index, new.clone());
+            }
+            e.set_org(&org);
+            e.set_repo_org(&new, &org_name);
+        }
+        let subscription_ids = e.get_repo_subscriptions(&old);
+        for subscription_id in subscription_ids.iter() {
+            let mut subscription = e.get_subscription(subscription_id).unwrap();
+            subscription.repo = new.clone();
+            e.set_subscription(&subscription);
+        }
+        e.set_repo_subscriptions(&new, &subscription_ids);
+        e.set_repo_subscriptions(&old, &Vec::new(e));
+        Self::__forget_repo(e, &old);
+
+        let mut renamed_grants: Vec<u32> = Vec::new(e);
+        for issue in issues.keys().iter() {
+            if let Some(escrow) = e.get_escrow(&old, &issue) {
+                e.set_escrow(&new, &issue, &escrow);
+                e.remove_escrow(&old, &issue);
+            }
+            if let Some(claimant) = e.get_issue_claimant(&old, &issue) {
+                e.set_issue_claimant(&new, &issue, &claimant);
+                e.remove_issue_claimant(&old, &issue);
+            }
+            if let Some(schedule) = e.get_vesting_schedule(&old, &issue) {
+                e.set_vesting_schedule(&new, &issue, &schedule);
+                e.remove_vesting_schedule(&old, &issue);
+            }
+            if let Some(vesting) = e.get_vesting(&old, &issue) {
+                e.set_vesting(&new, &issue, &vesting);
+                e.remove_vesting(&old, &issue);
+            }
+
+            let labels = e.get_issue_labels(&old, &issue);
+            if !labels.is_empty() {
+                let old_ref = IssueRef {
+                    repo: old.clone(),
+                    issue: issue.clone(),
+                };
+                let new_ref = IssueRef {
+                    repo: new.clone(),
+                    issue: issue.clone(),
+                };
+                for label in labels.iter() {
+                    Self::__unindex_label(e, &label, &old_ref);
+                    Self::__index_label(e, &label, &new_ref);
+                }
+                e.set_issue_labels(&new, &issue, &labels);
+                e.remove_issue_labels(&old, &issue);
+            }
+            Self::__rename_issue_dependencies(e, &old, &new, &issue);
+
+            let grant_ids = e.get_issue_grants(&old, &issue);
+            if grant_ids.is_empty() {
+                continue;
+            }
+            for grant_id in grant_ids.iter() {
+                if !renamed_grants.contains(grant_id) {
+                    Self::__rename_grant_repo(e, grant_id, &old, &new);
+                    renamed_grants.push_back(grant_id);
+                }
+            }
+            e.set_issue_grants(&new, &issue, &grant_ids);
+            e.remove_issue_grants(&old, &issue);
+        }
+
+        for proposal_id in e.get_pending_issue_proposals().iter() {
+            let mut proposal = e.get_issue_proposal(proposal_id).unwrap();
+            if proposal.repo == old {
+                proposal.repo = new.clone();
+                e.set_issue_proposal(&proposal);
+            }
+        }
+
+        Self::__commit_repos(e, &Vec::from_array(e, [old.clone(), new.clone()]));
+        e.set_repo_alias(&old, &new);
+        e.events().publish((symbol_short!("renamed"), old), new);
+    }
+
+    fn __rename_grant_repo(e: &Env, grant_id: u32, old: &String, new: &String) {
+        let mut grant = Self::__get_grant(e, grant_id);
+        for (index, mut milestone) in grant.milestones.clone().iter().enumerate() {
+            milestone.issues = Self::__rename_issue_refs(e, &milestone.issues, old, new);
+            grant.milestones.set(index as u32, milestone);
+        }
+        e.set_grant(grant_id, &grant);
+    }
+
+    fn __rename_issue_dependencies(e: &Env, old: &String, new: &String, issue: &String) {
+        let old_ref = IssueRef {
+            repo: old.clone(),
+            issue: issue.clone(),
+        };
+        let new_ref = IssueRef {
+            repo: new.clone(),
+            issue: issue.clone(),
+        };
+        let blockers = e.get_issue_blockers(&old_ref);
+        let dependents = e.get_issue_dependents(&old_ref);
+
+        //issues of the renamed repo itself are moved by their own iteration
+        for blocker in blockers.iter() {
+            if blocker.repo != *old {
+                let blocker_dependents = e.get_issue_dependents(&blocker);
+                e.set_issue_dependents(
+                    &blocker,
+                    &Self::__rename_issue_refs(e, &blocker_dependents, old, new),
+                );
+            }
+        }
+        for dependent in dependents.iter() {
+            if dependent.repo != *old {
+                let dependent_blockers = e.get_issue_blockers(&dependent);
+                e.set_issue_blockers(
+                    &dependent,
+                    &Self::__rename_issue_refs(e, &dependent_blockers, old, new),
+                );
+            }
+        }
+
+        e.set_issue_blockers(&new_ref, &Self::__rename_issue_refs(e, &blockers, old, new));
+        e.set_issue_dependents(
+            &new_ref,
+            &Self::__rename_issue_refs(e, &dependents, old, new),
+        );
+        e.set_issue_blockers(&old_ref, &Vec::new(e));
+        e.set_issue_dependents(&old_ref, &Vec::new(e));
+    }
+
+    fn __rename_issue_refs(
+        e: &Env,
+        issue_refs: &Vec<IssueRef>,
+        old: &String,
+        new: &String,
+    ) -> Vec<IssueRef> {
+        let mut renamed = Vec::new(e);
+        for mut issue_ref in issue_refs.iter() {
+            if issue_ref.repo == *old {
+                issue_ref.repo = new.clone();
+            }
+            renamed.push_back(issue_ref);
+        }
+
+        renamed
+    }
+
+    fn __resolve_repo(e: &Env, repo: String) -> String {
+        //aliases always point towards a newer name, so the chain cannot loop
+        let mut resolved = repo.clone();
+        while let Some(target) = e.get_repo_alias(&resolved) {
+            resolved = target;
+        }
+
+        if resolved != repo {
+            e.events()
+                .publish((Symbol::new(e, "deprecated"), repo), resolved.clone());
+        }
+
+        resolved
+    }
+
+    fn __resolve_issue_ref(e: &Env, mut issue_ref: IssueRef) -> IssueRef {
+        issue_ref.repo = Self::__resolve_repo(e, issue_ref.repo);
+        issue_ref
+    }
+
+    fn __resolve_issue_refs(e: &Env, issue_refs: &Vec<IssueRef>) -> Vec<IssueRef> {
+        let mut resolved = Vec::new(e);
+        for issue_ref in issue_refs.iter() {
+            resolved.push_back(Self::__resolve_issue_ref(e, issue_ref));
+        }
+
+        resolved
+    }
+
+    fn __panic_if_not_repo_manager(e: &Env, caller: &Address, repo: &String) {
+        e.panic_if_importing();
+        caller.require_auth();
+        if Some(caller.clone()) == e.get_admin()
+            || Some(caller.clone()) == e.get_repo_maintainer(repo)
+        {
+            return;
+        }
+        if let Some(org_name) = e.get_repo_org(repo) {
+            if e.get_org(&org_name)
+                .unwrap()
+                .maintainers
+                .contains(caller.clone())
+            {
+                return;
+            }
+        }
+
+        panic_with_error!(&e, Error::Unauthorized);
+    }
+
+    fn __set_issue_labels(e: &Env, repo: String, issue: String, labels: Vec<String>) {
+        Self::__get_issue_status(e, &repo, &issue);
+        if labels.len() > MAX_LABELS_PER_ISSUE {
+            panic_with_error!(&e, ExtendedError::InvalidLabels);
+        }
+
+        let mut new_labels: Vec<String> = Vec::new(e);
+        for label in labels.iter() {
+            if label.is_empty() {
+                panic_with_error!(&e, ExtendedError::InvalidLabels);
+            }
+            if !new_labels.contains(label.clone()) {
+                new_labels.push_back(label);
+            }
+        }
+
+        let issue_ref = IssueRef {
+            repo: repo.clone(),
+            issue: issue.clone(),
+        };
+        let old_labels = e.get_issue_labels(&repo, &issue);
+        for label in old_labels.iter() {
+            if !new_labels.contains(label.clone()) {
+                Self::__unindex_label(e, &label, &issue_ref);
+            }
+        }
+        for label in new_labels.iter() {
+            if !old_labels.contains(label.clone()) {
+                Self::__index_label(e, &label, &issue_ref);
+            }
+        }
+
+        e.set_issue_labels(&repo, &issue, &new_labels);
+        e.events()
+            .publish((symbol_short!("labels"), repo, issue), new_labels);
+    }
+
+    fn __add_issue_dependency(e: &Env, issue: IssueRef, blocker: IssueRef) {
+        Self::__get_issue_status(e, &issue.repo, &issue.issue);
+        Self::__get_issue_status(e, &blocker.repo, &blocker.issue);
+
+        let mut blockers = e.get_issue_blockers(&issue);
+        if blockers.contains(blocker.clone()) {
+            return;
+        }
+        //the new edge closes a cycle if the blocker already waits on the issue
+        if Self::__depends_on(e, &blocker, &issue) {
+            panic_with_error!(&e, ExtendedError::DependencyCycle);
+        }
+
+        blockers.push_back(blocker.clone());
+        e.set_issue_blockers(&issue, &blockers);
+        let mut dependents = e.get_issue_dependents(&blocker);
+        dependents.push_back(issue.clone());
+        e.set_issue_dependents(&blocker, &dependents);
+    }
+
+    fn __depends_on(e: &Env, issue: &IssueRef, target: &IssueRef) -> bool {
+        let mut pending = Vec::from_array(e, [issue.clone()]);
+        let mut visited: Vec<IssueRef> = Vec::new(e);
+        while let Some(current) = pending.pop_back() {
+            if current == *target {
+                return true;
+            }
+            if visited.contains(current.clone()) {
+                continue;
+            }
+
+            pending.append(&e.get_issue_blockers(&current));
+            visited.push_back(current);
+        }
+
+        false
+    }
+
+    fn __unlink_issues(e: &Env, issue: &IssueRef, blocker: &IssueRef) {
+        let mut blockers = e.get_issue_blockers(issue);
+        if let Some(position) = blockers.first_index_of(blocker.clone()) {
+            blockers.remove(position);
+            e.set_issue_blockers(issue, &blockers);
+        }
+
+        let mut dependents = e.get_issue_dependents(blocker);
+        if let Some(position) = dependents.first_index_of(issue.clone()) {
+            dependents.remove(position);
+            e.set_issue_dependents(blocker, &dependents);
+        }
+    }
+
+    //escrow, unwithdrawn vesting and unreleased grant tranches would be stranded
+    //by removing the issue, so they have to be refunded, withdrawn or cancelled first
+    fn __panic_if_funds_held(e: &Env, repo: &String, issue: &String) {
+        if e.get_escrow(repo, issue).is_some() {
+            panic_with_error!(&e, Error::FundsEscrowed);
+        }
+        if let Some(vesting) = e.get_vesting(repo, issue) {
+            for (_, share) in vesting.shares.iter() {
+                if share.total > share.withdrawn {
+                    panic_with_error!(&e, Error::FundsEscrowed);
+                }
+            }
+        }
+
+        let issue_ref = IssueRef {
+            repo: repo.clone(),
+            issue: issue.clone(),
+        };
+        for grant_id in e.get_issue_grants(repo, issue).iter() {
+            let grant = Self::__get_grant(e, grant_id);
+            if grant.cancelled {
+                continue;
+            }
+            for milestone in grant
+                .milestones
+                .iter()
+                .skip(grant.released_milestones as usize)
+            {
+                if milestone.issues.contains(issue_ref.clone()) {
+                    panic_with_error!(&e, Error::FundsEscrowed);
+                }
+            }
+        }
+    }
+
+    fn __forget_issue(e: &Env, repo: &String, issue: &String) {
+        Self::__remove_issue_labels(e, repo, issue);
+        e.remove_vesting(repo, issue);
+        e.remove_vesting_schedule(repo, issue);
+        e.remove_issue_grants(repo, issue);
+        if let Some(claimant) = e.get_issue_claimant(repo, issue) {
+            e.remove_issue_claimant(repo, issue);
+            Self::__uncount_claimant(e, repo, &claimant);
+        }
+        for subscription_id in e.get_repo_subscriptions(repo).iter() {
+            let subscription = e.get_subscription(subscription_id).unwrap();
+            if subscription.issue.as_ref() == Some(issue) {
+                Self::__drop_subscription(e, &subscription);
+            }
+        }
+
+        let issue_ref = IssueRef {
+            repo: repo.clone(),
+            issue: issue.clone(),
+        };
+        for blocker in e.get_issue_blockers(&issue_ref).iter() {
+            Self::__unlink_issues(e, &issue_ref, &blocker);
+        }
+        for dependent in e.get_issue_dependents(&issue_ref).iter() {
+            Self::__unlink_issues(e, &dependent, &issue_ref);
+        }
+    }
+
+    fn __remove_issue_labels(e: &Env, repo: &String, issue: &String) {
+        let issue_ref = IssueRef {
+            repo: repo.clone(),
+            issue: issue.clone(),
+        };
+        for label in e.get_issue_labels(repo, issue).iter() {
+            Self::__unindex_label(e, &label, &issue_ref);
+        }
+        e.remove_issue_labels(repo, issue);
+    }
+
+    //label indexes are kept sorted so `issues_by_label` can resume from an issue ref
+    fn __index_label(e: &Env, label: &String, issue_ref: &IssueRef) {
+        let mut index = e.get_label_index(label);
+        if let Err(position) = index.binary_search(issue_ref) {
+            index.insert(position, issue_ref.clone());
+            e.set_label_index(label, &index);
+        }
+    }
+
+    fn __unindex_label(e: &Env, label: &String, issue_ref: &IssueRef) {
+        let mut index = e.get_label_index(label);
+        if let Some(position) = index.first_index_of(issue_ref.clone()) {
+            index.remove(position);
+            e.set_label_index(label, &index);
+        }
+    }
+
+    fn __update_stats(e: &Env, repo: &String, update: impl Fn(&mut Stats)) {
+        let mut repo_stats = e.get_repo_stats(repo);
+        update(&mut repo_stats);
+        e.set_repo_stats(repo, &repo_stats);
+        Self::__update_global_stats(e, update);
+    }
+
+    fn __update_global_stats(e: &Env, update: impl Fn(&mut Stats)) {
+        let mut global_stats = e.get_global_stats();
+        update(&mut global_stats);
+        e.set_global_stats(&global_stats);
+    }
+
+    fn __count_claimant(e: &Env, repo: &String, claimant: &Address) {
+        let (first_in_repo, first_overall) = Self::__add_claim(e, repo, claimant);
+        if first_in_repo {
+            let mut repo_stats = e.get_repo_stats(repo);
+            repo_stats.active_claimants += 1;
+            e.set_repo_stats(repo, &repo_stats);
+        }
+        if first_overall {
+            Self::__update_global_stats(e, |stats| stats.active_claimants += 1);
+        }
+    }
+
+    fn __uncount_claimant(e: &Env, repo: &String, claimant: &Address) {
+        let (last_in_repo, last_overall) = Self::__remove_claim(e, repo, claimant);
+        if last_in_repo {
+            let mut repo_stats = e.get_repo_stats(repo);
+            repo_stats.active_claimants = repo_stats.active_claimants.saturating_sub(1);
+            e.set_repo_stats(repo, &repo_stats);
+        }
+        if last_overall {
+            Self::__update_global_stats(e, |stats| {
+                stats.active_claimants = stats.active_claimants.saturating_sub(1)
+            });
+        }
+    }
+
+    //a claimant stays active while they hold the claim on at least one tracked
+    //issue, so their claims are counted per repo and overall
+    fn __add_claim(e: &Env, repo: &String, claimant: &Address) -> (bool, bool) {
+        let mut repo_claims = e.get_repo_claims(repo);
+        let repo_count = repo_claims.get(claimant.clone()).unwrap_or(0);
+        repo_claims.set(claimant.clone(), repo_count + 1);
+        e.set_repo_claims(repo, &repo_claims);
+
+        let count = e.get_claims(claimant);
+        e.set_claims(claimant, count + 1);
+        (repo_count == 0, count == 0)
+    }
+
+    fn __remove_claim(e: &Env, repo: &String, claimant: &Address) -> (bool, bool) {
+        let mut repo_claims = e.get_repo_claims(repo);
+        let repo_count = repo_claims.get(claimant.clone()).unwrap_or(0);
+        if repo_count > 1 {
+            repo_claims.set(claimant.clone(), repo_count - 1);
+        } else {
+            repo_claims.remove(claimant.clone());
+        }
+        if repo_claims.is_empty() {
+            e.remove_repo_claims(repo);
+        } else {
+            e.set_repo_claims(repo, &repo_claims);
+        }
+
+        let count = e.get_claims(claimant);
+        e.set_claims(claimant, count.saturating_sub(1));
+        (repo_count == 1, count == 1)
+    }
+
+    fn __drop_repo_stats(e: &Env, repo: &String) {
+        //payout totals are lifetime figures and survive the repo, claimants were
+        //already dropped along with the repo's issues
+        let repo_stats = e.get_repo_stats(repo);
+        let mut global_stats = e.get_global_stats();
+        global_stats.unclaimed_issues = global_stats
+            .unclaimed_issues
+            .saturating_sub(repo_stats.unclaimed_issues);
+        global_stats.completed_issues = global_stats
+            .completed_issues
+            .saturating_sub(repo_stats.completed_issues);
+        for (token, amount) in repo_stats.escrowed.iter() {
+            global_stats.add_escrowed(&token, -amount);
+        }
+        e.set_global_stats(&global_stats);
+    }
+
+    fn __forget_repo(e: &Env, repo: &String) {
+        e.remove_repo_stats(repo);
+        e.remove_repo_claims(repo);
+        e.remove_repo_issue_limit(repo);
+        e.remove_repo_maintainer(repo);
+        e.remove_repo_state(repo);
+        Self::__remove_repo_from_org(e, repo);
+        for subscription_id in e.get_repo_subscriptions(repo).iter() {
+            e.remove_subscription(subscription_id);
+        }
+        e.set_repo_subscriptions(repo, &Vec::new(e));
     }
 }
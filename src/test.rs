@@ -1,11 +1,32 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, vec, Address, Env, String, Vec};
+use crate::types::admin_action::AdminAction;
+use crate::types::forge::Forge;
+use crate::types::grant::Milestone;
+use crate::types::issue_proposal::ProposalDepositConfig;
+use crate::types::issue_ref::IssueRef;
+use crate::types::limits::Limits;
+use crate::types::merkle::IssueLeaf;
+use crate::types::read_pricing::ReadPricing;
+use crate::types::repo_governance::{RepoChange, RepoGovernance, VotingConfig};
+use crate::types::repo_state::RepoState;
+use crate::types::signer_set::SignerSet;
+use crate::types::subscription::SubscriptionConfig;
+use crate::types::vesting::VestingSchedule;
+use soroban_sdk::{
+    testutils::{Address as _, ContractFunctionSet, Ledger},
+    token, vec, Address, Bytes, BytesN, Env, String, Vec,
+};
 
 fn setup_contract<'a>() -> (Env, GithubOracleContractClient<'a>, ConfigData) {
-    let env = Env::default();
+    setup_contract_with_governance(Env::default(), RepoGovernance::Admin)
+}
 
+fn setup_contract_with_governance<'a>(
+    env: Env,
+    repo_governance: RepoGovernance,
+) -> (Env, GithubOracleContractClient<'a>, ConfigData) {
     let admin = Address::generate(&env);
 
     let contract_id = &Address::from_string(&String::from_str(
@@ -15,7 +36,7 @@ fn setup_contract<'a>() -> (Env, GithubOracleContractClient<'a>, ConfigData) {
 
     env.register_contract(contract_id, GithubOracleContract);
 
-    let client = GithubOracleContractClient::new(&env, &contract_id);
+    let client = GithubOracleContractClient::new(&env, contract_id);
 
     env.budget().reset_unlimited();
     env.mock_all_auths();
@@ -25,6 +46,9 @@ fn setup_contract<'a>() -> (Env, GithubOracleContractClient<'a>, ConfigData) {
     let config = ConfigData {
         admin: admin.clone(),
         repos: original_repos.clone(),
+        max_repos: 256,
+        max_issues_per_repo: 512,
+        repo_governance,
     };
 
     client.config(&config);
@@ -35,16 +59,16 @@ fn setup_contract<'a>() -> (Env, GithubOracleContractClient<'a>, ConfigData) {
     (env, client, config)
 }
 
-fn soroban_cli_repo<'a>(env: &Env) -> String {
-    String::from_str(&env, "sdf/soroban-cli")
+fn soroban_cli_repo(env: &Env) -> String {
+    String::from_str(env, "sdf/soroban-cli")
 }
 
-fn digicus_repo<'a>(env: &Env) -> String {
-    String::from_str(&env, "spaced-out-thoughts-dev-foundation/digicus")
+fn digicus_repo(env: &Env) -> String {
+    String::from_str(env, "spaced-out-thoughts-dev-foundation/digicus")
 }
 
-fn soroban_sdk_repo<'a>(env: &Env) -> String {
-    String::from_str(&env, "sdf/soroban-sdk")
+fn soroban_sdk_repo(env: &Env) -> String {
+    String::from_str(env, "sdf/soroban-sdk")
 }
 
 #[test]
@@ -151,7 +175,7 @@ fn adds_then_clears_repos() {
     let repos = client.get_repos();
     assert_eq!(repos, repos_to_add);
 
-    client.clear_repos();
+    clear_repos(&env, &client);
 
     let repos = client.get_repos();
     assert_eq!(repos, vec![&env]);
@@ -175,12 +199,62 @@ fn adds_then_clears_repo_with_issues() {
     let repos = client.get_repos();
     assert_eq!(repos, repos_to_add);
 
-    client.clear_repos();
+    clear_repos(&env, &client);
 
     let repos = client.get_repos();
     assert_eq!(repos, vec![&env]);
 }
 
+#[test]
+fn same_repo_name_can_be_tracked_on_several_forges() {
+    let (env, client, _config) = setup_contract();
+
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env)]);
+    client.add_forge_repos(&Forge::GitLab, &vec![&env, soroban_sdk_repo(&env)]);
+    client.add_forge_repos(&Forge::Gitea, &vec![&env, digicus_repo(&env)]);
+
+    let gitlab_key = client.repo_key(&Forge::GitLab, &soroban_sdk_repo(&env));
+    assert_eq!(gitlab_key, String::from_str(&env, "gitlab:sdf/soroban-sdk"));
+    assert_eq!(
+        client.repo_key(&Forge::GitHub, &soroban_sdk_repo(&env)),
+        soroban_sdk_repo(&env)
+    );
+
+    assert_eq!(
+        client.get_repos_by_forge(&Forge::GitHub),
+        vec![&env, soroban_sdk_repo(&env)]
+    );
+    assert_eq!(
+        client.get_repos_by_forge(&Forge::GitLab),
+        vec![&env, gitlab_key.clone()]
+    );
+    assert_eq!(client.get_repos_by_forge(&Forge::Gitea).len(), 1);
+    assert_eq!(client.get_repo_forge(&gitlab_key), Forge::GitLab);
+    assert_eq!(
+        client.get_repo_forge(&soroban_sdk_repo(&env)),
+        Forge::GitHub
+    );
+
+    client.add_issues(&gitlab_key, &vec![&env, String::from_str(&env, "42")]);
+    assert_eq!(client.get_issues_for_repo(&gitlab_key).len(), 1);
+    assert_eq!(client.get_issues_for_repo(&soroban_sdk_repo(&env)).len(), 0);
+}
+
+#[test]
+fn add_repos_rejects_forge_prefixed_and_overlong_names() {
+    let (env, client, _config) = setup_contract();
+
+    let prefixed = String::from_str(&env, "gitlab:sdf/soroban-sdk");
+    let result = client.try_add_repos(&vec![&env, prefixed]);
+    assert_eq!(result, Err(Ok(ExtendedError::InvalidRepoName.into())));
+
+    let overlong = String::from_bytes(&env, &[b'a'; 257]);
+    let result = client.try_add_repos(&vec![&env, overlong.clone()]);
+    assert_eq!(result, Err(Ok(ExtendedError::InvalidRepoName.into())));
+    assert_eq!(client.get_repo_forge(&overlong), Forge::GitHub);
+    assert_eq!(client.get_repos(), vec![&env]);
+}
+
 #[test]
 fn get_issues_empty() {
     let (env, client, _config) = setup_contract();
@@ -455,6 +529,2603 @@ fn remove_issues_that_do_not_exist() {
     client.remove_issues(&soroban_sdk_repo(&env), &issues_to_remove.clone());
 }
 
+#[test]
+fn funded_issue_is_removed_only_after_refund() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let issue = setup_issue(&env, &client);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &100);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &100,
+    );
+
+    let issues = vec![&env, issue.clone()];
+    assert_eq!(
+        client.try_remove_issues(&soroban_sdk_repo(&env), &issues),
+        Err(Ok(Error::FundsEscrowed.into()))
+    );
+    assert_eq!(
+        client.try_remove_repos(&vec![&env, soroban_sdk_repo(&env)]),
+        Err(Ok(Error::FundsEscrowed.into()))
+    );
+
+    assert_eq!(
+        client.refund_escrow(&soroban_sdk_repo(&env), &issue, &funder),
+        100
+    );
+    assert_eq!(token.balance(&funder), 100);
+    assert_eq!(client.get_escrow(&soroban_sdk_repo(&env), &issue), None);
+
+    client.remove_issues(&soroban_sdk_repo(&env), &issues);
+    assert!(client
+        .get_issues_for_repo(&soroban_sdk_repo(&env))
+        .is_empty());
+}
+
+#[test]
+fn issue_with_unreleased_grant_tranche_cannot_be_removed() {
+    let (env, client, config) = setup_contract();
+    let (_token, token_admin) = setup_token(&env, &config.admin);
+    let grant_data = setup_grant(&env, &client, &token_admin);
+    let grant_id = client.create_grant(&grant_data);
+
+    let issues = vec![&env, String::from_str(&env, "issue-3")];
+    assert_eq!(
+        client.try_remove_issues(&digicus_repo(&env), &issues),
+        Err(Ok(Error::FundsEscrowed.into()))
+    );
+
+    client.cancel_grant(&grant_id);
+    client.remove_issues(&digicus_repo(&env), &issues);
+}
+
+#[test]
+fn complete_issue_splits_escrow_between_contributors() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let issue = setup_issue(&env, &client);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &1_000);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &1_000,
+    );
+    assert_eq!(
+        client.get_escrow(&soroban_sdk_repo(&env), &issue),
+        Some(Escrow {
+            token: token.address.clone(),
+            amount: 1_000
+        })
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let shares = vec![
+        &env,
+        (alice.clone(), 5_000),
+        (bob.clone(), 3_000),
+        (carol.clone(), 2_000),
+    ];
+    client.complete_issue(&config.admin, &soroban_sdk_repo(&env), &issue, &shares);
+
+    assert_eq!(token.balance(&alice), 500);
+    assert_eq!(token.balance(&bob), 300);
+    assert_eq!(token.balance(&carol), 200);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(client.get_escrow(&soroban_sdk_repo(&env), &issue), None);
+    assert_eq!(
+        client
+            .get_issues_for_repo(&soroban_sdk_repo(&env))
+            .get(issue),
+        Some(String::from_str(&env, "completed"))
+    );
+}
+
+#[test]
+fn complete_issue_sends_rounding_dust_to_dust_recipient() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let issue = setup_issue(&env, &client);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &100);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &100,
+    );
+
+    let dust_recipient = Address::generate(&env);
+    client.set_dust_recipient(&dust_recipient);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let shares = vec![
+        &env,
+        (alice.clone(), 3_333),
+        (bob.clone(), 3_333),
+        (carol.clone(), 3_334),
+    ];
+    client.complete_issue(&config.admin, &soroban_sdk_repo(&env), &issue, &shares);
+
+    assert_eq!(token.balance(&alice), 33);
+    assert_eq!(token.balance(&bob), 33);
+    assert_eq!(token.balance(&carol), 33);
+    assert_eq!(token.balance(&dust_recipient), 1);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+#[should_panic]
+fn complete_issue_with_shares_not_totalling_100_percent() {
+    let (env, client, config) = setup_contract();
+    let issue = setup_issue(&env, &client);
+
+    let shares = vec![
+        &env,
+        (Address::generate(&env), 5_000),
+        (Address::generate(&env), 4_000),
+    ];
+    client.complete_issue(&config.admin, &soroban_sdk_repo(&env), &issue, &shares);
+}
+
+#[test]
+#[should_panic]
+fn complete_issue_twice() {
+    let (env, client, config) = setup_contract();
+    let issue = setup_issue(&env, &client);
+
+    let shares = vec![&env, (Address::generate(&env), 10_000)];
+    client.complete_issue(&config.admin, &soroban_sdk_repo(&env), &issue, &shares);
+    client.complete_issue(&config.admin, &soroban_sdk_repo(&env), &issue, &shares);
+}
+
+#[test]
+fn grant_releases_milestones_in_order_once_issues_complete() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let grant_data = setup_grant(&env, &client, &token_admin);
+    let grant_id = client.create_grant(&grant_data);
+    assert_eq!(token.balance(&client.address), 600);
+
+    let shares = vec![&env, (Address::generate(&env), 10_000)];
+
+    //completing the second milestone first does not release anything yet
+    client.complete_issue(
+        &config.admin,
+        &digicus_repo(&env),
+        &String::from_str(&env, "issue-3"),
+        &shares,
+    );
+    assert_eq!(token.balance(&grant_data.grantee), 0);
+
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-1"),
+        &shares,
+    );
+    assert_eq!(token.balance(&grant_data.grantee), 0);
+    assert_eq!(client.get_grant(&grant_id).released_milestones, 0);
+
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-2"),
+        &shares,
+    );
+    assert_eq!(token.balance(&grant_data.grantee), 600);
+    assert_eq!(client.get_grant(&grant_id).released_milestones, 2);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn grant_progress() {
+    let (env, client, config) = setup_contract();
+    let (_token, token_admin) = setup_token(&env, &config.admin);
+    let grant_data = setup_grant(&env, &client, &token_admin);
+    let grant_id = client.create_grant(&grant_data);
+
+    let shares = vec![&env, (Address::generate(&env), 10_000)];
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-1"),
+        &shares,
+    );
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-2"),
+        &shares,
+    );
+
+    assert_eq!(
+        client.get_grant_progress(&grant_id),
+        GrantProgress {
+            released_milestones: 1,
+            total_milestones: 2,
+            completed_issues: 2,
+            total_issues: 3,
+            released_amount: 200,
+            total_amount: 600,
+        }
+    );
+    assert_eq!(client.get_grant_count(), 1);
+}
+
+#[test]
+fn cancel_grant_refunds_unreleased_milestones() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let grant_data = setup_grant(&env, &client, &token_admin);
+    let grant_id = client.create_grant(&grant_data);
+
+    let shares = vec![&env, (Address::generate(&env), 10_000)];
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-1"),
+        &shares,
+    );
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-2"),
+        &shares,
+    );
+    client.cancel_grant(&grant_id);
+
+    assert_eq!(token.balance(&grant_data.grantee), 200);
+    assert_eq!(token.balance(&grant_data.funder), 400);
+    assert!(client.get_grant(&grant_id).cancelled);
+
+    //completing the remaining issue no longer releases anything
+    client.complete_issue(
+        &config.admin,
+        &digicus_repo(&env),
+        &String::from_str(&env, "issue-3"),
+        &shares,
+    );
+    assert_eq!(token.balance(&grant_data.grantee), 200);
+}
+
+#[test]
+#[should_panic]
+fn create_grant_with_untracked_issue() {
+    let (env, client, config) = setup_contract();
+    let (_token, token_admin) = setup_token(&env, &config.admin);
+    let mut grant_data = setup_grant(&env, &client, &token_admin);
+    grant_data.milestones.push_back(Milestone {
+        issues: vec![
+            &env,
+            IssueRef {
+                repo: digicus_repo(&env),
+                issue: String::from_str(&env, "issue-4"),
+            },
+        ],
+        amount: 100,
+    });
+
+    client.create_grant(&grant_data);
+}
+
+#[test]
+fn vested_payout_is_released_linearly() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let issue = setup_issue(&env, &client);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &1_000);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &1_000,
+    );
+    client.set_vesting_schedule(
+        &soroban_sdk_repo(&env),
+        &issue,
+        &VestingSchedule {
+            upfront_basis_points: 2_000,
+            duration_ledgers: 100,
+        },
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let shares = vec![&env, (alice.clone(), 5_000), (bob.clone(), 5_000)];
+    client.complete_issue(&config.admin, &soroban_sdk_repo(&env), &issue, &shares);
+    assert_eq!(token.balance(&alice), 100);
+    assert_eq!(token.balance(&bob), 100);
+
+    env.ledger().with_mut(|l| l.sequence_number += 50);
+    assert_eq!(
+        client.withdraw_vested(&alice, &soroban_sdk_repo(&env), &issue),
+        200
+    );
+    assert_eq!(token.balance(&alice), 300);
+
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    assert_eq!(
+        client.withdraw_vested(&alice, &soroban_sdk_repo(&env), &issue),
+        200
+    );
+    assert_eq!(
+        client.withdraw_vested(&bob, &soroban_sdk_repo(&env), &issue),
+        400
+    );
+    assert_eq!(token.balance(&alice), 500);
+    assert_eq!(token.balance(&bob), 500);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn clawback_returns_unvested_payout() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let issue = setup_issue(&env, &client);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &1_000);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &1_000,
+    );
+    client.set_vesting_schedule(
+        &soroban_sdk_repo(&env),
+        &issue,
+        &VestingSchedule {
+            upfront_basis_points: 0,
+            duration_ledgers: 100,
+        },
+    );
+
+    let alice = Address::generate(&env);
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &vec![&env, (alice.clone(), 10_000)],
+    );
+
+    env.ledger().with_mut(|l| l.sequence_number += 25);
+    assert_eq!(
+        client.clawback(&soroban_sdk_repo(&env), &issue, &funder),
+        750
+    );
+    assert_eq!(token.balance(&funder), 750);
+
+    //the part vested before the clawback can still be withdrawn
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    assert_eq!(
+        client.withdraw_vested(&alice, &soroban_sdk_repo(&env), &issue),
+        250
+    );
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+#[should_panic]
+fn withdraw_vested_before_anything_vested() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let issue = setup_issue(&env, &client);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &1_000);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &1_000,
+    );
+    client.set_vesting_schedule(
+        &soroban_sdk_repo(&env),
+        &issue,
+        &VestingSchedule {
+            upfront_basis_points: 0,
+            duration_ledgers: 100,
+        },
+    );
+
+    let alice = Address::generate(&env);
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &vec![&env, (alice.clone(), 10_000)],
+    );
+    client.withdraw_vested(&alice, &soroban_sdk_repo(&env), &issue);
+}
+
+#[test]
+fn approved_issue_proposal_adds_issue_and_refunds_deposit() {
+    let (env, client, config) = setup_contract();
+    let (token, proposer) = setup_issue_proposals(&env, &client, &config.admin);
+
+    let issue = String::from_str(&env, "issue-9");
+    let proposal_id = client.propose_issue(&proposer, &soroban_sdk_repo(&env), &issue);
+    assert_eq!(token.balance(&proposer), 0);
+    assert_eq!(client.get_issue_proposals(&0, &10).len(), 1);
+
+    let org_name = String::from_str(&env, "sdf");
+    let maintainer = Address::generate(&env);
+    client.create_org(&org_name, &vec![&env, maintainer.clone()]);
+    client.add_org_repos(&org_name, &vec![&env, soroban_sdk_repo(&env)]);
+    let result = client.try_approve_issue_proposal(&Address::generate(&env), &proposal_id);
+    assert_eq!(result, Err(Ok(Error::Unauthorized.into())));
+    client.approve_issue_proposal(&maintainer, &proposal_id);
+
+    assert_eq!(token.balance(&proposer), 50);
+    assert_eq!(
+        client
+            .get_issues_for_repo(&soroban_sdk_repo(&env))
+            .get(issue),
+        Some(String::from_str(&env, "unclaimed"))
+    );
+    assert_eq!(client.get_issue_proposals(&0, &10).len(), 0);
+}
+
+#[test]
+fn rejected_issue_proposal_can_slash_deposit() {
+    let (env, client, config) = setup_contract();
+    let (token, proposer) = setup_issue_proposals(&env, &client, &config.admin);
+
+    let issue = String::from_str(&env, "spam");
+    let proposal_id = client.propose_issue(&proposer, &soroban_sdk_repo(&env), &issue);
+    client.reject_issue_proposal(&config.admin, &proposal_id, &true);
+
+    assert_eq!(token.balance(&proposer), 0);
+    assert_eq!(token.balance(&config.admin), 50);
+    assert!(!client
+        .get_issues_for_repo(&soroban_sdk_repo(&env))
+        .contains_key(issue));
+}
+
+#[test]
+fn expired_issue_proposal_is_refunded() {
+    let (env, client, config) = setup_contract();
+    let (token, proposer) = setup_issue_proposals(&env, &client, &config.admin);
+
+    let proposal_id = client.propose_issue(
+        &proposer,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-9"),
+    );
+    assert!(client.try_expire_issue_proposal(&proposal_id).is_err());
+
+    env.ledger().with_mut(|l| l.sequence_number += 101);
+    assert!(client
+        .try_approve_issue_proposal(&config.admin, &proposal_id)
+        .is_err());
+    client.expire_issue_proposal(&proposal_id);
+
+    assert_eq!(token.balance(&proposer), 50);
+    assert!(client.try_get_issue_proposal(&proposal_id).is_err());
+}
+
+#[test]
+fn issue_proposals_are_paginated() {
+    let (env, client, config) = setup_contract();
+    let (_token, token_admin) = setup_token(&env, &config.admin);
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env)]);
+    client.set_proposal_deposit(&ProposalDepositConfig {
+        token: token_admin.address.clone(),
+        amount: 10,
+        expiry_ledgers: 100,
+    });
+
+    let proposer = Address::generate(&env);
+    token_admin.mint(&proposer, &30);
+    for name in ["issue-1", "issue-2", "issue-3"] {
+        client.propose_issue(
+            &proposer,
+            &soroban_sdk_repo(&env),
+            &String::from_str(&env, name),
+        );
+    }
+
+    let first_page = client.get_issue_proposals(&0, &2);
+    assert_eq!(first_page.len(), 2);
+    let next_cursor = first_page.last().unwrap().id + 1;
+
+    let second_page = client.get_issue_proposals(&next_cursor, &2);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(
+        second_page.get(0).unwrap().issue,
+        String::from_str(&env, "issue-3")
+    );
+}
+
+#[test]
+#[should_panic]
+fn propose_issue_without_deposit_config() {
+    let (env, client, _config) = setup_contract();
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env)]);
+
+    client.propose_issue(
+        &Address::generate(&env),
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-9"),
+    );
+}
+
+#[test]
+fn proposal_deposit_must_be_positive() {
+    let (env, client, config) = setup_contract();
+    let (token, _token_admin) = setup_token(&env, &config.admin);
+    let result = client.try_set_proposal_deposit(&ProposalDepositConfig {
+        token: token.address.clone(),
+        amount: 0,
+        expiry_ledgers: 100,
+    });
+    assert_eq!(result, Err(Ok(Error::InvalidAmount.into())));
+}
+
+fn setup_issue_proposals<'a>(
+    env: &Env,
+    client: &GithubOracleContractClient,
+    admin: &Address,
+) -> (token::Client<'a>, Address) {
+    let (token, token_admin) = setup_token(env, admin);
+    client.add_repos(&vec![env, soroban_sdk_repo(env)]);
+    client.set_proposal_deposit(&ProposalDepositConfig {
+        token: token.address.clone(),
+        amount: 50,
+        expiry_ledgers: 100,
+    });
+
+    let proposer = Address::generate(env);
+    token_admin.mint(&proposer, &50);
+
+    (token, proposer)
+}
+
+#[test]
+fn rename_repo_moves_data_and_redirects_old_name() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let (org_name, _maintainer) = setup_org(&env, &client);
+
+    let funder = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let issue = String::from_str(&env, "issue-1");
+    token_admin.mint(&funder, &500);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &500,
+    );
+
+    let new_name = String::from_str(&env, "stellar/rs-soroban-sdk");
+    client.rename_repo(&soroban_sdk_repo(&env), &new_name);
+
+    assert!(client.get_repos().contains(new_name.clone()));
+    assert!(!client.get_repos().contains(soroban_sdk_repo(&env)));
+    assert_eq!(
+        client.get_org_repos(&org_name),
+        vec![&env, new_name.clone(), soroban_cli_repo(&env)]
+    );
+    assert_eq!(client.get_repo_org(&new_name), Some(org_name));
+    assert_eq!(client.get_escrow(&new_name, &issue).unwrap().amount, 500);
+
+    //the old name keeps working
+    assert_eq!(
+        client.get_issues_for_repo(&soroban_sdk_repo(&env)),
+        client.get_issues_for_repo(&new_name)
+    );
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &vec![&env, (contributor.clone(), 10_000)],
+    );
+    assert_eq!(token.balance(&contributor), 500);
+    assert_eq!(client.get_escrow(&new_name, &issue), None);
+}
+
+#[test]
+fn rename_repo_rejects_taken_names() {
+    let (env, client, _config) = setup_contract();
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env), digicus_repo(&env)]);
+
+    assert!(client
+        .try_rename_repo(&soroban_sdk_repo(&env), &digicus_repo(&env))
+        .is_err());
+
+    let new_name = String::from_str(&env, "stellar/rs-soroban-sdk");
+    client.rename_repo(&soroban_sdk_repo(&env), &new_name);
+    assert!(client
+        .try_add_repos(&vec![&env, soroban_sdk_repo(&env)])
+        .is_err());
+
+    //renaming back replaces the redirect
+    client.rename_repo(&new_name, &soroban_sdk_repo(&env));
+    assert_repo_names(
+        client.get_repos(),
+        vec![&env, soroban_sdk_repo(&env), digicus_repo(&env)],
+    );
+    assert!(client
+        .try_rename_repo(&String::from_str(&env, "sdf/unknown"), &new_name)
+        .is_err());
+}
+
+#[test]
+fn rename_repo_stays_on_the_repos_forge() {
+    let (env, client, _config) = setup_contract();
+    client.add_forge_repos(&Forge::GitLab, &vec![&env, soroban_sdk_repo(&env)]);
+    let old_key = client.repo_key(&Forge::GitLab, &soroban_sdk_repo(&env));
+
+    for name in ["gitea:sdf/soroban-sdk", "sdf:soroban-sdk", ""] {
+        assert_eq!(
+            client.try_rename_repo(&old_key, &String::from_str(&env, name)),
+            Err(Ok(ExtendedError::InvalidRepoName.into()))
+        );
+    }
+
+    let new_name = String::from_str(&env, "stellar/rs-soroban-sdk");
+    client.rename_repo(&old_key, &new_name);
+    assert_eq!(
+        client.get_repos_by_forge(&Forge::GitLab),
+        vec![&env, client.repo_key(&Forge::GitLab, &new_name)]
+    );
+}
+
+#[test]
+fn issues_by_label_pages_across_repos() {
+    let (env, client, config) = setup_contract();
+    let (_org_name, maintainer) = setup_org(&env, &client);
+    client.add_issues(
+        &digicus_repo(&env),
+        &vec![&env, String::from_str(&env, "issue-3")],
+    );
+
+    let good_first_issue = String::from_str(&env, "good first issue");
+    let area = String::from_str(&env, "area:cli");
+    client.set_issue_labels(
+        &maintainer,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-1"),
+        &vec![&env, good_first_issue.clone(), area.clone()],
+    );
+    client.set_issue_labels(
+        &maintainer,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-2"),
+        &vec![&env, good_first_issue.clone()],
+    );
+    client.set_issue_labels(
+        &config.admin,
+        &digicus_repo(&env),
+        &String::from_str(&env, "issue-3"),
+        &vec![&env, good_first_issue.clone()],
+    );
+
+    //org maintainers cannot label repos outside their org
+    assert!(client
+        .try_set_issue_labels(
+            &maintainer,
+            &digicus_repo(&env),
+            &String::from_str(&env, "issue-3"),
+            &vec![&env, area.clone()],
+        )
+        .is_err());
+
+    let first_page = client.issues_by_label(&good_first_issue, &None, &2);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(
+        first_page.get(0).unwrap().issue,
+        String::from_str(&env, "issue-1")
+    );
+
+    //dropping an issue from the index does not shift the next page
+    client.set_issue_labels(
+        &maintainer,
+        &soroban_sdk_repo(&env),
+        &String::from_str(&env, "issue-1"),
+        &vec![&env, area.clone()],
+    );
+    let last = first_page.last();
+    let second_page = client.issues_by_label(&good_first_issue, &last, &2);
+    let digicus_issue = IssueRef {
+        repo: digicus_repo(&env),
+        issue: String::from_str(&env, "issue-3"),
+    };
+    assert_eq!(second_page, vec![&env, digicus_issue.clone()]);
+    assert_eq!(
+        client
+            .issues_by_label(&good_first_issue, &Some(digicus_issue), &2)
+            .len(),
+        0
+    );
+    assert_eq!(client.issues_by_label(&area, &None, &10).len(), 1);
+}
+
+#[test]
+fn issues_by_label_skips_completed_issues() {
+    let (env, client, config) = setup_contract();
+    let issue = setup_issue(&env, &client);
+    let open_issue = String::from_str(&env, "issue-2");
+    client.add_issues(&soroban_sdk_repo(&env), &vec![&env, open_issue.clone()]);
+
+    let bug = String::from_str(&env, "bug");
+    for labelled in [issue.clone(), open_issue.clone()] {
+        client.set_issue_labels(
+            &config.admin,
+            &soroban_sdk_repo(&env),
+            &labelled,
+            &vec![&env, bug.clone()],
+        );
+    }
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &vec![&env, (Address::generate(&env), 10_000)],
+    );
+
+    assert_eq!(
+        client.issues_by_label(&bug, &None, &10),
+        vec![
+            &env,
+            IssueRef {
+                repo: soroban_sdk_repo(&env),
+                issue: open_issue,
+            }
+        ]
+    );
+}
+
+#[test]
+fn relabeling_and_removing_issues_updates_label_index() {
+    let (env, client, config) = setup_contract();
+    let issue = setup_issue(&env, &client);
+
+    let easy = String::from_str(&env, "easy");
+    let hard = String::from_str(&env, "hard");
+    client.set_issue_labels(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &vec![&env, easy.clone()],
+    );
+    client.set_issue_labels(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &vec![&env, hard.clone(), hard.clone()],
+    );
+
+    assert_eq!(client.issues_by_label(&easy, &None, &10).len(), 0);
+    assert_eq!(client.issues_by_label(&hard, &None, &10).len(), 1);
+    assert_eq!(
+        client.get_issue_labels(&soroban_sdk_repo(&env), &issue),
+        vec![&env, hard.clone()]
+    );
+
+    client.remove_issues(&soroban_sdk_repo(&env), &vec![&env, issue.clone()]);
+    assert_eq!(client.issues_by_label(&hard, &None, &10).len(), 0);
+    assert_eq!(
+        client
+            .get_issue_labels(&soroban_sdk_repo(&env), &issue)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn blocked_issue_cannot_complete_until_blocker_does() {
+    let (env, client, config) = setup_contract();
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env), digicus_repo(&env)]);
+    client.add_issues(
+        &soroban_sdk_repo(&env),
+        &vec![&env, String::from_str(&env, "issue-1")],
+    );
+    client.add_issues(
+        &digicus_repo(&env),
+        &vec![&env, String::from_str(&env, "issue-2")],
+    );
+
+    let epic_step = IssueRef {
+        repo: digicus_repo(&env),
+        issue: String::from_str(&env, "issue-2"),
+    };
+    let blocker = IssueRef {
+        repo: soroban_sdk_repo(&env),
+        issue: String::from_str(&env, "issue-1"),
+    };
+    client.add_issue_dependency(&config.admin, &epic_step, &blocker);
+    assert_eq!(
+        client.get_issue_blockers(&epic_step),
+        vec![&env, blocker.clone()]
+    );
+    assert_eq!(
+        client.get_issue_dependents(&blocker),
+        vec![&env, epic_step.clone()]
+    );
+
+    let shares = vec![&env, (Address::generate(&env), 10_000)];
+    assert!(client
+        .try_complete_issue(&config.admin, &epic_step.repo, &epic_step.issue, &shares)
+        .is_err());
+
+    client.complete_issue(&config.admin, &blocker.repo, &blocker.issue, &shares);
+    client.complete_issue(&config.admin, &epic_step.repo, &epic_step.issue, &shares);
+}
+
+#[test]
+fn issue_dependency_cycles_are_rejected() {
+    let (env, client, config) = setup_contract();
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env)]);
+    client.add_issues(
+        &soroban_sdk_repo(&env),
+        &vec![
+            &env,
+            String::from_str(&env, "issue-1"),
+            String::from_str(&env, "issue-2"),
+            String::from_str(&env, "issue-3"),
+        ],
+    );
+    let issue_ref = |issue: &str| IssueRef {
+        repo: soroban_sdk_repo(&env),
+        issue: String::from_str(&env, issue),
+    };
+
+    client.add_issue_dependency(&config.admin, &issue_ref("issue-2"), &issue_ref("issue-1"));
+    client.add_issue_dependency(&config.admin, &issue_ref("issue-3"), &issue_ref("issue-2"));
+    assert!(client
+        .try_add_issue_dependency(&config.admin, &issue_ref("issue-1"), &issue_ref("issue-3"))
+        .is_err());
+    assert!(client
+        .try_add_issue_dependency(&config.admin, &issue_ref("issue-1"), &issue_ref("issue-1"))
+        .is_err());
+
+    //removing an issue drops its edges
+    client.remove_issues(
+        &soroban_sdk_repo(&env),
+        &vec![&env, String::from_str(&env, "issue-2")],
+    );
+    assert_eq!(client.get_issue_blockers(&issue_ref("issue-3")).len(), 0);
+    assert_eq!(client.get_issue_dependents(&issue_ref("issue-1")).len(), 0);
+}
+
+#[test]
+fn stats_track_issues_escrow_and_payouts() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env), digicus_repo(&env)]);
+    client.add_issues(
+        &soroban_sdk_repo(&env),
+        &vec![
+            &env,
+            String::from_str(&env, "issue-1"),
+            String::from_str(&env, "issue-2"),
+        ],
+    );
+    client.add_issues(
+        &digicus_repo(&env),
+        &vec![&env, String::from_str(&env, "issue-3")],
+    );
+
+    let funder = Address::generate(&env);
+    let issue = String::from_str(&env, "issue-1");
+    token_admin.mint(&funder, &1_000);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &1_000,
+    );
+    client.set_vesting_schedule(
+        &soroban_sdk_repo(&env),
+        &issue,
+        &VestingSchedule {
+            upfront_basis_points: 5_000,
+            duration_ledgers: 100,
+        },
+    );
+
+    let alice = Address::generate(&env);
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &vec![&env, (alice.clone(), 10_000)],
+    );
+
+    let repo_stats = client.repo_stats(&soroban_sdk_repo(&env));
+    assert_eq!(repo_stats.unclaimed_issues, 1);
+    assert_eq!(repo_stats.completed_issues, 1);
+    assert_eq!(repo_stats.escrowed.get(token.address.clone()), Some(500));
+    assert_eq!(repo_stats.paid_out.get(token.address.clone()), Some(500));
+    assert_eq!(repo_stats.active_claimants, 1);
+
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    client.withdraw_vested(&alice, &soroban_sdk_repo(&env), &issue);
+
+    let global_stats = client.global_stats();
+    assert_eq!(global_stats.unclaimed_issues, 2);
+    assert_eq!(global_stats.completed_issues, 1);
+    assert_eq!(global_stats.escrowed.get(token.address.clone()), None);
+    assert_eq!(
+        global_stats.paid_out.get(token.address.clone()),
+        Some(1_000)
+    );
+    assert_eq!(global_stats.active_claimants, 1);
+
+    client.remove_repos(&vec![&env, soroban_sdk_repo(&env)]);
+    let global_stats = client.global_stats();
+    assert_eq!(global_stats.unclaimed_issues, 1);
+    assert_eq!(global_stats.completed_issues, 0);
+    assert_eq!(global_stats.active_claimants, 0);
+    assert_eq!(
+        global_stats.paid_out.get(token.address.clone()),
+        Some(1_000)
+    );
+    assert_eq!(client.repo_stats(&digicus_repo(&env)).unclaimed_issues, 1);
+}
+
+#[test]
+fn stats_drop_a_removed_funded_issue() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env)]);
+    let issues = vec![
+        &env,
+        String::from_str(&env, "issue-1"),
+        String::from_str(&env, "issue-2"),
+    ];
+    client.add_issues(&soroban_sdk_repo(&env), &issues);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &300);
+    for issue in issues.iter() {
+        client.fund_issue(
+            &funder,
+            &soroban_sdk_repo(&env),
+            &issue,
+            &token.address,
+            &150,
+        );
+    }
+    let alice = Address::generate(&env);
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &issues.get_unchecked(0),
+        &vec![&env, (alice, 10_000)],
+    );
+    assert_eq!(client.global_stats().active_claimants, 1);
+
+    client.refund_escrow(&soroban_sdk_repo(&env), &issues.get_unchecked(1), &funder);
+    client.remove_issues(&soroban_sdk_repo(&env), &issues);
+
+    let repo_stats = client.repo_stats(&soroban_sdk_repo(&env));
+    assert_eq!(repo_stats.unclaimed_issues, 0);
+    assert_eq!(repo_stats.completed_issues, 0);
+    assert_eq!(repo_stats.escrowed.get(token.address.clone()), None);
+    assert_eq!(repo_stats.active_claimants, 0);
+    let global_stats = client.global_stats();
+    assert_eq!(global_stats.escrowed.get(token.address.clone()), None);
+    assert_eq!(global_stats.paid_out.get(token.address.clone()), Some(150));
+    assert_eq!(global_stats.active_claimants, 0);
+}
+
+#[test]
+fn global_stats_count_grant_funds() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let grant_data = setup_grant(&env, &client, &token_admin);
+    let grant_id = client.create_grant(&grant_data);
+    assert_eq!(
+        client.global_stats().escrowed.get(token.address.clone()),
+        Some(600)
+    );
+
+    let shares = vec![&env, (Address::generate(&env), 10_000)];
+    for issue in ["issue-1", "issue-2"] {
+        client.complete_issue(
+            &config.admin,
+            &soroban_sdk_repo(&env),
+            &String::from_str(&env, issue),
+            &shares,
+        );
+    }
+    let global_stats = client.global_stats();
+    assert_eq!(global_stats.escrowed.get(token.address.clone()), Some(400));
+    assert_eq!(global_stats.paid_out.get(token.address.clone()), Some(200));
+
+    client.cancel_grant(&grant_id);
+    let global_stats = client.global_stats();
+    assert_eq!(global_stats.escrowed.get(token.address.clone()), None);
+    assert_eq!(global_stats.paid_out.get(token.address.clone()), Some(200));
+    assert_eq!(
+        client
+            .repo_stats(&soroban_sdk_repo(&env))
+            .paid_out
+            .get(token.address.clone()),
+        None
+    );
+}
+
+fn setup_grant(
+    env: &Env,
+    client: &GithubOracleContractClient,
+    token_admin: &token::StellarAssetClient,
+) -> GrantData {
+    client.add_repos(&vec![env, soroban_sdk_repo(env), digicus_repo(env)]);
+    client.add_issues(
+        &soroban_sdk_repo(env),
+        &vec![
+            env,
+            String::from_str(env, "issue-1"),
+            String::from_str(env, "issue-2"),
+        ],
+    );
+    client.add_issues(
+        &digicus_repo(env),
+        &vec![env, String::from_str(env, "issue-3")],
+    );
+
+    let funder = Address::generate(env);
+    token_admin.mint(&funder, &600);
+
+    GrantData {
+        funder,
+        grantee: Address::generate(env),
+        token: token_admin.address.clone(),
+        milestones: vec![
+            env,
+            Milestone {
+                issues: vec![
+                    env,
+                    IssueRef {
+                        repo: soroban_sdk_repo(env),
+                        issue: String::from_str(env, "issue-1"),
+                    },
+                    IssueRef {
+                        repo: soroban_sdk_repo(env),
+                        issue: String::from_str(env, "issue-2"),
+                    },
+                ],
+                amount: 200,
+            },
+            Milestone {
+                issues: vec![
+                    env,
+                    IssueRef {
+                        repo: digicus_repo(env),
+                        issue: String::from_str(env, "issue-3"),
+                    },
+                ],
+                amount: 400,
+            },
+        ],
+    }
+}
+
+#[test]
+fn limits_are_stored_from_config() {
+    let (_env, client, config) = setup_contract();
+
+    assert_eq!(
+        client.get_limits(),
+        Limits {
+            max_repos: config.max_repos,
+            max_issues_per_repo: config.max_issues_per_repo,
+        }
+    );
+}
+
+#[test]
+fn adds_repos_up_to_the_limit() {
+    let (env, client, _config) = setup_contract();
+    client.set_limits(&Limits {
+        max_repos: 2,
+        max_issues_per_repo: 512,
+    });
+
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env), digicus_repo(&env)]);
+    assert_eq!(client.get_repos().len(), 2);
+
+    let result = client.try_add_repos(&vec![&env, soroban_cli_repo(&env)]);
+    assert_eq!(result, Err(Ok(Error::RepoLimitExceeded.into())));
+    assert_eq!(client.get_repos().len(), 2);
+}
+
+#[test]
+fn per_repo_issue_limit_overrides_global_limit() {
+    let (env, client, _config) = setup_contract();
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env), digicus_repo(&env)]);
+    client.set_repo_issue_limit(&soroban_sdk_repo(&env), &Some(1));
+    assert_eq!(client.get_repo_issue_limit(&soroban_sdk_repo(&env)), 1);
+    assert_eq!(client.get_repo_issue_limit(&digicus_repo(&env)), 512);
+
+    let issues: Vec<String> = vec![
+        &env,
+        String::from_str(&env, "issue-1"),
+        String::from_str(&env, "issue-2"),
+    ];
+    let result = client.try_add_issues(&soroban_sdk_repo(&env), &issues);
+    assert_eq!(result, Err(Ok(Error::IssueLimitExceeded.into())));
+    assert!(client
+        .get_issues_for_repo(&soroban_sdk_repo(&env))
+        .is_empty());
+    client.add_issues(&digicus_repo(&env), &issues);
+
+    client.set_repo_issue_limit(&soroban_sdk_repo(&env), &None);
+    client.add_issues(&soroban_sdk_repo(&env), &issues);
+    assert_eq!(client.get_issues_for_repo(&soroban_sdk_repo(&env)).len(), 2);
+}
+
+#[test]
+fn signer_set_requires_threshold_for_sensitive_actions() {
+    let (env, client, _config) = setup_contract();
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env)]);
+    let (alice, bob, _carol) = setup_signers(&env, &client);
+
+    let state_hash = client.get_state_hash();
+    assert!(client.try_request_clear(&state_hash).is_err());
+
+    let proposal_id =
+        client.propose_admin_action(&alice, &AdminAction::RequestClear(state_hash.clone()));
+    assert!(client.try_execute_admin_action(&proposal_id).is_err());
+
+    client.approve_admin_action(&bob, &proposal_id);
+    client.execute_admin_action(&proposal_id);
+    assert_eq!(client.get_clear_request().unwrap().state_hash, state_hash);
+
+    //the executed proposal is removed, so it cannot be replayed
+    assert_eq!(
+        client.try_execute_admin_action(&proposal_id),
+        Err(Ok(Error::AdminProposalMissing.into()))
+    );
+}
+
+#[test]
+fn weighted_signer_can_meet_threshold_alone() {
+    let (env, client, _config) = setup_contract();
+    let (_alice, _bob, carol) = setup_signers(&env, &client);
+
+    let limits = Limits {
+        max_repos: 10,
+        max_issues_per_repo: 20,
+    };
+    assert!(client.try_set_limits(&limits).is_err());
+
+    let proposal_id = client.propose_admin_action(&carol, &AdminAction::SetLimits(limits.clone()));
+    client.execute_admin_action(&proposal_id);
+    assert_eq!(client.get_limits(), limits);
+}
+
+#[test]
+fn signers_can_take_upgrades_back_from_the_upgrader() {
+    let (env, client, _config) = setup_contract();
+    client.set_upgrader(&Some(Address::generate(&env)));
+    let (alice, bob, _carol) = setup_signers(&env, &client);
+    assert!(client.try_set_upgrader(&None).is_err());
+
+    let proposal_id = client.propose_admin_action(&alice, &AdminAction::SetUpgrader(None));
+    client.approve_admin_action(&bob, &proposal_id);
+    client.execute_admin_action(&proposal_id);
+    assert_eq!(client.get_upgrader(), None);
+}
+
+#[test]
+fn role_changes_go_through_the_signer_set() {
+    let (env, client, _config) = setup_contract();
+    let org_name = String::from_str(&env, "stellar");
+    client.create_org(&org_name, &vec![&env, Address::generate(&env)]);
+    let (_alice, _bob, carol) = setup_signers(&env, &client);
+
+    let attestor = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let maintainer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let multisig_required = Err(Ok(Error::MultisigRequired.into()));
+    assert_eq!(client.try_set_attestor(&attestor), multisig_required);
+    assert_eq!(
+        client.try_register_voters(&vec![&env, voter.clone()]),
+        multisig_required
+    );
+    assert_eq!(
+        client.try_remove_voters(&vec![&env, voter.clone()]),
+        multisig_required
+    );
+    assert_eq!(
+        client.try_set_org_maintainers(&org_name, &vec![&env, maintainer.clone()]),
+        multisig_required
+    );
+    assert_eq!(client.try_set_dust_recipient(&recipient), multisig_required);
+
+    for action in [
+        AdminAction::SetAttestor(attestor.clone()),
+        AdminAction::RegisterVoters(vec![&env, voter.clone()]),
+        AdminAction::SetOrgMaintainers(org_name.clone(), vec![&env, maintainer.clone()]),
+        AdminAction::SetDustRecipient(recipient.clone()),
+    ] {
+        let proposal_id = client.propose_admin_action(&carol, &action);
+        client.execute_admin_action(&proposal_id);
+    }
+    assert_eq!(client.attestor(), Some(attestor));
+    assert_eq!(client.get_voters(), vec![&env, voter.clone()]);
+    assert_eq!(
+        client.get_org(&org_name).maintainers,
+        vec![&env, maintainer]
+    );
+    assert_eq!(client.dust_recipient(), Some(recipient));
+
+    let proposal_id =
+        client.propose_admin_action(&carol, &AdminAction::RemoveVoters(vec![&env, voter]));
+    client.execute_admin_action(&proposal_id);
+    assert!(client.get_voters().is_empty());
+}
+
+#[test]
+fn expired_admin_proposal_cannot_be_executed() {
+    let (env, client, _config) = setup_contract();
+    let (alice, bob, _carol) = setup_signers(&env, &client);
+
+    let proposal_id = client.propose_admin_action(&alice, &AdminAction::RemoveSigners);
+    env.ledger().with_mut(|l| l.sequence_number += 11);
+
+    assert!(client.try_approve_admin_action(&bob, &proposal_id).is_err());
+    assert!(client.try_execute_admin_action(&proposal_id).is_err());
+    assert!(client.get_signers().is_some());
+
+    //anyone can remove it once it has expired
+    client.expire_admin_action(&proposal_id);
+    assert_eq!(
+        client.try_get_admin_proposal(&proposal_id),
+        Err(Ok(Error::AdminProposalMissing.into()))
+    );
+    let proposal_id = client.propose_admin_action(&alice, &AdminAction::RemoveSigners);
+    assert_eq!(
+        client.try_expire_admin_action(&proposal_id),
+        Err(Ok(Error::ProposalNotExpired.into()))
+    );
+}
+
+#[test]
+#[should_panic]
+fn non_signer_cannot_propose_admin_action() {
+    let (env, client, _config) = setup_contract();
+    setup_signers(&env, &client);
+
+    client.propose_admin_action(&Address::generate(&env), &AdminAction::RemoveSigners);
+}
+
+#[test]
+fn other_forge_repos_can_be_voted_in() {
+    let (env, client, _config) = setup_contract_with_governance(
+        Env::default(),
+        RepoGovernance::Voting(VotingConfig {
+            voting_period_ledgers: 10,
+            quorum: 2,
+            weight_token: None,
+        }),
+    );
+    let (alice, bob, _carol) = setup_voters(&env, &client);
+
+    let change = RepoChange::Add(Forge::Gitea, digicus_repo(&env));
+    let proposal_id = client.propose_repo_change(&alice, &change);
+    client.vote_repo_change(&alice, &proposal_id, &true);
+    client.vote_repo_change(&bob, &proposal_id, &true);
+    env.ledger().with_mut(|l| l.sequence_number += 11);
+    client.execute_repo_change(&proposal_id);
+    assert_eq!(
+        client.get_repos_by_forge(&Forge::Gitea),
+        vec![&env, client.repo_key(&Forge::Gitea, &digicus_repo(&env))]
+    );
+
+    //a name cannot pass for another forge's key
+    let change = RepoChange::Add(
+        Forge::GitHub,
+        String::from_str(&env, "gitlab:sdf/soroban-sdk"),
+    );
+    assert_eq!(
+        client.try_propose_repo_change(&alice, &change),
+        Err(Ok(ExtendedError::InvalidRepoName.into()))
+    );
+}
+
+#[test]
+fn repo_proposal_passes_with_majority_after_voting_period() {
+    let (env, client, _config) = setup_contract_with_governance(
+        Env::default(),
+        RepoGovernance::Voting(VotingConfig {
+            voting_period_ledgers: 10,
+            quorum: 2,
+            weight_token: None,
+        }),
+    );
+    let (alice, bob, carol) = setup_voters(&env, &client);
+
+    assert!(client
+        .try_add_repos(&vec![&env, soroban_sdk_repo(&env)])
+        .is_err());
+
+    let proposal_id = client.propose_repo_change(
+        &alice,
+        &RepoChange::Add(Forge::GitHub, soroban_sdk_repo(&env)),
+    );
+    client.vote_repo_change(&alice, &proposal_id, &true);
+    client.vote_repo_change(&bob, &proposal_id, &true);
+    client.vote_repo_change(&carol, &proposal_id, &false);
+    assert!(client.try_execute_repo_change(&proposal_id).is_err());
+
+    env.ledger().with_mut(|l| l.sequence_number += 11);
+    assert!(client
+        .try_vote_repo_change(&carol, &proposal_id, &false)
+        .is_err());
+    client.execute_repo_change(&proposal_id);
+    assert_eq!(client.get_repos(), vec![&env, soroban_sdk_repo(&env)]);
+    //the executed proposal is removed, so it cannot be replayed
+    assert_eq!(
+        client.try_execute_repo_change(&proposal_id),
+        Err(Ok(Error::RepoProposalMissing.into()))
+    );
+
+    let proposal_id = client.propose_repo_change(&bob, &RepoChange::Remove(soroban_sdk_repo(&env)));
+    client.vote_repo_change(&alice, &proposal_id, &true);
+    client.vote_repo_change(&bob, &proposal_id, &true);
+    env.ledger().with_mut(|l| l.sequence_number += 11);
+    client.execute_repo_change(&proposal_id);
+    assert_eq!(client.get_repos(), vec![&env]);
+}
+
+#[test]
+fn repo_proposal_votes_are_weighted_by_token_snapshot() {
+    let env = Env::default();
+    let (token, token_admin) = setup_token(&env, &Address::generate(&env));
+    let (env, client, _config) = setup_contract_with_governance(
+        env,
+        RepoGovernance::Voting(VotingConfig {
+            voting_period_ledgers: 10,
+            quorum: 0,
+            weight_token: Some(token.address.clone()),
+        }),
+    );
+    let (alice, bob, carol) = setup_voters(&env, &client);
+    token_admin.mint(&alice, &10);
+    token_admin.mint(&bob, &10);
+    token_admin.mint(&carol, &50);
+
+    let proposal_id = client.propose_repo_change(
+        &alice,
+        &RepoChange::Add(Forge::GitHub, soroban_sdk_repo(&env)),
+    );
+
+    //balances moved after the snapshot do not change the vote weights
+    token.transfer(&carol, &alice, &45);
+
+    client.vote_repo_change(&alice, &proposal_id, &true);
+    client.vote_repo_change(&bob, &proposal_id, &true);
+    client.vote_repo_change(&carol, &proposal_id, &false);
+
+    let proposal = client.get_repo_proposal(&proposal_id);
+    assert_eq!(proposal.votes_for, 20);
+    assert_eq!(proposal.votes_against, 50);
+
+    env.ledger().with_mut(|l| l.sequence_number += 11);
+    assert!(client.try_execute_repo_change(&proposal_id).is_err());
+
+    //a rejected proposal can be removed by anyone once voting closes
+    client.expire_repo_change(&proposal_id);
+    assert_eq!(
+        client.try_get_repo_proposal(&proposal_id),
+        Err(Ok(Error::RepoProposalMissing.into()))
+    );
+}
+
+#[test]
+fn passed_repo_proposal_expires_after_another_voting_period() {
+    let (env, client, _config) = setup_contract_with_governance(
+        Env::default(),
+        RepoGovernance::Voting(VotingConfig {
+            voting_period_ledgers: 10,
+            quorum: 0,
+            weight_token: None,
+        }),
+    );
+    let (alice, _bob, _carol) = setup_voters(&env, &client);
+
+    let proposal_id = client.propose_repo_change(
+        &alice,
+        &RepoChange::Add(Forge::GitHub, soroban_sdk_repo(&env)),
+    );
+    client.vote_repo_change(&alice, &proposal_id, &true);
+    assert_eq!(
+        client.try_expire_repo_change(&proposal_id),
+        Err(Ok(Error::VotingOpen.into()))
+    );
+
+    env.ledger().with_mut(|l| l.sequence_number += 11);
+    assert_eq!(
+        client.try_expire_repo_change(&proposal_id),
+        Err(Ok(Error::ProposalNotExpired.into()))
+    );
+    env.ledger().with_mut(|l| l.sequence_number += 10);
+    client.expire_repo_change(&proposal_id);
+    assert_eq!(
+        client.try_execute_repo_change(&proposal_id),
+        Err(Ok(Error::RepoProposalMissing.into()))
+    );
+}
+
+#[test]
+#[should_panic]
+fn voter_cannot_vote_twice_on_repo_proposal() {
+    let (env, client, _config) = setup_contract_with_governance(
+        Env::default(),
+        RepoGovernance::Voting(VotingConfig {
+            voting_period_ledgers: 10,
+            quorum: 0,
+            weight_token: None,
+        }),
+    );
+    let (alice, _bob, _carol) = setup_voters(&env, &client);
+
+    let proposal_id = client.propose_repo_change(
+        &alice,
+        &RepoChange::Add(Forge::GitHub, soroban_sdk_repo(&env)),
+    );
+    client.vote_repo_change(&alice, &proposal_id, &true);
+    client.vote_repo_change(&alice, &proposal_id, &true);
+}
+
+#[test]
+fn clear_requires_delay_and_archives_registry() {
+    let (env, client, _config) = setup_contract();
+    let issue = setup_issue(&env, &client);
+    let repos_and_issues = client.get_repos_and_issues();
+
+    let state_hash = client.get_state_hash();
+    client.request_clear(&state_hash);
+    assert!(client.try_confirm_clear(&state_hash).is_err());
+
+    env.ledger().with_mut(|l| l.sequence_number += 17_280);
+    let archive_id = client.confirm_clear(&state_hash);
+
+    assert_eq!(client.get_repos(), vec![&env]);
+    assert_eq!(client.get_clear_request(), None);
+    assert_eq!(client.get_archive_count(), 1);
+    let archive = client.get_archive(&archive_id).unwrap();
+    assert_eq!(archive.repos, repos_and_issues);
+    assert_eq!(archive.state_hash, state_hash);
+    assert!(archive
+        .repos
+        .get(soroban_sdk_repo(&env))
+        .unwrap()
+        .contains_key(issue));
+}
+
+#[test]
+fn clear_is_refused_when_state_changed_since_request() {
+    let (env, client, _config) = setup_contract();
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env)]);
+
+    let state_hash = client.get_state_hash();
+    assert!(client
+        .try_request_clear(&BytesN::from_array(&env, &[0; 32]))
+        .is_err());
+    client.request_clear(&state_hash);
+
+    client.add_repos(&vec![&env, digicus_repo(&env)]);
+    env.ledger().with_mut(|l| l.sequence_number += 17_280);
+
+    assert!(client.try_confirm_clear(&state_hash).is_err());
+    assert_eq!(client.get_repos().len(), 2);
+}
+
+#[test]
+fn clear_is_refused_while_funds_are_escrowed() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let issue = setup_issue(&env, &client);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &100);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &100,
+    );
+
+    let state_hash = client.get_state_hash();
+    client.request_clear(&state_hash);
+    env.ledger().with_mut(|l| l.sequence_number += 17_280);
+
+    assert!(client.try_confirm_clear(&state_hash).is_err());
+    assert_eq!(client.get_repos().len(), 1);
+}
+
+#[test]
+fn clear_is_refused_while_grant_milestones_are_unreleased() {
+    let (env, client, config) = setup_contract();
+    let (_token, token_admin) = setup_token(&env, &config.admin);
+    let grant_data = setup_grant(&env, &client, &token_admin);
+    let grant_id = client.create_grant(&grant_data);
+
+    let state_hash = client.get_state_hash();
+    client.request_clear(&state_hash);
+    env.ledger().with_mut(|l| l.sequence_number += 17_280);
+    assert_eq!(
+        client.try_confirm_clear(&state_hash),
+        Err(Ok(Error::FundsEscrowed.into()))
+    );
+
+    client.cancel_grant(&grant_id);
+    client.confirm_clear(&state_hash);
+    assert!(client.get_repos().is_empty());
+}
+
+#[test]
+fn exported_state_imports_into_a_fresh_contract() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let (org_name, maintainer) = setup_org(&env, &client);
+    let issue = String::from_str(&env, "issue-1");
+    let blocked = String::from_str(&env, "issue-2");
+    let issue_ref = |issue: &String| IssueRef {
+        repo: soroban_sdk_repo(&env),
+        issue: issue.clone(),
+    };
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &700);
+    client.fund_issue(
+        &funder,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &token.address,
+        &700,
+    );
+    client.set_issue_labels(
+        &maintainer,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &vec![&env, String::from_str(&env, "bug")],
+    );
+    client.add_issue_dependency(&maintainer, &issue_ref(&blocked), &issue_ref(&issue));
+    client.set_repo_state(&digicus_repo(&env), &RepoState::Paused);
+    let attestor = Address::generate(&env);
+    client.set_attestor(&attestor);
+    let consumer = Address::generate(&env);
+    token_admin.mint(&consumer, &20);
+    client.set_read_pricing(&Some(ReadPricing {
+        token: token.address.clone(),
+        fee: 5,
+        free_reads: 0,
+        period_ledgers: 100,
+    }));
+    client.deposit_read_credit(&consumer, &20);
+    client.get_issue_proof_paid(&consumer, &soroban_sdk_repo(&env), &issue);
+
+    let vested = String::from_str(&env, "issue-3");
+    client.add_issues(&soroban_cli_repo(&env), &vec![&env, vested.clone()]);
+    token_admin.mint(&funder, &300);
+    client.fund_issue(
+        &funder,
+        &soroban_cli_repo(&env),
+        &vested,
+        &token.address,
+        &200,
+    );
+    client.set_vesting_schedule(
+        &soroban_cli_repo(&env),
+        &vested,
+        &VestingSchedule {
+            upfront_basis_points: 0,
+            duration_ledgers: 100,
+        },
+    );
+    client.complete_issue(
+        &config.admin,
+        &soroban_cli_repo(&env),
+        &vested,
+        &vec![&env, (Address::generate(&env), 10_000)],
+    );
+    let grant_id = client.create_grant(&GrantData {
+        funder: funder.clone(),
+        grantee: Address::generate(&env),
+        token: token.address.clone(),
+        milestones: vec![
+            &env,
+            Milestone {
+                issues: vec![&env, issue_ref(&blocked)],
+                amount: 100,
+            },
+        ],
+    });
+
+    let restored_id = env.register_contract(None, GithubOracleContract);
+    let restored = GithubOracleContractClient::new(&env, &restored_id);
+    let importer = Address::generate(&env);
+    token_admin.mint(&importer, &1_100);
+    restored.begin_import(&importer);
+
+    let mut cursor = Some(0);
+    let mut checksum = BytesN::from_array(&env, &[0; 32]);
+    while let Some(next) = cursor {
+        let chunk = client.export_chunk(&next, &checksum);
+        assert_eq!(chunk.state_hash, client.get_state_hash());
+        restored.import_chunk(&chunk);
+        (cursor, checksum) = (chunk.next_cursor, chunk.checksum);
+    }
+    //settings, three repos and the grant
+    assert_eq!(restored.get_import_state().unwrap().chunks, 5);
+
+    //roles are only granted once the import is finished
+    assert_eq!(restored.admin(), None);
+    assert!(restored
+        .try_set_org_repos_state(&maintainer, &org_name, &RepoState::Paused)
+        .is_err());
+    restored.finish_import();
+
+    assert_eq!(restored.admin(), Some(config.admin.clone()));
+    assert_eq!(restored.attestor(), Some(attestor));
+    assert_eq!(restored.get_import_state(), None);
+    assert_eq!(restored.get_state_hash(), client.get_state_hash());
+    assert_eq!(restored.state_root(), client.state_root());
+    //read credit stays with the old address
+    assert!(restored.get_read_credit(&consumer).is_empty());
+    assert_eq!(restored.get_read_revenue(), client.get_read_revenue());
+    assert_eq!(
+        restored.get_repos_and_issues(),
+        client.get_repos_and_issues()
+    );
+    assert_eq!(restored.get_org(&org_name), client.get_org(&org_name));
+    assert_eq!(
+        restored.get_repo_org(&soroban_sdk_repo(&env)),
+        Some(org_name)
+    );
+    assert_eq!(
+        restored.get_repo_state(&digicus_repo(&env)),
+        RepoState::Paused
+    );
+    assert_eq!(
+        restored.get_escrow(&soroban_sdk_repo(&env), &issue),
+        client.get_escrow(&soroban_sdk_repo(&env), &issue)
+    );
+    assert_eq!(
+        restored.issues_by_label(&String::from_str(&env, "bug"), &None, &10),
+        vec![&env, issue_ref(&issue)]
+    );
+    assert_eq!(
+        restored.get_issue_dependents(&issue_ref(&issue)),
+        vec![&env, issue_ref(&blocked)]
+    );
+    assert_eq!(
+        restored.repo_stats(&soroban_sdk_repo(&env)),
+        client.repo_stats(&soroban_sdk_repo(&env))
+    );
+    assert_eq!(restored.global_stats(), client.global_stats());
+    assert_eq!(restored.get_grant(&grant_id), client.get_grant(&grant_id));
+    assert_eq!(restored.get_grant_count(), 1);
+    assert_eq!(
+        restored.get_vesting(&soroban_cli_repo(&env), &vested),
+        client.get_vesting(&soroban_cli_repo(&env), &vested)
+    );
+    assert_eq!(
+        restored.get_vesting_schedule(&soroban_cli_repo(&env), &vested),
+        client.get_vesting_schedule(&soroban_cli_repo(&env), &vested)
+    );
+    //the grant still holds the issue it pays for
+    assert_eq!(
+        restored.try_remove_issues(&soroban_sdk_repo(&env), &vec![&env, blocked.clone()]),
+        Err(Ok(Error::FundsEscrowed.into()))
+    );
+
+    //every balance is backed by tokens pulled from the importer
+    assert_eq!(token.balance(&client.address), 1_020);
+    assert_eq!(token.balance(&restored.address), 1_005);
+    assert_eq!(token.balance(&importer), 95);
+    client.withdraw_read_credit(&consumer, &token.address, &15);
+    assert_eq!(token.balance(&consumer), 15);
+}
+
+#[test]
+fn import_rejects_chunks_out_of_order() {
+    let (env, client, _config) = setup_contract();
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env), soroban_cli_repo(&env)]);
+
+    let restored_id = env.register_contract(None, GithubOracleContract);
+    let restored = GithubOracleContractClient::new(&env, &restored_id);
+    restored.begin_import(&Address::generate(&env));
+
+    let zero = BytesN::from_array(&env, &[0; 32]);
+    let first = client.export_chunk(&0, &zero);
+    let second = client.export_chunk(&1, &first.checksum);
+    let third = client.export_chunk(&2, &second.checksum);
+    assert_eq!(third.next_cursor, None);
+
+    //skipping a chunk is refused
+    let checksum_mismatch = Err(Ok(ExtendedError::ChecksumMismatch.into()));
+    assert_eq!(restored.try_import_chunk(&second), checksum_mismatch);
+    let skipped = client.export_chunk(&1, &zero);
+    assert_eq!(restored.try_import_chunk(&skipped), checksum_mismatch);
+
+    restored.import_chunk(&first);
+    //so is replaying one, or tampering with its records
+    assert_eq!(restored.try_import_chunk(&first), checksum_mismatch);
+    let mut tampered = second.clone();
+    tampered.records = third.records.clone();
+    assert_eq!(restored.try_import_chunk(&tampered), checksum_mismatch);
+    assert_eq!(
+        restored.try_finish_import(),
+        Err(Ok(ExtendedError::ImportInProgress.into()))
+    );
+
+    //pages exported after the registry changed belong to another snapshot
+    client.add_issues(
+        &soroban_sdk_repo(&env),
+        &vec![&env, String::from_str(&env, "issue-1")],
+    );
+    let changed = client.export_chunk(&1, &first.checksum);
+    assert_eq!(
+        restored.try_import_chunk(&changed),
+        Err(Ok(Error::StateHashMismatch.into()))
+    );
+
+    restored.import_chunk(&second);
+    restored.import_chunk(&third);
+    assert_eq!(restored.try_import_chunk(&third), checksum_mismatch);
+    restored.finish_import();
+    assert_eq!(
+        restored.get_repos(),
+        vec![&env, soroban_cli_repo(&env), soroban_sdk_repo(&env)]
+    );
+}
+
+#[test]
+fn config_is_refused_while_importing() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, GithubOracleContract);
+    let client = GithubOracleContractClient::new(&env, &contract_id);
+    client.begin_import(&Address::generate(&env));
+
+    let result = client.try_config(&ConfigData {
+        admin: Address::generate(&env),
+        repos: vec![&env],
+        max_repos: 256,
+        max_issues_per_repo: 512,
+        repo_governance: RepoGovernance::Admin,
+    });
+    assert_eq!(result, Err(Ok(ExtendedError::ImportInProgress.into())));
+    assert!(client.try_begin_import(&Address::generate(&env)).is_err());
+}
+
+#[test]
+fn upgrader_takes_over_contract_updates() {
+    let (env, client, config) = setup_contract();
+    //native test contracts run under the hash of the empty wasm
+    let wasm_hash = env.deployer().upload_contract_wasm(Bytes::new(&env));
+    client.update_contract(&wasm_hash);
+    assert_eq!(env.auths()[0].0, config.admin);
+
+    let upgrader = Address::generate(&env);
+    client.set_upgrader(&Some(upgrader.clone()));
+    assert_eq!(client.get_upgrader(), Some(upgrader.clone()));
+    client.update_contract(&wasm_hash);
+    assert_eq!(env.auths()[0].0, upgrader);
+
+    client.set_upgrader(&None);
+    client.update_contract(&wasm_hash);
+    assert_eq!(env.auths()[0].0, config.admin);
+}
+
+#[test]
+fn issue_proofs_verify_against_the_state_root() {
+    let (env, client, config) = setup_contract();
+    let issue = setup_issue(&env, &client);
+    client.add_repos(&vec![&env, soroban_cli_repo(&env), digicus_repo(&env)]);
+    client.add_issues(
+        &soroban_cli_repo(&env),
+        &vec![
+            &env,
+            String::from_str(&env, "1"),
+            String::from_str(&env, "2"),
+            String::from_str(&env, "3"),
+        ],
+    );
+
+    let leaf = |status: &str, claimant: Option<Address>| IssueLeaf {
+        repo: soroban_sdk_repo(&env),
+        issue: issue.clone(),
+        status: String::from_str(&env, status),
+        claimant,
+    };
+    let proof = client.get_issue_proof(&soroban_sdk_repo(&env), &issue);
+    assert!(client.verify_issue_proof(&leaf("unclaimed", None), &proof));
+    let other_proof = client.get_issue_proof(&soroban_cli_repo(&env), &String::from_str(&env, "2"));
+    assert!(client.verify_issue_proof(
+        &IssueLeaf {
+            repo: soroban_cli_repo(&env),
+            issue: String::from_str(&env, "2"),
+            status: String::from_str(&env, "unclaimed"),
+            claimant: None,
+        },
+        &other_proof
+    ));
+    assert!(!client.verify_issue_proof(&leaf("unclaimed", None), &other_proof));
+
+    let root = client.state_root();
+    let contributor = Address::generate(&env);
+    client.complete_issue(
+        &config.admin,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &vec![&env, (contributor.clone(), 10_000)],
+    );
+    assert_ne!(client.state_root(), root);
+
+    //the old proof no longer holds, and the new one binds the claimant
+    assert!(!client.verify_issue_proof(&leaf("unclaimed", None), &proof));
+    let proof = client.get_issue_proof(&soroban_sdk_repo(&env), &issue);
+    assert!(client.verify_issue_proof(&leaf("completed", Some(contributor)), &proof));
+    assert!(!client.verify_issue_proof(&leaf("completed", Some(Address::generate(&env))), &proof));
+}
+
+#[test]
+fn state_root_depends_only_on_current_state() {
+    let (env, client, _config) = setup_contract();
+    let empty_root = client.state_root();
+    client.add_repos(&vec![&env, soroban_sdk_repo(&env)]);
+    let repo_root = client.state_root();
+    assert_ne!(repo_root, empty_root);
+
+    let issues = vec![&env, String::from_str(&env, "issue-1")];
+    client.add_issues(&soroban_sdk_repo(&env), &issues);
+    assert_ne!(client.state_root(), repo_root);
+    client.remove_issues(&soroban_sdk_repo(&env), &issues);
+    assert_eq!(client.state_root(), repo_root);
+
+    client.remove_repos(&vec![&env, soroban_sdk_repo(&env)]);
+    assert_eq!(client.state_root(), empty_root);
+}
+
+#[test]
+fn verified_repo_becomes_active_under_requester() {
+    let (env, client, _config) = setup_contract();
+    client.set_attestor(&Address::generate(&env));
+
+    let maintainer = Address::generate(&env);
+    let nonce =
+        client.request_repo_verification(&maintainer, &Forge::GitHub, &soroban_sdk_repo(&env));
+    assert_eq!(
+        client
+            .get_repo_challenge(&soroban_sdk_repo(&env), &maintainer)
+            .unwrap()
+            .nonce,
+        nonce
+    );
+    assert_eq!(
+        client.try_confirm_repo_verification(&soroban_sdk_repo(&env), &maintainer, &(nonce + 1)),
+        Err(Ok(Error::NonceMismatch.into()))
+    );
+
+    client.confirm_repo_verification(&soroban_sdk_repo(&env), &maintainer, &nonce);
+
+    assert_eq!(client.get_repos(), vec![&env, soroban_sdk_repo(&env)]);
+    assert_eq!(
+        client.get_repo_maintainer(&soroban_sdk_repo(&env)),
+        Some(maintainer.clone())
+    );
+    assert_eq!(
+        client.get_repo_challenge(&soroban_sdk_repo(&env), &maintainer),
+        None
+    );
+}
+
+#[test]
+fn other_forge_repos_can_be_verified() {
+    let (env, client, _config) = setup_contract();
+    client.set_attestor(&Address::generate(&env));
+    let gitlab_key = client.repo_key(&Forge::GitLab, &soroban_sdk_repo(&env));
+
+    let maintainer = Address::generate(&env);
+    let nonce =
+        client.request_repo_verification(&maintainer, &Forge::GitLab, &soroban_sdk_repo(&env));
+    client.confirm_repo_verification(&gitlab_key, &maintainer, &nonce);
+    assert_eq!(
+        client.get_repos_by_forge(&Forge::GitLab),
+        vec![&env, gitlab_key.clone()]
+    );
+    assert_eq!(client.get_repo_maintainer(&gitlab_key), Some(maintainer));
+}
+
+#[test]
+fn repo_challenges_are_kept_per_requester() {
+    let (env, client, _config) = setup_contract();
+    client.set_attestor(&Address::generate(&env));
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let first_nonce =
+        client.request_repo_verification(&first, &Forge::GitHub, &soroban_sdk_repo(&env));
+    let second_nonce =
+        client.request_repo_verification(&second, &Forge::GitHub, &soroban_sdk_repo(&env));
+
+    //renewing a challenge replaces the requester's own nonce and nobody else's
+    env.ledger().with_mut(|l| l.sequence_number += 17_000);
+    let renewed_nonce =
+        client.request_repo_verification(&first, &Forge::GitHub, &soroban_sdk_repo(&env));
+    assert_eq!(
+        client.try_confirm_repo_verification(&soroban_sdk_repo(&env), &first, &first_nonce),
+        Err(Ok(Error::NonceMismatch.into()))
+    );
+    client.confirm_repo_verification(&soroban_sdk_repo(&env), &second, &second_nonce);
+    assert_eq!(
+        client.get_repo_maintainer(&soroban_sdk_repo(&env)),
+        Some(second)
+    );
+    assert_eq!(
+        client.try_confirm_repo_verification(&soroban_sdk_repo(&env), &first, &renewed_nonce),
+        Err(Ok(Error::RepoAlreadyExists.into()))
+    );
+}
+
+#[test]
+fn expired_repo_challenge_cannot_be_confirmed() {
+    let (env, client, _config) = setup_contract();
+    client.set_attestor(&Address::generate(&env));
+
+    let requester = Address::generate(&env);
+    let nonce =
+        client.request_repo_verification(&requester, &Forge::GitHub, &soroban_sdk_repo(&env));
+    env.ledger().with_mut(|l| l.sequence_number += 17_281);
+    assert_eq!(
+        client.try_confirm_repo_verification(&soroban_sdk_repo(&env), &requester, &nonce),
+        Err(Ok(Error::ChallengeMissing.into()))
+    );
+}
+
+#[test]
+#[should_panic]
+fn repo_verification_requires_attestor() {
+    let (env, client, _config) = setup_contract();
+
+    let requester = Address::generate(&env);
+    let nonce =
+        client.request_repo_verification(&requester, &Forge::GitHub, &soroban_sdk_repo(&env));
+    client.confirm_repo_verification(&soroban_sdk_repo(&env), &requester, &nonce);
+}
+
+#[test]
+fn org_groups_repos_and_counts_issues() {
+    let (env, client, _config) = setup_contract();
+    let (org_name, _maintainer) = setup_org(&env, &client);
+
+    assert_eq!(client.get_orgs(), vec![&env, org_name.clone()]);
+    assert_eq!(
+        client.get_org_repos(&org_name),
+        vec![&env, soroban_sdk_repo(&env), soroban_cli_repo(&env)]
+    );
+    assert_eq!(
+        client.get_repo_org(&soroban_sdk_repo(&env)),
+        Some(org_name.clone())
+    );
+    assert_eq!(client.get_repo_org(&digicus_repo(&env)), None);
+
+    let counts = client.get_org_issue_counts(&org_name);
+    assert_eq!(counts.get(soroban_sdk_repo(&env)), Some(2));
+    assert_eq!(counts.get(soroban_cli_repo(&env)), Some(0));
+
+    client.remove_repos(&vec![&env, soroban_cli_repo(&env)]);
+    assert_eq!(
+        client.get_org_repos(&org_name),
+        vec![&env, soroban_sdk_repo(&env)]
+    );
+    assert!(client
+        .try_add_org_repos(&org_name, &vec![&env, soroban_sdk_repo(&env)])
+        .is_err());
+}
+
+#[test]
+fn org_maintainer_can_pause_all_org_repos() {
+    let (env, client, _config) = setup_contract();
+    let (org_name, maintainer) = setup_org(&env, &client);
+
+    assert!(client
+        .try_set_org_repos_state(&Address::generate(&env), &org_name, &RepoState::Paused)
+        .is_err());
+    client.set_org_repos_state(&maintainer, &org_name, &RepoState::Paused);
+
+    assert_eq!(
+        client.get_repo_state(&soroban_cli_repo(&env)),
+        RepoState::Paused
+    );
+    assert_eq!(
+        client.get_repo_state(&digicus_repo(&env)),
+        RepoState::Active
+    );
+    let issues = vec![&env, String::from_str(&env, "issue-3")];
+    assert!(client
+        .try_add_issues(&soroban_sdk_repo(&env), &issues)
+        .is_err());
+    client.add_issues(&digicus_repo(&env), &issues);
+
+    client.set_org_repos_state(&maintainer, &org_name, &RepoState::Active);
+    client.add_issues(&soroban_sdk_repo(&env), &issues);
+}
+
+#[test]
+fn org_maintainer_or_attestor_can_complete_issues() {
+    let (env, client, _config) = setup_contract();
+    let (_org_name, maintainer) = setup_org(&env, &client);
+    let attestor = Address::generate(&env);
+    client.set_attestor(&attestor);
+    let shares = vec![&env, (Address::generate(&env), 10_000)];
+    let (first, second) = (
+        String::from_str(&env, "issue-1"),
+        String::from_str(&env, "issue-2"),
+    );
+
+    let result = client.try_complete_issue(
+        &Address::generate(&env),
+        &soroban_sdk_repo(&env),
+        &first,
+        &shares,
+    );
+    assert_eq!(result, Err(Ok(Error::Unauthorized.into())));
+
+    client.complete_issue(&maintainer, &soroban_sdk_repo(&env), &first, &shares);
+    client.complete_issue(&attestor, &soroban_sdk_repo(&env), &second, &shares);
+    let issues = client.get_issues_for_repo(&soroban_sdk_repo(&env));
+    let completed = String::from_str(&env, "completed");
+    assert_eq!(issues.get(first), Some(completed.clone()));
+    assert_eq!(issues.get(second), Some(completed));
+}
+
+#[test]
+fn org_budget_funds_issue_escrow() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let (org_name, maintainer) = setup_org(&env, &client);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &1_000);
+    client.fund_org(&funder, &org_name, &token.address, &1_000);
+
+    let issue = String::from_str(&env, "issue-1");
+    client.fund_issue_from_org(
+        &maintainer,
+        &org_name,
+        &soroban_sdk_repo(&env),
+        &issue,
+        &400,
+    );
+    assert!(client
+        .try_fund_issue_from_org(
+            &maintainer,
+            &org_name,
+            &soroban_sdk_repo(&env),
+            &issue,
+            &601
+        )
+        .is_err());
+
+    assert_eq!(
+        client
+            .get_escrow(&soroban_sdk_repo(&env), &issue)
+            .unwrap()
+            .amount,
+        400
+    );
+    assert_eq!(client.get_org(&org_name).budget, 600);
+    assert_eq!(token.balance(&client.address), 1_000);
+}
+
+#[test]
+fn org_budget_can_be_withdrawn() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let (org_name, maintainer) = setup_org(&env, &client);
+
+    let funder = Address::generate(&env);
+    token_admin.mint(&funder, &1_000);
+    client.fund_org(&funder, &org_name, &token.address, &1_000);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_withdraw_org_budget(&outsider, &org_name, &outsider, &100);
+    assert_eq!(result, Err(Ok(Error::Unauthorized.into())));
+    let result = client.try_withdraw_org_budget(&maintainer, &org_name, &funder, &1_001);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount.into())));
+
+    client.withdraw_org_budget(&maintainer, &org_name, &funder, &400);
+    assert_eq!(client.get_org(&org_name).budget, 600);
+    assert_eq!(token.balance(&funder), 400);
+
+    client.withdraw_org_budget(&config.admin, &org_name, &funder, &600);
+    let org = client.get_org(&org_name);
+    assert_eq!((org.budget, org.budget_token), (0, None));
+    assert_eq!(token.balance(&funder), 1_000);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+fn setup_org(env: &Env, client: &GithubOracleContractClient) -> (String, Address) {
+    client.add_repos(&vec![
+        env,
+        soroban_sdk_repo(env),
+        soroban_cli_repo(env),
+        digicus_repo(env),
+    ]);
+    client.add_issues(
+        &soroban_sdk_repo(env),
+        &vec![
+            env,
+            String::from_str(env, "issue-1"),
+            String::from_str(env, "issue-2"),
+        ],
+    );
+
+    let org_name = String::from_str(env, "sdf");
+    let maintainer = Address::generate(env);
+    client.create_org(&org_name, &vec![env, maintainer.clone()]);
+    client.add_org_repos(
+        &org_name,
+        &vec![env, soroban_sdk_repo(env), soroban_cli_repo(env)],
+    );
+
+    (org_name, maintainer)
+}
+
+fn clear_repos(env: &Env, client: &GithubOracleContractClient) {
+    client.request_clear(&client.get_state_hash());
+    env.ledger().with_mut(|l| l.sequence_number += 17_280);
+    client.confirm_clear(&client.get_state_hash());
+}
+
+fn setup_voters(env: &Env, client: &GithubOracleContractClient) -> (Address, Address, Address) {
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+    let carol = Address::generate(env);
+    client.register_voters(&vec![env, alice.clone(), bob.clone(), carol.clone()]);
+
+    (alice, bob, carol)
+}
+
+fn setup_signers(env: &Env, client: &GithubOracleContractClient) -> (Address, Address, Address) {
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+    let carol = Address::generate(env);
+
+    let mut signers = Map::new(env);
+    signers.set(alice.clone(), 1);
+    signers.set(bob.clone(), 1);
+    signers.set(carol.clone(), 2);
+    client.set_signers(&SignerSet {
+        signers,
+        threshold: 2,
+        proposal_expiry_ledgers: 10,
+    });
+
+    (alice, bob, carol)
+}
+
+#[test]
+fn premium_reads_use_the_free_tier_then_charge_credit() {
+    let (env, client, config) = setup_contract();
+    let (token, token_admin) = setup_token(&env, &config.admin);
+    let issue = setup_issue(&env, &client);
+    let repo = soroban_sdk_repo(&env);
+    let consumer = Address::generate(&env);
+    token_admin.mint(&consumer, &100);
+
+    //reads are free until they are priced
+    client.get_issue_proof_paid(&consumer, &repo, &issue);
+    assert_eq!(client.get_read_usage(&consumer), None);
+    let result = client.try_deposit_read_credit(&consumer, &100);
+    assert_eq!(result, Err(Ok(ExtendedError::ReadPricingMissing.into())));
+
+    client.set_read_pricing(&Some(ReadPricing {
+        token: token.address.clone(),
+        fee: 15,
+        free_reads: 2,
+        period_ledgers: 100,
+    }));
+    client.deposit_read_credit(&consumer, &40);
+    assert_eq!(token.balance(&consumer), 60);
+
+    let leaf = IssueLeaf {
+        repo: repo.clone(),
+        issue: issue.clone(),
+        status: String::from_str(&env, "unclaimed"),
+        claimant: None,
+    };
+    let proof = client.get_issue_proof_paid(&consumer, &repo, &issue);
+    assert!(client.verify_issue_proof(&leaf, &proof));
+    assert_eq!(
+        client.get_read_credit(&consumer).get(token.address.clone()),
+        Some(40)
+    );
+    //the free proof is withdrawn while reads are priced, and verification is never charged
+    let result = client.try_get_issue_proof(&repo, &issue);
+    assert_eq!(result, Err(Ok(ExtendedError::PaidReadRequired.into())));
+    assert_eq!(client.get_read_usage(&consumer).unwrap().reads, 1);
+
+    for _ in 0..3 {
+        client.get_issue_proof_paid(&consumer, &repo, &issue);
+    }
+    assert_eq!(
+        client.get_read_credit(&consumer).get(token.address.clone()),
+        Some(10)
+    );
+    let result = client.try_get_issue_proof_paid(&consumer, &repo, &issue);
+    assert_eq!(result, Err(Ok(ExtendedError::InsufficientCredit.into())));
+
+    //the free tier starts over with the next period, and basic views stay free
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number += 100);
+    client.get_issue_proof_paid(&consumer, &repo, &issue);
+    assert_eq!(client.get_read_usage(&consumer).unwrap().reads, 1);
+    assert_eq!(client.get_repos(), vec![&env, repo]);
+
+    assert_eq!(
+        client.get_read_revenue().get(token.address.clone()),
+        Some(30)
+    );
+    let treasury = Address::generate(&env);
+    assert_eq!(client.withdraw_read_revenue(&token.address, &treasury), 30);
+    assert_eq!(token.balance(&treasury), 30);
+    let result = client.try_withdraw_read_revenue(&token.address, &treasury);
+    assert_eq!(result, Err(Ok(Error::NothingToWithdraw.into())));
+
+    client.withdraw_read_credit(&consumer, &token.address, &10);
+    assert_eq!(token.balance(&consumer), 70);
+    assert!(client.get_read_credit(&consumer).is_empty());
+}
+
+#[test]
+fn read_credit_survives_a_price_change() {
+    let (env, client, config) = setup_contract();
+    let (old_token, old_token_admin) = setup_token(&env, &config.admin);
+    let (new_token, _) = setup_token(&env, &config.admin);
+    let issue = setup_issue(&env, &client);
+    let consumer = Address::generate(&env);
+    old_token_admin.mint(&consumer, &50);
+
+    let pricing = ReadPricing {
+        token: old_token.address.clone(),
+        fee: 5,
+        free_reads: 0,
+        period_ledgers: 100,
+    };
+    client.set_read_pricing(&Some(pricing.clone()));
+    client.deposit_read_credit(&consumer, &50);
+    client.set_read_pricing(&Some(ReadPricing {
+        token: new_token.address.clone(),
+        ..pricing
+    }));
+
+    //old credit does not pay for reads in the new token, but can be withdrawn
+    let result = client.try_get_issue_proof_paid(&consumer, &soroban_sdk_repo(&env), &issue);
+    assert_eq!(result, Err(Ok(ExtendedError::InsufficientCredit.into())));
+    let result = client.try_withdraw_read_credit(&consumer, &old_token.address, &51);
+    assert_eq!(result, Err(Ok(ExtendedError::InsufficientCredit.into())));
+    client.withdraw_read_credit(&consumer, &old_token.address, &50);
+    assert_eq!(old_token.balance(&consumer), 50);
+
+    client.set_read_pricing(&None);
+    client.get_issue_proof_paid(&consumer, &soroban_sdk_repo(&env), &issue);
+    client.get_issue_proof(&soroban_sdk_repo(&env), &issue);
+}
+
+mod consumer {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env, String, Vec};
+
+    //records every update it is notified of
+    #[contract]
+    pub struct Consumer;
+
+    #[contractimpl]
+    impl Consumer {
+        pub fn on_oracle_update(e: Env, repo: String, issue: String, status: String) {
+            let mut updates = Self::updates(e.clone());
+            updates.push_back((repo, issue, status));
+            e.storage()
+                .instance()
+                .set(&symbol_short!("updates"), &updates);
+        }
+
+        pub fn updates(e: Env) -> Vec<(String, String, String)> {
+            e.storage()
+                .instance()
+                .get(&symbol_short!("updates"))
+                .unwrap_or_else(|| Vec::new(&e))
+        }
+    }
+}
+
+mod failing_consumer {
+    use soroban_sdk::{contract, contractimpl, Env, String};
+
+    #[contract]
+    pub struct FailingConsumer;
+
+    #[contractimpl]
+    impl FailingConsumer {
+        pub fn on_oracle_update(_e: Env, _repo: String, _issue: String, _status: String) {
+            panic!("the consumer is broken");
+        }
+    }
+}
+
+fn setup_subscriptions<'a>(
+    env: &Env,
+    client: &GithubOracleContractClient,
+    admin: &Address,
+) -> token::Client<'a> {
+    let (token, _token_admin) = setup_token(env, admin);
+    client.add_repos(&vec![env, soroban_sdk_repo(env)]);
+    client.add_issues(
+        &soroban_sdk_repo(env),
+        &vec![
+            env,
+            String::from_str(env, "1"),
+            String::from_str(env, "2"),
+            String::from_str(env, "3"),
+        ],
+    );
+    client.set_subscription_config(&SubscriptionConfig {
+        token: token.address.clone(),
+        fee: 10,
+        duration_ledgers: 1_000,
+    });
+    token
+}
+
+fn register_consumer(
+    env: &Env,
+    token: &token::Client,
+    contract: impl ContractFunctionSet + 'static,
+) -> Address {
+    let consumer = env.register_contract(None, contract);
+    token::StellarAssetClient::new(env, &token.address).mint(&consumer, &100);
+    consumer
+}
+
+#[test]
+fn subscribers_are_notified_of_matching_completions() {
+    let (env, client, config) = setup_contract();
+    let token = setup_subscriptions(&env, &client, &config.admin);
+    let repo = soroban_sdk_repo(&env);
+    let issue = |name: &str| String::from_str(&env, name);
+
+    let any = register_consumer(&env, &token, consumer::Consumer);
+    let watcher = register_consumer(&env, &token, consumer::Consumer);
+    let any_id = client.subscribe(&any, &repo, &None);
+    let watcher_id = client.subscribe(&watcher, &repo, &Some(issue("2")));
+    assert_eq!(token.balance(&config.admin), 20);
+    assert_eq!(client.get_subscriptions(&repo).len(), 2);
+
+    //completing only queues the update, dispatching it calls the subscriber
+    client.complete_issue(
+        &config.admin,
+        &repo,
+        &issue("1"),
+        &vec![&env, (Address::generate(&env), 10_000)],
+    );
+    assert!(consumer::ConsumerClient::new(&env, &any)
+        .updates()
+        .is_empty());
+    assert_eq!(client.get_notification(&0).subscriber, any);
+    assert!(client.dispatch_notification(&0));
+    let completed = String::from_str(&env, "completed");
+    assert_eq!(
+        consumer::ConsumerClient::new(&env, &any).updates(),
+        vec![&env, (repo.clone(), issue("1"), completed.clone())]
+    );
+    assert!(consumer::ConsumerClient::new(&env, &watcher)
+        .updates()
+        .is_empty());
+    assert_eq!(
+        client.try_dispatch_notification(&0),
+        Err(Ok(ExtendedError::NotificationMissing.into()))
+    );
+
+    //an issue subscription ends once its issue completes
+    client.complete_issue(
+        &config.admin,
+        &repo,
+        &issue("2"),
+        &vec![&env, (Address::generate(&env), 10_000)],
+    );
+    assert!(client.dispatch_notification(&1));
+    assert!(client.dispatch_notification(&2));
+    assert_eq!(
+        consumer::ConsumerClient::new(&env, &watcher).updates(),
+        vec![&env, (repo.clone(), issue("2"), completed)]
+    );
+    assert_eq!(consumer::ConsumerClient::new(&env, &any).updates().len(), 2);
+    assert_eq!(
+        client.try_get_subscription(&watcher_id),
+        Err(Ok(ExtendedError::SubscriptionMissing.into()))
+    );
+
+    //renames carry subscriptions along
+    let renamed = String::from_str(&env, "stellar/rs-soroban-sdk");
+    client.rename_repo(&repo, &renamed);
+    assert_eq!(client.get_subscription(&any_id).repo, renamed);
+    client.complete_issue(
+        &config.admin,
+        &renamed,
+        &issue("3"),
+        &vec![&env, (Address::generate(&env), 10_000)],
+    );
+    assert!(client.dispatch_notification(&3));
+    assert_eq!(consumer::ConsumerClient::new(&env, &any).updates().len(), 3);
+}
+
+#[test]
+fn failing_callbacks_do_not_block_completion() {
+    let (env, client, config) = setup_contract();
+    let token = setup_subscriptions(&env, &client, &config.admin);
+    let repo = soroban_sdk_repo(&env);
+
+    let broken = register_consumer(&env, &token, failing_consumer::FailingConsumer);
+    let subscription_id = client.subscribe(&broken, &repo, &None);
+
+    for (completed, issue) in ["1", "2"].iter().enumerate() {
+        client.complete_issue(
+            &config.admin,
+            &repo,
+            &String::from_str(&env, issue),
+            &vec![&env, (Address::generate(&env), 10_000)],
+        );
+        assert!(!client.dispatch_notification(&(completed as u32)));
+        assert_eq!(
+            client.get_subscription(&subscription_id).failures,
+            completed as u32 + 1
+        );
+    }
+
+    //the third failure in a row drops the subscription
+    client.complete_issue(
+        &config.admin,
+        &repo,
+        &String::from_str(&env, "3"),
+        &vec![&env, (Address::generate(&env), 10_000)],
+    );
+    assert!(!client.dispatch_notification(&2));
+    assert!(client.get_subscriptions(&repo).is_empty());
+    assert_eq!(client.repo_stats(&repo).completed_issues, 3);
+}
+
+#[test]
+fn notifications_of_removed_subscriptions_are_discarded() {
+    let (env, client, config) = setup_contract();
+    let token = setup_subscriptions(&env, &client, &config.admin);
+    let repo = soroban_sdk_repo(&env);
+
+    let consumer = register_consumer(&env, &token, consumer::Consumer);
+    let subscription_id = client.subscribe(&consumer, &repo, &None);
+    client.complete_issue(
+        &config.admin,
+        &repo,
+        &String::from_str(&env, "1"),
+        &vec![&env, (Address::generate(&env), 10_000)],
+    );
+    client.remove_subscription(&subscription_id);
+
+    assert!(!client.dispatch_notification(&0));
+    assert!(consumer::ConsumerClient::new(&env, &consumer)
+        .updates()
+        .is_empty());
+    assert_eq!(
+        client.try_get_notification(&0),
+        Err(Ok(ExtendedError::NotificationMissing.into()))
+    );
+}
+
+#[test]
+fn subscriptions_are_paid_capped_and_expire() {
+    let (env, client, config) = setup_contract();
+    let repo = soroban_sdk_repo(&env);
+    let result = client.try_subscribe(&Address::generate(&env), &repo, &None);
+    assert_eq!(result, Err(Ok(ExtendedError::SubscriptionsDisabled.into())));
+
+    let token = setup_subscriptions(&env, &client, &config.admin);
+    let result = client.try_set_subscription_config(&SubscriptionConfig {
+        token: token.address.clone(),
+        fee: 0,
+        duration_ledgers: 1_000,
+    });
+    assert_eq!(result, Err(Ok(Error::InvalidAmount.into())));
+    let result = client.try_subscribe(
+        &Address::generate(&env),
+        &repo,
+        &Some(String::from_str(&env, "missing")),
+    );
+    assert_eq!(result, Err(Ok(Error::IssueMissing.into())));
+
+    let consumer = register_consumer(&env, &token, consumer::Consumer);
+    for _ in 0..MAX_SUBSCRIPTIONS_PER_REPO {
+        client.subscribe(&consumer, &repo, &None);
+    }
+    assert_eq!(
+        token.balance(&consumer),
+        100 - 10 * MAX_SUBSCRIPTIONS_PER_REPO as i128
+    );
+    let result = client.try_subscribe(&consumer, &repo, &None);
+    assert_eq!(
+        result,
+        Err(Ok(ExtendedError::SubscriptionLimitExceeded.into()))
+    );
+
+    client.unsubscribe(&0);
+    client.remove_subscription(&1);
+    assert_eq!(
+        client.get_subscriptions(&repo).len(),
+        MAX_SUBSCRIPTIONS_PER_REPO - 2
+    );
+
+    //expired subscriptions are dropped instead of notified
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number += 1_001);
+    client.complete_issue(
+        &config.admin,
+        &repo,
+        &String::from_str(&env, "1"),
+        &vec![&env, (Address::generate(&env), 10_000)],
+    );
+    assert!(client.get_subscriptions(&repo).is_empty());
+    assert_eq!(
+        client.try_get_notification(&0),
+        Err(Ok(ExtendedError::NotificationMissing.into()))
+    );
+}
+
+fn setup_token<'a>(
+    env: &Env,
+    admin: &Address,
+) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
+    let token_address = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    (
+        token::Client::new(env, &token_address),
+        token::StellarAssetClient::new(env, &token_address),
+    )
+}
+
+fn setup_issue(env: &Env, client: &GithubOracleContractClient) -> String {
+    let issue = String::from_str(env, "issue-1");
+    client.add_repos(&vec![env, soroban_sdk_repo(env)]);
+    client.add_issues(&soroban_sdk_repo(env), &vec![env, issue.clone()]);
+
+    issue
+}
+
 fn assert_repo_names(actual: Vec<String>, expected: Vec<String>) {
     assert_eq!(actual.len(), expected.len());
 
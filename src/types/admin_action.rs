@@ -0,0 +1,32 @@
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+
+use crate::types::limits::Limits;
+use crate::types::signer_set::SignerSet;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    RequestClear(BytesN<32>),
+    ConfirmClear(BytesN<32>),
+    UpdateContract(BytesN<32>),
+    SetAdmin(Address),
+    SetSigners(SignerSet),
+    RemoveSigners,
+    SetLimits(Limits),
+    SetRepoIssueLimit(String, Option<u32>),
+    SetUpgrader(Option<Address>),
+    SetAttestor(Address),
+    RegisterVoters(Vec<Address>),
+    RemoveVoters(Vec<Address>),
+    SetOrgMaintainers(String, Vec<Address>),
+    SetDustRecipient(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub id: u32,
+    pub action: AdminAction,
+    pub approvals: Vec<Address>,
+    pub expires_at_ledger: u32,
+}
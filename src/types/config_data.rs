@@ -1,8 +1,13 @@
 use soroban_sdk::{contracttype, Address, String, Vec};
 
+use crate::types::repo_governance::RepoGovernance;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ConfigData {
     pub admin: Address,
     pub repos: Vec<String>,
+    pub max_repos: u32,
+    pub max_issues_per_repo: u32,
+    pub repo_governance: RepoGovernance,
 }
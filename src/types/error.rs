@@ -1,7 +1,9 @@
+use core::fmt;
+
 use soroban_sdk::contracterror;
 
 #[contracterror]
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Error {
     AlreadyInitialized = 0,
     Unauthorized = 1,
@@ -11,4 +13,167 @@ pub enum Error {
     IssueAlreadyExists = 5,
     IssueLimitExceeded = 6,
     IssueMissing = 7,
+    IssueAlreadyCompleted = 8,
+    InvalidShares = 9,
+    InvalidAmount = 10,
+    EscrowTokenMismatch = 11,
+    GrantMissing = 12,
+    InvalidGrant = 13,
+    GrantCancelled = 14,
+    InvalidVestingSchedule = 15,
+    VestingMissing = 16,
+    NothingToWithdraw = 17,
+    ProposalsDisabled = 18,
+    ProposalMissing = 19,
+    ProposalAlreadyExists = 20,
+    ProposalExpired = 21,
+    ProposalNotExpired = 22,
+    InvalidLimits = 23,
+    MultisigRequired = 24,
+    MultisigNotConfigured = 25,
+    InvalidSignerSet = 26,
+    NotSigner = 27,
+    AdminProposalMissing = 28,
+    AdminProposalExpired = 29,
+    // reserved: executed admin proposals are removed, so they read as missing
+    AdminProposalExecuted = 30,
+    ThresholdNotMet = 31,
+    RepoVotingActive = 32,
+    RepoVotingDisabled = 33,
+    InvalidVotingConfig = 34,
+    NotVoter = 35,
+    AlreadyVoted = 36,
+    VotingClosed = 37,
+    VotingOpen = 38,
+    RepoProposalMissing = 39,
+    // reserved: executed repo proposals are removed, so they read as missing
+    RepoProposalExecuted = 40,
+    RepoProposalRejected = 41,
+    ClearRequestMissing = 42,
+    ClearDelayPending = 43,
+    StateHashMismatch = 44,
+    FundsEscrowed = 45,
+    AttestorMissing = 46,
+    ChallengeMissing = 47,
+    // reserved: challenges are kept per requester, so a new one never waits on another
+    ChallengePending = 48,
+    NonceMismatch = 49,
+}
+
+// a contract error enum holds at most 50 cases, so codes from 50 on continue here
+#[contracterror]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExtendedError {
+    InvalidRepoName = 50,
+    OrgMissing = 51,
+    OrgAlreadyExists = 52,
+    RepoInactive = 53,
+    InvalidLabels = 54,
+    IssueBlocked = 55,
+    DependencyCycle = 56,
+    ImportMissing = 57,
+    ImportInProgress = 58,
+    ChecksumMismatch = 59,
+    SubscriptionsDisabled = 60,
+    SubscriptionMissing = 61,
+    SubscriptionLimitExceeded = 62,
+    ReadPricingMissing = 63,
+    InsufficientCredit = 64,
+    NotificationMissing = 65,
+    PaidReadRequired = 66,
+}
+
+impl Error {
+    pub fn message(&self) -> &'static str {
+        match self {
+            Error::AlreadyInitialized => "the oracle is already configured",
+            Error::Unauthorized => "the caller is not allowed to do this",
+            Error::RepoMissing => "the repo is not tracked",
+            Error::RepoAlreadyExists => "the repo is already tracked",
+            Error::RepoLimitExceeded => "too many repos are tracked",
+            Error::IssueAlreadyExists => "the issue is already tracked",
+            Error::IssueLimitExceeded => "the repo tracks too many issues",
+            Error::IssueMissing => "the issue is not tracked",
+            Error::IssueAlreadyCompleted => "the issue is already completed",
+            Error::InvalidShares => "payout shares must be non-zero and total 10000 basis points",
+            Error::InvalidAmount => "the amount is zero, negative or otherwise invalid",
+            Error::EscrowTokenMismatch => "the issue is escrowed in a different token",
+            Error::GrantMissing => "no grant with this id",
+            Error::InvalidGrant => "a grant needs milestones with issues and a positive amount",
+            Error::GrantCancelled => "the grant was cancelled",
+            Error::InvalidVestingSchedule => "the vesting schedule is invalid",
+            Error::VestingMissing => "the issue has no vesting payout",
+            Error::NothingToWithdraw => "there is nothing to withdraw or refund",
+            Error::ProposalsDisabled => "issue proposals are not enabled",
+            Error::ProposalMissing => "no proposal with this id",
+            Error::ProposalAlreadyExists => "the issue has already been proposed",
+            Error::ProposalExpired => "the issue proposal has expired",
+            Error::ProposalNotExpired => "the proposal has not expired yet",
+            Error::InvalidLimits => "limits must be non-zero",
+            Error::MultisigRequired => "this action must be proposed to the signer set",
+            Error::MultisigNotConfigured => "no signer set is configured",
+            Error::InvalidSignerSet => "the signer set is invalid",
+            Error::NotSigner => "the caller is not a signer",
+            Error::AdminProposalMissing => "no admin proposal with this id",
+            Error::AdminProposalExpired => "the admin proposal has expired",
+            Error::AdminProposalExecuted => "the admin proposal was already executed",
+            Error::ThresholdNotMet => "the approvals do not meet the signer threshold",
+            Error::RepoVotingActive => "repo changes must go through a vote",
+            Error::RepoVotingDisabled => "repo voting is not enabled",
+            Error::InvalidVotingConfig => "the voting config is invalid",
+            Error::NotVoter => "the caller is not a voter",
+            Error::AlreadyVoted => "the voter has already voted",
+            Error::VotingClosed => "the voting period has ended",
+            Error::VotingOpen => "the voting period has not ended",
+            Error::RepoProposalMissing => "no repo proposal with this id",
+            Error::RepoProposalExecuted => "the repo proposal was already executed",
+            Error::RepoProposalRejected => "the repo proposal did not pass",
+            Error::ClearRequestMissing => "no clear has been requested",
+            Error::ClearDelayPending => "the clear delay has not passed",
+            Error::StateHashMismatch => "the registry changed since the state hash was taken",
+            Error::FundsEscrowed => "funds are still escrowed",
+            Error::AttestorMissing => "no attestor is configured",
+            Error::ChallengeMissing => "the repo has no open verification challenge",
+            Error::ChallengePending => "another verification challenge is still open",
+            Error::NonceMismatch => "the verification nonce does not match",
+        }
+    }
+}
+
+impl ExtendedError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            ExtendedError::InvalidRepoName => "the repo name is empty, too long or contains `:`",
+            ExtendedError::OrgMissing => "the org does not exist",
+            ExtendedError::OrgAlreadyExists => "the org already exists",
+            ExtendedError::RepoInactive => "the repo is paused or deprecated",
+            ExtendedError::InvalidLabels => "too many labels, or an empty label",
+            ExtendedError::IssueBlocked => "the issue is blocked by an incomplete issue",
+            ExtendedError::DependencyCycle => "the dependency would create a cycle",
+            ExtendedError::ImportMissing => "no import has been started",
+            ExtendedError::ImportInProgress => "the contract is still importing state",
+            ExtendedError::ChecksumMismatch => "the chunk does not match its checksum",
+            ExtendedError::SubscriptionsDisabled => "subscriptions are not enabled",
+            ExtendedError::SubscriptionMissing => "no subscription with this id",
+            ExtendedError::SubscriptionLimitExceeded => "the repo has too many subscriptions",
+            ExtendedError::ReadPricingMissing => "reads are not priced",
+            ExtendedError::InsufficientCredit => {
+                "the consumer's read credit does not cover the fee"
+            }
+            ExtendedError::NotificationMissing => "no queued notification with this id",
+            ExtendedError::PaidReadRequired => "reads are priced, use the paid entrypoint",
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl fmt::Display for ExtendedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message())
+    }
 }
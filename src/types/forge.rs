@@ -0,0 +1,25 @@
+use soroban_sdk::contracttype;
+
+// GitHub repos keep their bare `owner/name` keys, every other forge is
+// stored as `<prefix>owner/name` so the same path on two forges never collides.
+// Issues are kept under the per-repo number the forge shows for them (the IID on GitLab,
+// not its global id). Pull and merge requests never reach the contract, only the payouts
+// they trigger do, so telling them apart is left to whatever relays them
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Forge {
+    GitHub = 0,
+    GitLab = 1,
+    // Gitea / Forgejo (Codeberg)
+    Gitea = 2,
+}
+
+impl Forge {
+    pub fn key_prefix(&self) -> &'static str {
+        match self {
+            Forge::GitHub => "",
+            Forge::GitLab => "gitlab:",
+            Forge::Gitea => "gitea:",
+        }
+    }
+}
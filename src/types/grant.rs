@@ -0,0 +1,42 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+use crate::types::issue_ref::IssueRef;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub issues: Vec<IssueRef>,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrantData {
+    pub funder: Address,
+    pub grantee: Address,
+    pub token: Address,
+    pub milestones: Vec<Milestone>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grant {
+    pub funder: Address,
+    pub grantee: Address,
+    pub token: Address,
+    pub milestones: Vec<Milestone>,
+    // milestones are released in order, so this is also the index of the next one
+    pub released_milestones: u32,
+    pub cancelled: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrantProgress {
+    pub released_milestones: u32,
+    pub total_milestones: u32,
+    pub completed_issues: u32,
+    pub total_issues: u32,
+    pub released_amount: i128,
+    pub total_amount: i128,
+}
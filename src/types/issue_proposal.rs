@@ -0,0 +1,21 @@
+use soroban_sdk::{contracttype, Address, String};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalDepositConfig {
+    pub token: Address,
+    pub amount: i128,
+    pub expiry_ledgers: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssueProposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub repo: String,
+    pub issue: String,
+    pub token: Address,
+    pub deposit: i128,
+    pub expires_at_ledger: u32,
+}
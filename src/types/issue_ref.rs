@@ -0,0 +1,8 @@
+use soroban_sdk::{contracttype, String};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssueRef {
+    pub repo: String,
+    pub issue: String,
+}
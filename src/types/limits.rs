@@ -0,0 +1,8 @@
+use soroban_sdk::contracttype;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Limits {
+    pub max_repos: u32,
+    pub max_issues_per_repo: u32,
+}
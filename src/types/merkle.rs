@@ -0,0 +1,114 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, String, Vec};
+
+// domain separation keeps a leaf from ever hashing like an inner node
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssueLeaf {
+    pub repo: String,
+    pub issue: String,
+    pub status: String,
+    // the first contributor in the payout shares, once completed
+    pub claimant: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleStep {
+    pub sibling: BytesN<32>,
+    pub sibling_on_left: bool,
+}
+
+pub fn hash_leaf(e: &Env, leaf: &IssueLeaf) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(e, &[LEAF_PREFIX]);
+    preimage.append(&leaf.clone().to_xdr(e));
+    e.crypto().sha256(&preimage).to_bytes()
+}
+
+// a repo's leaf in the state root binds its name to the root of its issues,
+// so tracked repos are committed even while they have no issues
+pub fn repo_leaf(e: &Env, repo: &String, issues_root: &BytesN<32>) -> BytesN<32> {
+    hash_node(e, &repo_step(e, repo).sibling, issues_root)
+}
+
+pub fn repo_step(e: &Env, repo: &String) -> MerkleStep {
+    let mut preimage = Bytes::from_array(e, &[LEAF_PREFIX]);
+    preimage.append(&repo.clone().to_xdr(e));
+    MerkleStep {
+        sibling: e.crypto().sha256(&preimage).to_bytes(),
+        sibling_on_left: true,
+    }
+}
+
+pub fn empty_root(e: &Env) -> BytesN<32> {
+    BytesN::from_array(e, &[0; 32])
+}
+
+// an odd node out is carried up to the next level unchanged
+pub fn merkle_root(e: &Env, leaves: &Vec<BytesN<32>>) -> BytesN<32> {
+    if leaves.is_empty() {
+        return empty_root(e);
+    }
+
+    let mut level = leaves.clone();
+    while level.len() > 1 {
+        level = next_level(e, &level);
+    }
+    level.get_unchecked(0)
+}
+
+pub fn merkle_path(e: &Env, leaves: &Vec<BytesN<32>>, mut index: u32) -> Vec<MerkleStep> {
+    let mut path = Vec::new(e);
+    let mut level = leaves.clone();
+    while level.len() > 1 {
+        let sibling_index = index ^ 1;
+        if sibling_index < level.len() {
+            path.push_back(MerkleStep {
+                sibling: level.get_unchecked(sibling_index),
+                sibling_on_left: sibling_index < index,
+            });
+        }
+        level = next_level(e, &level);
+        index /= 2;
+    }
+    path
+}
+
+pub fn fold_path(e: &Env, mut hash: BytesN<32>, path: &Vec<MerkleStep>) -> BytesN<32> {
+    for step in path.iter() {
+        hash = if step.sibling_on_left {
+            hash_node(e, &step.sibling, &hash)
+        } else {
+            hash_node(e, &hash, &step.sibling)
+        };
+    }
+    hash
+}
+
+fn next_level(e: &Env, level: &Vec<BytesN<32>>) -> Vec<BytesN<32>> {
+    let mut next = Vec::new(e);
+    let mut index = 0;
+    while index < level.len() {
+        if index + 1 < level.len() {
+            next.push_back(hash_node(
+                e,
+                &level.get_unchecked(index),
+                &level.get_unchecked(index + 1),
+            ));
+        } else {
+            next.push_back(level.get_unchecked(index));
+        }
+        index += 2;
+    }
+    next
+}
+
+fn hash_node(e: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(e, &[NODE_PREFIX]);
+    preimage.extend_from_array(&left.to_array());
+    preimage.extend_from_array(&right.to_array());
+    e.crypto().sha256(&preimage).to_bytes()
+}
@@ -1,2 +1,21 @@
+pub mod admin_action;
 pub mod config_data;
 pub mod error;
+pub mod escrow;
+pub mod forge;
+pub mod grant;
+pub mod issue_proposal;
+pub mod issue_ref;
+pub mod limits;
+pub mod merkle;
+pub mod org;
+pub mod read_pricing;
+pub mod record;
+pub mod registry_archive;
+pub mod repo_challenge;
+pub mod repo_governance;
+pub mod repo_state;
+pub mod signer_set;
+pub mod stats;
+pub mod subscription;
+pub mod vesting;
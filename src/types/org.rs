@@ -0,0 +1,12 @@
+use soroban_sdk::{contracttype, Address, String, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Org {
+    pub name: String,
+    pub maintainers: Vec<Address>,
+    pub repos: Vec<String>,
+    // the budget token is fixed by the first deposit
+    pub budget_token: Option<Address>,
+    pub budget: i128,
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadPricing {
+    pub token: Address,
+    // charged per premium read once the free tier is used up
+    pub fee: i128,
+    pub free_reads: u32,
+    pub period_ledgers: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadUsage {
+    pub period_start_ledger: u32,
+    pub reads: u32,
+}
@@ -0,0 +1,88 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, Map, String, Vec};
+
+use crate::types::escrow::Escrow;
+use crate::types::grant::Grant;
+use crate::types::issue_proposal::ProposalDepositConfig;
+use crate::types::issue_ref::IssueRef;
+use crate::types::limits::Limits;
+use crate::types::org::Org;
+use crate::types::read_pricing::ReadPricing;
+use crate::types::repo_governance::RepoGovernance;
+use crate::types::repo_state::RepoState;
+use crate::types::signer_set::SignerSet;
+use crate::types::stats::Stats;
+use crate::types::subscription::{Subscription, SubscriptionConfig};
+use crate::types::vesting::{Vesting, VestingSchedule};
+
+// one piece of exported state, replayed as-is by `import_chunk`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Record {
+    // roles, only granted once the import is finished
+    Admin(Address),
+    Signers(SignerSet),
+    Attestor(Address),
+    Upgrader(Address),
+    Voters(Vec<Address>),
+    // settings
+    Limits(Limits),
+    RepoGovernance(RepoGovernance),
+    DustRecipient(Address),
+    ProposalDeposit(ProposalDepositConfig),
+    SubscriptionConfig(SubscriptionConfig),
+    ReadPricing(ReadPricing),
+    // read fees held for the admin, per token
+    ReadRevenue(Map<Address, i128>),
+    GlobalStats(Stats),
+    Org(Org),
+    // a repo with its issues and their statuses
+    Repo(String, Map<String, String>),
+    RepoState(String, RepoState),
+    RepoMaintainer(String, Address),
+    RepoIssueLimit(String, u32),
+    RepoStats(String, Stats),
+    Escrow(String, String, Escrow),
+    Claimant(String, String, Address),
+    Labels(String, String, Vec<String>),
+    Blockers(IssueRef, Vec<IssueRef>),
+    Subscription(Subscription),
+    Vesting(String, String, Vesting),
+    VestingSchedule(String, String, VestingSchedule),
+    Grant(u32, Grant),
+}
+
+// one page of `export_chunk`, handed to `import_chunk` as-is
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportChunk {
+    pub cursor: u32,
+    pub records: Vec<Record>,
+    pub next_cursor: Option<u32>,
+    // `chunk_checksum` of the records, chained from the checksum passed to `export_chunk`
+    pub checksum: BytesN<32>,
+    // state hash of the exporting contract, the same on every page of one snapshot
+    pub state_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportState {
+    pub importer: Address,
+    // chained over every imported chunk, see `chunk_checksum`
+    pub checksum: BytesN<32>,
+    pub chunks: u32,
+    pub deferred_roles: Vec<Record>,
+    // state hash every chunk was exported at, taken from the first one
+    pub state_hash: BytesN<32>,
+    // whether the last chunk, the one without a next cursor, was imported
+    pub complete: bool,
+}
+
+// checksum `import_chunk` expects for `records` after the chunks summed up in
+// `previous`, which is all zeros for the first chunk
+pub fn chunk_checksum(e: &Env, previous: &BytesN<32>, records: &Vec<Record>) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(e, &previous.to_array());
+    preimage.append(&records.clone().to_xdr(e));
+    e.crypto().sha256(&preimage).to_bytes()
+}
@@ -0,0 +1,16 @@
+use soroban_sdk::{contracttype, BytesN, Map, String};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClearRequest {
+    pub state_hash: BytesN<32>,
+    pub executable_at_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistryArchive {
+    pub repos: Map<String, Map<String, String>>,
+    pub state_hash: BytesN<32>,
+    pub archived_at_ledger: u32,
+}
@@ -0,0 +1,9 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RepoChallenge {
+    pub requester: Address,
+    pub nonce: u64,
+    pub expires_at_ledger: u32,
+}
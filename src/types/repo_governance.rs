@@ -0,0 +1,41 @@
+use soroban_sdk::{contracttype, Address, Map, String, Vec};
+
+use crate::types::forge::Forge;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotingConfig {
+    pub voting_period_ledgers: u32,
+    pub quorum: i128,
+    // when set, votes are weighted by the voter's balance of this token
+    pub weight_token: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RepoGovernance {
+    Admin,
+    Voting(VotingConfig),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RepoChange {
+    // a repo name on its forge, keyed the way `add_forge_repos` keys it
+    Add(Forge, String),
+    // a registry key, as returned by `get_repos`
+    Remove(String),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RepoProposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub change: RepoChange,
+    pub weights: Map<Address, i128>,
+    pub voted: Vec<Address>,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub end_ledger: u32,
+}
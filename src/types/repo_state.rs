@@ -0,0 +1,11 @@
+use soroban_sdk::contracttype;
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RepoState {
+    Active = 0,
+    // no new issues, funding or payouts until resumed
+    Paused = 1,
+    // read-only, already vested payouts can still be withdrawn
+    Archived = 2,
+}
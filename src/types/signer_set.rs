@@ -0,0 +1,9 @@
+use soroban_sdk::{contracttype, Address, Map};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerSet {
+    pub signers: Map<Address, u32>,
+    pub threshold: u32,
+    pub proposal_expiry_ledgers: u32,
+}
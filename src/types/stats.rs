@@ -0,0 +1,38 @@
+use soroban_sdk::{contracttype, Address, Env, Map};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub unclaimed_issues: u32,
+    pub completed_issues: u32,
+    pub escrowed: Map<Address, i128>,
+    pub paid_out: Map<Address, i128>,
+    // addresses holding the claim on at least one tracked issue
+    pub active_claimants: u32,
+}
+
+impl Stats {
+    pub fn new(e: &Env) -> Stats {
+        Stats {
+            unclaimed_issues: 0,
+            completed_issues: 0,
+            escrowed: Map::new(e),
+            paid_out: Map::new(e),
+            active_claimants: 0,
+        }
+    }
+
+    pub fn add_escrowed(&mut self, token: &Address, amount: i128) {
+        let escrowed = self.escrowed.get(token.clone()).unwrap_or(0) + amount;
+        if escrowed == 0 {
+            self.escrowed.remove(token.clone());
+        } else {
+            self.escrowed.set(token.clone(), escrowed);
+        }
+    }
+
+    pub fn add_paid_out(&mut self, token: &Address, amount: i128) {
+        let paid_out = self.paid_out.get(token.clone()).unwrap_or(0) + amount;
+        self.paid_out.set(token.clone(), paid_out);
+    }
+}
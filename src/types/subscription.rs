@@ -0,0 +1,35 @@
+use soroban_sdk::{contracttype, Address, String};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionConfig {
+    pub token: Address,
+    pub fee: i128,
+    pub duration_ledgers: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub id: u32,
+    // the contract whose `on_oracle_update` is called
+    pub subscriber: Address,
+    pub repo: String,
+    // none to be notified of every completion in the repo
+    pub issue: Option<String>,
+    pub expires_at_ledger: u32,
+    // consecutive callbacks that failed
+    pub failures: u32,
+}
+
+// a completion waiting for `dispatch_notification` to call its subscriber
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Notification {
+    pub id: u32,
+    pub subscription_id: u32,
+    pub subscriber: Address,
+    pub repo: String,
+    pub issue: String,
+    pub status: String,
+}
@@ -0,0 +1,24 @@
+use soroban_sdk::{contracttype, Address, Map};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub upfront_basis_points: u32,
+    pub duration_ledgers: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestedShare {
+    pub total: i128,
+    pub withdrawn: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Vesting {
+    pub token: Address,
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+    pub shares: Map<Address, VestedShare>,
+}